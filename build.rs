@@ -73,11 +73,23 @@ impl Parser<'_> {
 }
 
 fn main() {
-  let parsers = vec![Parser {
-    name: "tree-sitter-just",
-    src: "vendor/tree-sitter-just-src",
-    extra: vec!["scanner.c"],
-  }];
+  let parsers = vec![
+    Parser {
+      name: "tree-sitter-bash",
+      src: "vendor/tree-sitter-bash-src",
+      extra: vec!["scanner.c"],
+    },
+    Parser {
+      name: "tree-sitter-just",
+      src: "vendor/tree-sitter-just-src",
+      extra: vec!["scanner.c"],
+    },
+    Parser {
+      name: "tree-sitter-python",
+      src: "vendor/tree-sitter-python-src",
+      extra: vec!["scanner.c"],
+    },
+  ];
 
   for parser in &parsers {
     println!("cargo:rerun-if-changed={}", parser.src);
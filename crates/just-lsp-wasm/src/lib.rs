@@ -30,6 +30,7 @@ pub fn analyze(source: &str) -> Result<JsValue, JsError> {
       config: None,
       document: &document,
       imported_documents: Vec::new(),
+      project: None,
     }
     .analyze()
     .into_iter()
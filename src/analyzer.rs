@@ -5,18 +5,25 @@ pub struct Analyzer<'a> {
   pub config: Option<&'a Config>,
   pub document: &'a Document,
   pub imported_documents: Vec<&'a Document>,
+  /// The project rooted at `document`, whose recorded imports rules about
+  /// the import graph are based on.
+  pub project: Option<&'a Project>,
 }
 
 impl Analyzer<'_> {
-  /// Run all registered rules against the document.
+  /// Run all registered rules, followed by any custom rules from the
+  /// config, against the document.
   ///
   /// Rules that return `None` from `severity()` are filtered out, so
   /// config can suppress individual rules entirely. Diagnostics are
   /// sorted by position then message for deterministic output.
   #[must_use]
   pub fn analyze(&self) -> Vec<Diagnostic> {
-    let context =
-      RuleContext::new(self.document, self.imported_documents.iter().copied());
+    let context = RuleContext::new(
+      self.document,
+      self.imported_documents.iter().copied(),
+      self.project,
+    );
 
     let default = Config::default();
 
@@ -46,6 +53,27 @@ impl Analyzer<'_> {
       })
       .collect::<Vec<_>>();
 
+    diagnostics.extend(config.custom_rules.iter().flat_map(|rule| {
+      let rule_config = config.rule_config(&rule.id);
+
+      if !rule.enabled(&rule_config) {
+        return Vec::new();
+      }
+
+      rule
+        .diagnostics(self.document)
+        .into_iter()
+        .filter_map(|diagnostic| {
+          Some(Diagnostic {
+            id: rule.id.clone(),
+            display: rule.display().to_string(),
+            severity: rule_config.severity(diagnostic.severity)?,
+            ..diagnostic
+          })
+        })
+        .collect()
+    }));
+
     diagnostics.sort_by(|a, b| {
       a.range
         .start
@@ -67,6 +95,48 @@ mod tests {
     pretty_assertions::assert_eq,
   };
 
+  /// The diagnostics for the root of a project loaded from disk.
+  struct ProjectTest {
+    diagnostics: Vec<Diagnostic>,
+    tempdir: tempfile::TempDir,
+  }
+
+  impl ProjectTest {
+    /// Write `files`, the first of which is the root justfile, and analyze
+    /// the root.
+    fn new(files: &[(&str, &str)]) -> Self {
+      let tempdir = tempfile::tempdir().unwrap();
+
+      for (path, content) in files {
+        fs::write(tempdir.path().join(path), content).unwrap();
+      }
+
+      let root =
+        lsp::Url::from_file_path(tempdir.path().join(files[0].0)).unwrap();
+
+      let mut documents = DocumentStore::default();
+
+      let project = ProjectLoader::load(&mut documents, &root).unwrap();
+
+      let diagnostics = Analyzer {
+        config: None,
+        document: documents.get(&root).unwrap(),
+        imported_documents: project.imported_documents(&documents).collect(),
+        project: Some(&project),
+      }
+      .analyze();
+
+      Self {
+        diagnostics,
+        tempdir,
+      }
+    }
+
+    fn uri(&self, path: &str) -> lsp::Url {
+      lsp::Url::from_file_path(self.tempdir.path().join(path)).unwrap()
+    }
+  }
+
   #[derive(Debug)]
   struct Test {
     config: Config,
@@ -123,6 +193,7 @@ mod tests {
         config: Some(&config),
         document: &document,
         imported_documents: Vec::new(),
+        project: None,
       };
 
       let diagnostics = analyzer
@@ -274,6 +345,23 @@ mod tests {
     .run();
   }
 
+  #[test]
+  fn aliases_missing_recipe_suggestion() {
+    Test::new(indoc! {
+      "
+      build:
+        echo \"build\"
+
+      alias b := buidl
+      "
+    })
+    .error(
+      "Recipe `buidl` not found, did you mean `build`?",
+      lsp::Range::at(3, 11, 3, 16),
+    )
+    .run();
+  }
+
   #[test]
   fn aliases_missing_target() {
     Test::new(indoc! {
@@ -1143,6 +1231,22 @@ mod tests {
     .run();
   }
 
+  #[test]
+  fn attributes_unknown_suggestion() {
+    Test::new(indoc! {
+      "
+      [privte]
+      foo:
+        echo \"foo\"
+      "
+    })
+    .error(
+      "Unknown attribute `privte`, did you mean `private`?",
+      lsp::Range::at(0, 1, 0, 7),
+    )
+    .run();
+  }
+
   #[test]
   fn attributes_working_directory_allowed_with_global_no_cd() {
     Test::new(indoc! {
@@ -1698,6 +1802,83 @@ mod tests {
     .run();
   }
 
+  #[test]
+  fn custom_rule_level_override() {
+    let config = serde_json::from_value::<Config>(serde_json::json!({
+      "custom-rules": [{
+        "id": "no-env",
+        "message": "Avoid calling `@name` directly",
+        "query": "((function_call name: (identifier) @name) (#eq? @name \"env\"))"
+      }],
+      "rules": {
+        "no-env": "error"
+      }
+    }))
+    .unwrap();
+
+    Test::new(indoc! {
+      "
+      foo := env('HOME')
+
+      bar:
+        echo {{ foo }}
+      "
+    })
+    .config(config)
+    .error("Avoid calling `env` directly", lsp::Range::at(0, 7, 0, 10))
+    .run();
+  }
+
+  #[test]
+  fn custom_rule_off_suppresses_diagnostic() {
+    let config = serde_json::from_value::<Config>(serde_json::json!({
+      "custom-rules": [{
+        "id": "no-env",
+        "message": "Avoid calling `@name` directly",
+        "query": "((function_call name: (identifier) @name) (#eq? @name \"env\"))"
+      }],
+      "rules": {
+        "no-env": "off"
+      }
+    }))
+    .unwrap();
+
+    Test::new(indoc! {
+      "
+      foo := env('HOME')
+
+      bar:
+        echo {{ foo }}
+      "
+    })
+    .config(config)
+    .run();
+  }
+
+  #[test]
+  fn custom_rule_reports_matches() {
+    let config = serde_json::from_value::<Config>(serde_json::json!({
+      "custom-rules": [{
+        "id": "no-env",
+        "message": "Avoid calling `@name` directly",
+        "query": "((function_call name: (identifier) @name) (#eq? @name \"env\"))"
+      }]
+    }))
+    .unwrap();
+
+    Test::new(indoc! {
+      "
+      foo := env('HOME')
+
+      bar:
+        echo {{ foo }}
+      "
+    })
+    .config(config)
+    .warning("Avoid calling `env` directly", lsp::Range::at(0, 7, 0, 10))
+    .run();
+  }
+
   #[test]
   fn cross_parameter_default_references_preceding_parameter() {
     Test::new(indoc! {
@@ -1945,6 +2126,18 @@ mod tests {
     .run();
   }
 
+  #[test]
+  fn duplicate_modules() {
+    Test::new(indoc! {
+      "
+      mod? foo
+      mod? foo
+      "
+    })
+    .error("Duplicate module `foo`", lsp::Range::at(1, 0, 1, 8))
+    .run();
+  }
+
   #[test]
   fn duplicate_netbsd_attribute() {
     Test::new(indoc! {
@@ -2540,6 +2733,21 @@ mod tests {
     .run();
   }
 
+  #[test]
+  fn function_calls_unknown_suggestion() {
+    Test::new(indoc! {
+      "
+      foo := uppercsae('bar')
+      "
+    })
+    .warning("Variable `foo` appears unused", lsp::Range::at(0, 0, 0, 3))
+    .error(
+      "Unknown function `uppercsae`, did you mean `uppercase`?",
+      lsp::Range::at(0, 7, 0, 16),
+    )
+    .run();
+  }
+
   #[test]
   fn function_calls_too_few_args() {
     Test::new(indoc! {
@@ -2585,6 +2793,131 @@ mod tests {
     .run();
   }
 
+  #[test]
+  fn import_collisions_between_imported_files() {
+    let test = ProjectTest::new(&[
+      ("justfile", "import 'bar.just'\nimport 'foo.just'"),
+      ("bar.just", "x := 'bar'"),
+      ("foo.just", "x := 'foo'"),
+    ]);
+
+    assert_eq!(
+      test
+        .diagnostics
+        .iter()
+        .map(|diagnostic| (
+          diagnostic.id.as_str(),
+          diagnostic.message.as_str(),
+          diagnostic.range,
+          diagnostic.related.len()
+        ))
+        .collect::<Vec<_>>(),
+      [(
+        "import-collisions",
+        "Variable `x` is defined in both `foo.just` and `bar.just`",
+        lsp::Range::at(0, 7, 0, 17),
+        2
+      )]
+    );
+  }
+
+  #[test]
+  fn import_collisions_report_each_definition() {
+    let test = ProjectTest::new(&[
+      ("justfile", "import 'foo.just'\n\nfoo:\n  echo root"),
+      ("foo.just", "foo:\n  echo foo"),
+    ]);
+
+    assert_eq!(
+      test
+        .diagnostics
+        .iter()
+        .map(|diagnostic| (
+          diagnostic.id.as_str(),
+          diagnostic.message.as_str(),
+          diagnostic.range,
+          diagnostic.related.clone()
+        ))
+        .collect::<Vec<_>>(),
+      [(
+        "import-collisions",
+        "Recipe `foo` is also defined in `foo.just`",
+        lsp::Range::at(2, 0, 2, 3),
+        vec![lsp::DiagnosticRelatedInformation {
+          location: lsp::Location {
+            uri: test.uri("foo.just"),
+            range: lsp::Range::at(0, 0, 0, 3),
+          },
+          message: "`foo` is defined here".into(),
+        }]
+      )]
+    );
+  }
+
+  #[test]
+  fn import_collisions_respect_allow_duplicate_recipes() {
+    let test = ProjectTest::new(&[
+      (
+        "justfile",
+        "set allow-duplicate-recipes\n\nimport 'foo.just'\n\nfoo:",
+      ),
+      ("foo.just", "foo:"),
+    ]);
+
+    assert!(test.diagnostics.is_empty());
+  }
+
+  #[test]
+  fn import_cycles_report_chain() {
+    let test = ProjectTest::new(&[
+      ("justfile", "import 'foo.just'"),
+      ("foo.just", "import 'bar.just'"),
+      ("bar.just", "import 'justfile'"),
+    ]);
+
+    assert_eq!(test.diagnostics.len(), 1);
+
+    assert_eq!(
+      test.diagnostics[0].message,
+      "Import cycle: `justfile` -> `foo.just` -> `bar.just` -> `justfile`"
+    );
+
+    assert_eq!(test.diagnostics[0].range, lsp::Range::at(0, 7, 0, 17));
+
+    assert_eq!(
+      test.diagnostics[0]
+        .related
+        .iter()
+        .map(|related| (related.location.uri.clone(), related.message.as_str()))
+        .collect::<Vec<_>>(),
+      [
+        (test.uri("justfile"), "`justfile` imports `foo.just`"),
+        (test.uri("foo.just"), "`foo.just` imports `bar.just`"),
+        (test.uri("bar.just"), "`bar.just` imports `justfile`"),
+      ]
+    );
+  }
+
+  #[test]
+  fn import_cycles_through_format_string_import() {
+    let test = ProjectTest::new(&[
+      ("justfile", "dir := 'foo'\n\nimport f'{{ dir }}.just'"),
+      ("foo.just", "import 'justfile'"),
+    ]);
+
+    assert_eq!(
+      test
+        .diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.message.as_str(), diagnostic.range))
+        .collect::<Vec<_>>(),
+      [(
+        "Import cycle: `justfile` -> `foo.just` -> `justfile`",
+        lsp::Range::at(2, 7, 2, 24)
+      )]
+    );
+  }
+
   #[test]
   fn import_format_string_skipped() {
     Test::new(indoc! {
@@ -2633,177 +2966,344 @@ mod tests {
   }
 
   #[test]
-  fn linux_openbsd_no_conflict() {
+  fn invalid_builtin_arguments_datetime_format() {
     Test::new(indoc! {
       "
-      [linux]
-      build:
-        echo \"Building on Linux\"
+      set export
 
-      [openbsd]
-      build:
-        echo \"Building on OpenBSD\"
+      foo := datetime('%Y-%m-%Q')
+      bar := datetime_utc('%Y-%m-%dT%H:%M:%S%.3f%:z')
       "
     })
+    .error(
+      "Invalid `strftime` specifier `%Q` in `datetime()`",
+      lsp::Range::at(2, 23, 2, 25),
+    )
     .run();
   }
 
   #[test]
-  fn linux_unix_conflict() {
+  fn invalid_builtin_arguments_platform_comparison() {
     Test::new(indoc! {
       "
-      [linux]
-      build:
-        echo \"Building on Linux\"
+      set export
 
-      [unix]
-      build:
-        echo \"Building on Unix systems\"
+      foo := if os() == 'darwin' { 'a' } else { 'b' }
+      bar := if arch() != 'aarch65' { 'a' } else { 'b' }
+      baz := if os_family() == 'unix' { 'a' } else { 'b' }
       "
     })
-    .error("Duplicate recipe name `build`", lsp::Range::at(4, 0, 7, 0))
+    .warning(
+      "Unknown `os()` value `darwin`, did you mean `macos`?",
+      lsp::Range::at(2, 19, 2, 25),
+    )
+    .warning(
+      "Unknown `arch()` value `aarch65`, did you mean `aarch64`?",
+      lsp::Range::at(3, 21, 3, 28),
+    )
     .run();
   }
 
   #[test]
-  fn list_features_allow_shadowed_functions_without_lists() {
+  fn invalid_builtin_arguments_platform_comparison_uncommon_values() {
     Test::new(indoc! {
       "
-      bool(value) := value
-      join_list(value) := value
-      len(value) := value
-      num_jobs() := 'qux'
-      show(value) := value
-      split(value) := value
-      which(value) := value
-
-      foo := bool('foo')
-      bar := join_list('bar')
-      baz := len('baz')
-      qux := num_jobs()
-      quux := show('quux')
-      corge := split('corge')
-      grault := which('grault')
+      set export
 
-      recipe:
-        echo {{ foo }}
-        echo {{ bar }}
-        echo {{ baz }}
-        echo {{ qux }}
-        echo {{ quux }}
-        echo {{ corge }}
-        echo {{ grault }}
+      foo := if os() == 'illumos' { 'a' } else { 'b' }
+      bar := if arch() == 'riscv64' { 'a' } else { 'b' }
+      baz := if arch() != 'loongarch64' { 'a' } else { 'b' }
       "
     })
     .run();
   }
 
   #[test]
-  fn list_features_bool_function_requires_lists() {
+  fn invalid_builtin_arguments_platform_comparison_windows() {
     Test::new(indoc! {
       "
-      foo := bool('true')
+      set export
 
-      bar:
-        echo {{ foo }}
+      foo := if os() == 'windows' { 'a' } else { 'b' }
+      bar := if os() == 'win32' { 'a' } else { 'b' }
       "
     })
-    .error(
-      "the `bool()` function requires `set lists`",
-      lsp::Range::at(0, 7, 0, 11),
+    .warning(
+      "Unknown `os()` value `win32`, did you mean `windows`?",
+      lsp::Range::at(3, 19, 3, 24),
     )
     .run();
   }
 
   #[test]
-  fn list_features_comparison_as_value_requires_lists() {
+  fn invalid_builtin_arguments_semver_requirement() {
     Test::new(indoc! {
       "
-      foo := 'a' == 'b'
+      set export
 
-      bar:
-        echo {{ foo }}
+      foo := semver_matches('1.2.3', '>=1.0, <2.x.1')
+      bar := semver_matches('1.2.3', '^1.2')
       "
     })
     .error(
-      "comparison operators require `set lists`",
-      lsp::Range::at(0, 11, 0, 13),
+      "Invalid version requirement `<2.x.1`: version components can't follow a wildcard in `semver_matches()`",
+      lsp::Range::at(2, 39, 2, 45),
     )
     .run();
   }
 
   #[test]
-  fn list_features_if_without_else_requires_lists() {
+  fn invalid_builtin_arguments_style_name() {
     Test::new(indoc! {
       "
-      foo := if 'a' == 'b' { 'c' }
-
-      bar:
-        echo {{ foo }}
+      foo:
+        echo '{{ style('warn') }}hello{{ NORMAL }}'
       "
     })
     .error(
-      "`if` without `else` requires `set lists`",
-      lsp::Range::at(0, 7, 0, 9),
+      "Unknown style `warn` in `style()`, did you mean `warning`?",
+      lsp::Range::at(1, 18, 1, 22),
     )
     .run();
   }
 
   #[test]
-  fn list_features_join_list_function_requires_lists() {
+  fn invalid_regex_arg_pattern_never_matches() {
     Test::new(indoc! {
       "
-      foo := join_list('bar')
-
-      bar:
-        echo {{ foo }}
+      [arg('target', pattern='(dev|prod)$-x')]
+      deploy target:
+        echo {{ target }}
       "
     })
-    .error(
-      "the `join_list()` function requires `set lists`",
-      lsp::Range::at(0, 7, 0, 16),
+    .warning(
+      "Pattern `(dev|prod)$-x` can never match a complete argument",
+      lsp::Range::at(0, 24, 0, 37),
     )
     .run();
   }
 
   #[test]
-  fn list_features_len_function_accepts_scalar_without_lists() {
+  fn invalid_regex_comparison() {
     Test::new(indoc! {
       "
-      foo := len('bar')
+      set export
 
-      bar:
-        echo {{ foo }}
+      foo := if 'abc' =~ 'a(b' { 'x' } else { 'y' }
       "
     })
+    .error(
+      "Invalid regex: unclosed group",
+      lsp::Range::at(2, 21, 2, 22),
+    )
     .run();
   }
 
   #[test]
-  fn list_features_list_concatenation_requires_lists() {
+  fn invalid_regex_escaped_double_quoted_string() {
     Test::new(indoc! {
-      "
-      foo := 'a' ++ 'b'
+      r#"
+      set export
 
-      bar:
-        echo {{ foo }}
-      "
+      foo := replace_regex('abc', "\\d\\q", 'x')
+      "#
     })
     .error(
-      "list concatenation operator `++` requires `set lists`",
-      lsp::Range::at(0, 11, 0, 13),
+      "Invalid regex: unrecognized escape sequence",
+      lsp::Range::at(2, 32, 2, 35),
     )
     .run();
   }
 
   #[test]
-  fn list_features_list_literals_require_lists() {
+  fn invalid_regex_valid_patterns() {
     Test::new(indoc! {
-      "
-      foo := ['bar']
+      r#"
+      set export
 
-      bar:
-        echo {{ foo }}
+      foo := replace_regex('abc', "^a\\w+$", 'x')
+      bar := if 'abc' !~ '^[a-z]+$' { 'x' } else { 'y' }
+
+      [arg('target', pattern='dev|prod')]
+      deploy target:
+        echo {{ target }}
+      "#
+    })
+    .run();
+  }
+
+  #[test]
+  fn linux_openbsd_no_conflict() {
+    Test::new(indoc! {
+      "
+      [linux]
+      build:
+        echo \"Building on Linux\"
+
+      [openbsd]
+      build:
+        echo \"Building on OpenBSD\"
+      "
+    })
+    .run();
+  }
+
+  #[test]
+  fn linux_unix_conflict() {
+    Test::new(indoc! {
+      "
+      [linux]
+      build:
+        echo \"Building on Linux\"
+
+      [unix]
+      build:
+        echo \"Building on Unix systems\"
+      "
+    })
+    .error("Duplicate recipe name `build`", lsp::Range::at(4, 0, 7, 0))
+    .run();
+  }
+
+  #[test]
+  fn list_features_allow_shadowed_functions_without_lists() {
+    Test::new(indoc! {
+      "
+      bool(value) := value
+      join_list(value) := value
+      len(value) := value
+      num_jobs() := 'qux'
+      show(value) := value
+      split(value) := value
+      which(value) := value
+
+      foo := bool('foo')
+      bar := join_list('bar')
+      baz := len('baz')
+      qux := num_jobs()
+      quux := show('quux')
+      corge := split('corge')
+      grault := which('grault')
+
+      recipe:
+        echo {{ foo }}
+        echo {{ bar }}
+        echo {{ baz }}
+        echo {{ qux }}
+        echo {{ quux }}
+        echo {{ corge }}
+        echo {{ grault }}
+      "
+    })
+    .run();
+  }
+
+  #[test]
+  fn list_features_bool_function_requires_lists() {
+    Test::new(indoc! {
+      "
+      foo := bool('true')
+
+      bar:
+        echo {{ foo }}
+      "
+    })
+    .error(
+      "the `bool()` function requires `set lists`",
+      lsp::Range::at(0, 7, 0, 11),
+    )
+    .run();
+  }
+
+  #[test]
+  fn list_features_comparison_as_value_requires_lists() {
+    Test::new(indoc! {
+      "
+      foo := 'a' == 'b'
+
+      bar:
+        echo {{ foo }}
+      "
+    })
+    .error(
+      "comparison operators require `set lists`",
+      lsp::Range::at(0, 11, 0, 13),
+    )
+    .run();
+  }
+
+  #[test]
+  fn list_features_if_without_else_requires_lists() {
+    Test::new(indoc! {
+      "
+      foo := if 'a' == 'b' { 'c' }
+
+      bar:
+        echo {{ foo }}
+      "
+    })
+    .error(
+      "`if` without `else` requires `set lists`",
+      lsp::Range::at(0, 7, 0, 9),
+    )
+    .run();
+  }
+
+  #[test]
+  fn list_features_join_list_function_requires_lists() {
+    Test::new(indoc! {
+      "
+      foo := join_list('bar')
+
+      bar:
+        echo {{ foo }}
+      "
+    })
+    .error(
+      "the `join_list()` function requires `set lists`",
+      lsp::Range::at(0, 7, 0, 16),
+    )
+    .run();
+  }
+
+  #[test]
+  fn list_features_len_function_accepts_scalar_without_lists() {
+    Test::new(indoc! {
+      "
+      foo := len('bar')
+
+      bar:
+        echo {{ foo }}
+      "
+    })
+    .run();
+  }
+
+  #[test]
+  fn list_features_list_concatenation_requires_lists() {
+    Test::new(indoc! {
+      "
+      foo := 'a' ++ 'b'
+
+      bar:
+        echo {{ foo }}
+      "
+    })
+    .error(
+      "list concatenation operator `++` requires `set lists`",
+      lsp::Range::at(0, 11, 0, 13),
+    )
+    .run();
+  }
+
+  #[test]
+  fn list_features_list_literals_require_lists() {
+    Test::new(indoc! {
+      "
+      foo := ['bar']
+
+      bar:
+        echo {{ foo }}
       "
     })
     .error(
@@ -2978,6 +3478,58 @@ mod tests {
     .run();
   }
 
+  #[test]
+  fn missing_module() {
+    let test = ProjectTest::new(&[("justfile", "mod foo\nmod? bar\n")]);
+
+    assert_eq!(
+      test
+        .diagnostics
+        .iter()
+        .map(|diagnostic| (
+          diagnostic.id.as_str(),
+          diagnostic.message.as_str(),
+          diagnostic.range
+        ))
+        .collect::<Vec<_>>(),
+      [(
+        "missing-modules",
+        "Could not find source file for module `foo`",
+        lsp::Range::at(0, 4, 0, 7)
+      )]
+    );
+  }
+
+  #[test]
+  fn missing_module_dynamic_paths_skipped() {
+    let test = ProjectTest::new(&[(
+      "justfile",
+      "mod foo f'{{ 'foo' }}.just'\nmod bar x'$HOME/bar.just'\n",
+    )]);
+
+    assert!(test.diagnostics.is_empty());
+  }
+
+  #[test]
+  fn missing_module_without_file_path_skipped() {
+    let document = Document::new(
+      "mod foo\n",
+      lsp::Url::parse("untitled:Untitled-1").unwrap(),
+    )
+    .unwrap();
+
+    assert!(
+      Analyzer {
+        config: None,
+        document: &document,
+        imported_documents: Vec::new(),
+        project: None,
+      }
+      .analyze()
+      .is_empty()
+    );
+  }
+
   #[test]
   fn module_attributes_group() {
     Test::new(indoc! {
@@ -2989,6 +3541,23 @@ mod tests {
     .run();
   }
 
+  #[test]
+  fn module_recipe_conflict() {
+    Test::new(indoc! {
+      "
+      mod? foo
+
+      foo:
+        echo foo
+      "
+    })
+    .error(
+      "Module `foo` conflicts with a recipe of the same name",
+      lsp::Range::at(0, 5, 0, 8),
+    )
+    .run();
+  }
+
   #[test]
   fn openbsd_macos_no_conflict() {
     Test::new(indoc! {
@@ -3508,6 +4077,24 @@ mod tests {
     .run();
   }
 
+  #[test]
+  fn recipe_dependencies_missing_suggestion() {
+    Test::new(indoc! {
+      "
+      build:
+        echo \"build\"
+
+      test: buidl
+        echo \"test\"
+      "
+    })
+    .error(
+      "Recipe `buidl` not found, did you mean `build`?",
+      lsp::Range::at(3, 6, 3, 11),
+    )
+    .run();
+  }
+
   #[test]
   fn recipe_dependencies_multiple_missing() {
     Test::new(indoc! {
@@ -4523,6 +5110,23 @@ mod tests {
     .run();
   }
 
+  #[test]
+  fn settings_unknown_suggestion() {
+    Test::new(indoc! {
+      "
+      set dotenv-laod
+
+      foo:
+        echo \"foo\"
+      "
+    })
+    .error(
+      "Unknown setting `dotenv-laod`, did you mean `dotenv-load`?",
+      lsp::Range::at(0, 0, 1, 0),
+    )
+    .run();
+  }
+
   #[test]
   fn settings_unknown_with_expression() {
     Test::new(indoc! {
@@ -4638,6 +5242,164 @@ mod tests {
     .run();
   }
 
+  #[test]
+  fn shell_injection_is_off_by_default() {
+    Test::new(indoc! {
+      "
+      deploy dir:
+        rm -rf {{dir}}
+      "
+    })
+    .run();
+  }
+
+  #[test]
+  fn shell_injection_positional_arguments() {
+    let config = serde_json::from_value::<Config>(serde_json::json!({
+      "rules": {
+        "shell-injection": "warning"
+      }
+    }))
+    .unwrap();
+
+    Test::new(indoc! {
+      "
+      set positional-arguments
+
+      build target:
+        cargo build -p {{target}}
+      "
+    })
+    .config(config)
+    .warning(
+      "Parameter `target` is interpolated without quoting, wrap it in `quote()` or use `\"$1\"`",
+      lsp::Range::at(3, 17, 3, 27),
+    )
+    .run();
+  }
+
+  #[test]
+  fn shell_injection_unquoted_parameters() {
+    let config = serde_json::from_value::<Config>(serde_json::json!({
+      "rules": {
+        "shell-injection": "warning"
+      }
+    }))
+    .unwrap();
+
+    Test::new(indoc! {
+      "
+      deploy dir msg:
+        rm -rf {{dir}}
+        git commit -m \"{{msg}}\"
+        echo {{quote(msg)}}
+        echo '{{dir}}'
+        echo {{dir / 'x'}}
+
+      script dir:
+        #!/usr/bin/env bash
+        rm -rf {{dir}}
+      "
+    })
+    .config(config)
+    .warning(
+      "Parameter `dir` is interpolated without quoting, wrap it in `quote()`",
+      lsp::Range::at(1, 9, 1, 16),
+    )
+    .warning(
+      "Parameter `dir` is interpolated without quoting, wrap it in `quote()`",
+      lsp::Range::at(5, 7, 5, 20),
+    )
+    .run();
+  }
+
+  #[test]
+  fn shell_syntax_backticks_and_shell_function() {
+    Test::new(indoc! {
+      r#"
+      set export
+
+      x := `echo $((1 + ))`
+      y := shell('echo "$1', 'a')
+      z := `ls | wc -l`
+      "#
+    })
+    .warning(
+      "Shell syntax error near `$((1`",
+      lsp::Range::at(2, 11, 2, 20),
+    )
+    .warning(
+      "Shell syntax error near `\"$1`",
+      lsp::Range::at(3, 17, 3, 20),
+    )
+    .run();
+  }
+
+  #[test]
+  fn shell_syntax_linewise_recipe() {
+    Test::new(indoc! {
+      r#"
+      foo x:
+        @echo {{x}} \
+          bar
+        if true; then
+        echo "$(ls"
+        -rm -rf {{x}}/foo
+      "#
+    })
+    .warning(
+      "Shell syntax error: missing `fi`",
+      lsp::Range::at(3, 15, 3, 15),
+    )
+    .warning(
+      "Shell syntax error near `\"$(ls\"`",
+      lsp::Range::at(4, 7, 4, 13),
+    )
+    .run();
+  }
+
+  #[test]
+  fn shell_syntax_script_recipes() {
+    Test::new(indoc! {
+      "
+      foo:
+        #!/usr/bin/env bash
+        if [ -f x ]; then
+          cat <<EOF
+        hi
+        EOF
+        fi
+        echo (
+
+      [script('python3')]
+      bar:
+        if x: (
+
+      baz:
+        #!/usr/bin/env python3
+        def f(:
+      "
+    })
+    .warning("Shell syntax error near `(`", lsp::Range::at(7, 7, 7, 8))
+    .run();
+  }
+
+  #[test]
+  fn shell_syntax_skips_non_posix_shell() {
+    Test::new(indoc! {
+      "
+      set shell := ['pwsh', '-c']
+
+      x := `Get-ChildItem | % { $_.Name }`
+
+      foo:
+        if ($true) { echo hi }
+      "
+    })
+    .warning("Variable `x` appears unused", lsp::Range::at(2, 0, 2, 1))
+    .run();
+  }
+
   #[test]
   fn should_recognize_recipe_parameters_in_dependency_arguments() {
     Test::new(indoc! {
@@ -4669,6 +5431,113 @@ mod tests {
     .run();
   }
 
+  #[test]
+  fn type_mismatch_builtin_argument() {
+    Test::new(indoc! {
+      "
+      set export
+      set lists
+
+      files := ['a.txt', 'b.txt']
+      renamed := replace(files, '.txt', '.md')
+      "
+    })
+    .error(
+      "Argument 1 of `replace()` expects a string, found a list",
+      lsp::Range::at(4, 19, 4, 24),
+    )
+    .run();
+  }
+
+  #[test]
+  fn type_mismatch_ignored_without_lists() {
+    Test::new(indoc! {
+      "
+      set export
+
+      renamed := join_list('a b')
+      "
+    })
+    .error(
+      "the `join_list()` function requires `set lists`",
+      lsp::Range::at(2, 11, 2, 20),
+    )
+    .run();
+  }
+
+  #[test]
+  fn type_mismatch_list_argument() {
+    Test::new(indoc! {
+      "
+      set export
+      set lists
+
+      joined := join_list('a b')
+      "
+    })
+    .error(
+      "Argument 1 of `join_list()` expects a list, found a string",
+      lsp::Range::at(3, 20, 3, 25),
+    )
+    .run();
+  }
+
+  #[test]
+  fn type_mismatch_mapped_builtins_accept_lists() {
+    Test::new(indoc! {
+      "
+      set export
+      set lists
+
+      files := quote(['a', 'b'])
+      joined := join_list(prepend('src/', files))
+
+      build:
+        echo {{ files }}
+      "
+    })
+    .run();
+  }
+
+  #[test]
+  fn type_mismatch_operator() {
+    Test::new(indoc! {
+      "
+      set export
+      set lists
+
+      files := split('a b')
+      paths := 'src' / files
+      "
+    })
+    .error(
+      "Operator `/` expects a string, found a list",
+      lsp::Range::at(4, 17, 4, 22),
+    )
+    .run();
+  }
+
+  #[test]
+  fn type_mismatch_shebang_interpolation() {
+    Test::new(indoc! {
+      "
+      set export
+      set lists
+
+      files := ['a', 'b']
+
+      build:
+        #!/usr/bin/env bash
+        echo {{ files }}
+      "
+    })
+    .error(
+      "Interpolation in a shebang recipe expects a string, found a list",
+      lsp::Range::at(7, 10, 7, 15),
+    )
+    .run();
+  }
+
   #[test]
   fn underscore_suppresses_unused_variable_warnings() {
     Test::new(indoc! {
@@ -4791,6 +5660,38 @@ mod tests {
     .run();
   }
 
+  #[test]
+  fn unreferenced_variable_suggests_parameter() {
+    Test::new(indoc! {
+      "
+      build target:
+        echo {{ target }} {{ targte }}
+      "
+    })
+    .error(
+      "Variable `targte` not found, did you mean `target`?",
+      lsp::Range::at(1, 23, 1, 29),
+    )
+    .run();
+  }
+
+  #[test]
+  fn unreferenced_variable_suggests_variable() {
+    Test::new(indoc! {
+      "
+      target_dir := 'out'
+
+      build:
+        echo {{ target_dir }} {{ target_dri }}
+      "
+    })
+    .error(
+      "Variable `target_dri` not found, did you mean `target_dir`?",
+      lsp::Range::at(3, 27, 3, 37),
+    )
+    .run();
+  }
+
   #[test]
   fn used_variables_no_warnings() {
     Test::new(indoc! {
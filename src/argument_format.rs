@@ -0,0 +1,237 @@
+use super::*;
+
+/// A mini-language that a builtin function argument is written in, which can
+/// be validated when the argument is a string literal.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ArgumentFormat {
+  Strftime,
+  Style,
+  VersionRequirement,
+}
+
+impl ArgumentFormat {
+  const COMPARISON_OPERATORS: [&'static str; 7] =
+    [">=", "<=", ">", "<", "=", "~", "^"];
+
+  const STRFTIME_SPECIFIERS: &'static [&'static str] = &[
+    "%", "+", ".3f", ".6f", ".9f", ".f", "3f", "6f", "9f", "::z", ":::z", ":z",
+    "#z", "A", "B", "C", "D", "F", "G", "H", "I", "M", "P", "R", "S", "T", "U",
+    "V", "W", "X", "Y", "Z", "a", "b", "c", "d", "e", "f", "g", "h", "j", "k",
+    "l", "m", "n", "p", "q", "r", "s", "t", "u", "v", "w", "x", "y", "z",
+  ];
+
+  pub const STYLES: [&'static str; 3] = ["command", "error", "warning"];
+
+  /// Validate `value`, returning the byte span and message of the first
+  /// error found.
+  #[must_use]
+  pub fn check(self, value: &str) -> Option<(Range<usize>, String)> {
+    match self {
+      Self::Strftime => Self::check_strftime(value),
+      Self::Style => (!Self::STYLES.contains(&value))
+        .then(|| (0..value.len(), format!("Unknown style `{value}`"))),
+      Self::VersionRequirement => Self::check_version_requirement(value),
+    }
+  }
+
+  fn check_strftime(value: &str) -> Option<(Range<usize>, String)> {
+    let mut characters = value.char_indices().peekable();
+
+    while let Some((start, character)) = characters.next() {
+      if character != '%' {
+        continue;
+      }
+
+      characters.next_if(|(_, character)| matches!(character, '-' | '_' | '0'));
+
+      let mut specifier = String::new();
+
+      let end = loop {
+        let Some((index, character)) = characters.next() else {
+          return Some((
+            start..value.len(),
+            "Incomplete `strftime` specifier at end of format".into(),
+          ));
+        };
+
+        specifier.push(character);
+
+        if !matches!(character, '.' | ':' | '#' | '3' | '6' | '9') {
+          break index + character.len_utf8();
+        }
+      };
+
+      if !Self::STRFTIME_SPECIFIERS.contains(&specifier.as_str()) {
+        return Some((
+          start..end,
+          format!("Invalid `strftime` specifier `{}`", &value[start..end]),
+        ));
+      }
+    }
+
+    None
+  }
+
+  fn check_version_requirement(value: &str) -> Option<(Range<usize>, String)> {
+    if value.trim() == "*" {
+      return None;
+    }
+
+    let mut offset = 0;
+
+    for comparator in value.split(',') {
+      let start = offset + (comparator.len() - comparator.trim_start().len());
+
+      let end = offset + comparator.trim_end().len();
+
+      offset += comparator.len() + 1;
+
+      let comparator = comparator.trim();
+
+      let Some(reason) = Self::comparator_error(comparator) else {
+        continue;
+      };
+
+      let end = end.max(start);
+
+      return Some((
+        start..end,
+        if comparator.is_empty() {
+          "Invalid version requirement: empty comparator".into()
+        } else {
+          format!("Invalid version requirement `{comparator}`: {reason}")
+        },
+      ));
+    }
+
+    None
+  }
+
+  /// Why `comparator` isn't a valid version comparator, if it isn't.
+  fn comparator_error(comparator: &str) -> Option<&'static str> {
+    let version = Self::COMPARISON_OPERATORS
+      .iter()
+      .find_map(|operator| comparator.strip_prefix(operator))
+      .unwrap_or(comparator)
+      .trim_start();
+
+    if version.is_empty() {
+      return Some("missing version");
+    }
+
+    let (version, suffix) = match version
+      .find(['-', '+'])
+      .map(|index| version.split_at(index))
+    {
+      Some((version, suffix)) => (version, Some(&suffix[1..])),
+      None => (version, None),
+    };
+
+    if suffix.is_some_and(|suffix| {
+      suffix.is_empty()
+        || suffix.split(['.', '+']).any(|identifier| {
+          identifier.is_empty()
+            || !identifier.chars().all(|character| {
+              character.is_ascii_alphanumeric() || character == '-'
+            })
+        })
+    }) {
+      return Some("invalid pre-release or build metadata");
+    }
+
+    let parts = version.split('.').collect::<Vec<_>>();
+
+    if parts.len() > 3 {
+      return Some("too many version components");
+    }
+
+    let mut wildcard = false;
+
+    for part in parts {
+      if matches!(part, "*" | "x" | "X") {
+        wildcard = true;
+        continue;
+      }
+
+      if wildcard {
+        return Some("version components can't follow a wildcard");
+      }
+
+      if part.is_empty() || !part.chars().all(|c| c.is_ascii_digit()) {
+        return Some("expected a numeric version component");
+      }
+
+      if part.len() > 1 && part.starts_with('0') {
+        return Some("version components can't have leading zeros");
+      }
+    }
+
+    if wildcard && suffix.is_some() {
+      return Some(
+        "wildcard versions can't have pre-release or build metadata",
+      );
+    }
+
+    None
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn strftime() {
+    #[track_caller]
+    fn case(value: &str, expected: Option<(usize, usize)>) {
+      assert_eq!(
+        ArgumentFormat::Strftime
+          .check(value)
+          .map(|(span, _)| (span.start, span.end)),
+        expected
+      );
+    }
+
+    case("%Y-%m-%d %H:%M:%S", None);
+    case("%-d %_H %0e %.3f %:z %::z %#z %%", None);
+    case("%Y-%Q", Some((3, 5)));
+    case("%Y %.4f", Some((3, 6)));
+    case("100%", Some((3, 4)));
+  }
+
+  #[test]
+  fn style() {
+    assert_eq!(ArgumentFormat::Style.check("error"), None);
+
+    assert_eq!(
+      ArgumentFormat::Style.check("warn"),
+      Some((0..4, "Unknown style `warn`".into()))
+    );
+  }
+
+  #[test]
+  fn version_requirement() {
+    #[track_caller]
+    fn case(value: &str, expected: Option<(usize, usize)>) {
+      assert_eq!(
+        ArgumentFormat::VersionRequirement
+          .check(value)
+          .map(|(span, _)| (span.start, span.end)),
+        expected
+      );
+    }
+
+    case("*", None);
+    case(">=1.2.3, <2", None);
+    case("^0.1", None);
+    case("~1.2.3-alpha.1", None);
+    case("1.*", None);
+    case("=1.x", None);
+    case(">=1.2.3, <2.a", Some((9, 13)));
+    case("1.2.3.4", Some((0, 7)));
+    case("01.2", Some((0, 4)));
+    case(">=1,", Some((4, 4)));
+    case(">=", Some((0, 2)));
+    case("1.*.3", Some((0, 5)));
+  }
+}
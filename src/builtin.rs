@@ -28,6 +28,22 @@ pub enum Builtin<'a> {
 }
 
 impl Builtin<'_> {
+  /// The mini-language that argument `index` of a builtin function is
+  /// written in, if any.
+  #[must_use]
+  pub fn argument_format(&self, index: usize) -> Option<ArgumentFormat> {
+    let Self::Function { name, .. } = self else {
+      return None;
+    };
+
+    match (*name, index) {
+      ("datetime" | "datetime_utc", 0) => Some(ArgumentFormat::Strftime),
+      ("semver_matches", 1) => Some(ArgumentFormat::VersionRequirement),
+      ("style", 0) => Some(ArgumentFormat::Style),
+      _ => None,
+    }
+  }
+
   #[must_use]
   pub fn completion_items(&self) -> Vec<lsp::CompletionItem> {
     match self {
@@ -240,6 +256,102 @@ impl Builtin<'_> {
       ..Default::default()
     }
   }
+
+  /// Every value a nullary builtin function can return, for functions whose
+  /// result is one of a fixed set. `arch()` and `os()` return Rust's
+  /// `std::env::consts::{ARCH, OS}`, so these lists cover every
+  /// `target_arch` and `target_os` the compiler knows.
+  #[must_use]
+  pub fn values(&self) -> Option<&'static [&'static str]> {
+    let Self::Function { name, .. } = self else {
+      return None;
+    };
+
+    match *name {
+      "arch" => Some(&[
+        "aarch64",
+        "amdgpu",
+        "arm",
+        "arm64ec",
+        "asmjs",
+        "avr",
+        "bpf",
+        "csky",
+        "hexagon",
+        "loongarch32",
+        "loongarch64",
+        "m68k",
+        "mips",
+        "mips32r6",
+        "mips64",
+        "mips64r6",
+        "msp430",
+        "nvptx64",
+        "powerpc",
+        "powerpc64",
+        "riscv32",
+        "riscv64",
+        "s390x",
+        "sparc",
+        "sparc64",
+        "spirv",
+        "wasm32",
+        "wasm64",
+        "x86",
+        "x86_64",
+        "xcore",
+        "xtensa",
+      ]),
+      "os" => Some(&[
+        "aix",
+        "android",
+        "bitrig",
+        "cuda",
+        "cygwin",
+        "dragonfly",
+        "emscripten",
+        "espidf",
+        "freebsd",
+        "fuchsia",
+        "haiku",
+        "hermit",
+        "horizon",
+        "hurd",
+        "illumos",
+        "ios",
+        "l4re",
+        "linux",
+        "lynxos178",
+        "macos",
+        "netbsd",
+        "none",
+        "nto",
+        "nuttx",
+        "openbsd",
+        "psp",
+        "qurt",
+        "redox",
+        "rtems",
+        "solaris",
+        "solid_asp3",
+        "teeos",
+        "trusty",
+        "tvos",
+        "uefi",
+        "unknown",
+        "visionos",
+        "vita",
+        "vxworks",
+        "wasi",
+        "watchos",
+        "windows",
+        "xous",
+        "zkvm",
+      ]),
+      "os_family" => Some(&["unix", "windows"]),
+      _ => None,
+    }
+  }
 }
 
 #[cfg(test)]
@@ -0,0 +1,324 @@
+use super::*;
+
+/// Where a references lens points, so its count can be computed when the
+/// client resolves it.
+#[derive(Debug, Deserialize, Serialize)]
+struct CodeLensData {
+  position: lsp::Position,
+  uri: lsp::Url,
+}
+
+/// Informational code lenses for a justfile: reference counts on recipes,
+/// variables and functions, the recipes that depend on each recipe, and
+/// where an imported recipe is overridden.
+///
+/// Reference lenses are returned without a command and filled in by
+/// [`CodeLenses::resolve`], since counting them means resolving every
+/// identifier in the project. Clicking a lens opens the first location it
+/// lists.
+pub(crate) struct CodeLenses<'a> {
+  document: &'a Document,
+  workspace: &'a Workspace,
+}
+
+impl<'a> CodeLenses<'a> {
+  /// Whether `identifier` names a recipe, variable or function at its
+  /// definition site rather than referring to one.
+  fn is_declaration(identifier: &Node) -> bool {
+    let Some(parent) = identifier.parent() else {
+      return false;
+    };
+
+    let field = match parent.kind() {
+      "assignment" => "left",
+      "function_definition" | "recipe_header" => "name",
+      _ => return false,
+    };
+
+    parent
+      .child_by_field_name(field)
+      .is_some_and(|name| name.id() == identifier.id())
+  }
+
+  pub(crate) fn lenses(&self) -> Vec<lsp::CodeLens> {
+    let mut lenses = Vec::new();
+
+    let views = Self::views(self.workspace, self.document);
+
+    for recipe in self.document.recipes() {
+      lenses.push(self.references_lens(recipe.name.range));
+
+      if let Some(lens) = self.overridden_lens(&views, &recipe) {
+        lenses.push(lens);
+      } else if let Some(lens) = self.used_by_lens(&views, &recipe) {
+        lenses.push(lens);
+      }
+    }
+
+    for variable in self.document.variables() {
+      lenses.push(self.references_lens(variable.name.range));
+    }
+
+    for function in self.document.functions() {
+      lenses.push(self.references_lens(function.name.range));
+    }
+
+    lenses
+  }
+
+  pub(crate) fn new(workspace: &'a Workspace, document: &'a Document) -> Self {
+    Self {
+      document,
+      workspace,
+    }
+  }
+
+  /// A lens naming the file whose recipe of the same name takes precedence
+  /// over `recipe`.
+  fn overridden_lens(
+    &self,
+    views: &[ProjectView],
+    recipe: &Recipe,
+  ) -> Option<lsp::CodeLens> {
+    let overriding = views
+      .iter()
+      .filter_map(|view| view.find_recipe(&recipe.name.value))
+      .find(|located| located.uri != self.document.uri)?;
+
+    let file_name = self
+      .workspace
+      .documents
+      .get(&overriding.uri)
+      .map_or("", Document::file_name);
+
+    Some(lsp::CodeLens {
+      range: recipe.name.range,
+      command: Some(Self::show_locations(
+        format!("overridden in {file_name}"),
+        &[lsp::Location {
+          uri: overriding.uri.clone(),
+          range: overriding.value.name.range,
+        }],
+      )),
+      data: None,
+    })
+  }
+
+  /// Every location in the project that refers to the declaration named by
+  /// the identifier at `position` in `uri`.
+  fn references(
+    workspace: &Workspace,
+    uri: &lsp::Url,
+    position: lsp::Position,
+  ) -> Vec<lsp::Location> {
+    let Some(document) = workspace.documents.get(uri) else {
+      return Vec::new();
+    };
+
+    let Some(identifier) = document
+      .node_at_position(position)
+      .filter(|node| node.kind() == "identifier")
+    else {
+      return Vec::new();
+    };
+
+    let Some(target) =
+      Resolver::new(document).resolve_identifier_definition(&identifier)
+    else {
+      return Vec::new();
+    };
+
+    let name = document.get_node_text(&identifier);
+
+    let mut references = Vec::new();
+
+    for view in Self::views(workspace, document) {
+      let candidate_document = view.document();
+
+      let Some(tree) = &candidate_document.tree else {
+        continue;
+      };
+
+      let resolver = Resolver::new(view);
+
+      for candidate in tree.root_node().find_all("identifier") {
+        if Self::is_declaration(&candidate)
+          || candidate_document.get_node_text(&candidate) != name
+        {
+          continue;
+        }
+
+        let location = lsp::Location {
+          uri: candidate_document.uri.clone(),
+          range: candidate.get_range(candidate_document),
+        };
+
+        if resolver.resolve_identifier_definition(&candidate).as_ref()
+          == Some(&target)
+          && !references.contains(&location)
+        {
+          references.push(location);
+        }
+      }
+    }
+
+    references
+  }
+
+  /// An unresolved lens counting the references to the declaration named
+  /// at `range`.
+  fn references_lens(&self, range: lsp::Range) -> lsp::CodeLens {
+    lsp::CodeLens {
+      range,
+      command: None,
+      data: serde_json::to_value(CodeLensData {
+        position: range.start,
+        uri: self.document.uri.clone(),
+      })
+      .ok(),
+    }
+  }
+
+  /// Fills in the command of a references lens, leaving other lenses
+  /// untouched.
+  pub(crate) fn resolve(
+    workspace: &Workspace,
+    lens: lsp::CodeLens,
+  ) -> lsp::CodeLens {
+    let Some(data) = lens
+      .data
+      .clone()
+      .and_then(|data| serde_json::from_value::<CodeLensData>(data).ok())
+    else {
+      return lens;
+    };
+
+    let references = Self::references(workspace, &data.uri, data.position);
+
+    let title = match references.len() {
+      1 => "1 reference".into(),
+      count => format!("{count} references"),
+    };
+
+    lsp::CodeLens {
+      command: Some(Self::show_locations(title, &references)),
+      data: None,
+      ..lens
+    }
+  }
+
+  /// A command titled `title` that opens the first of `locations` when the
+  /// lens is clicked.
+  fn show_locations(
+    title: String,
+    locations: &[lsp::Location],
+  ) -> lsp::Command {
+    lsp::Command {
+      title,
+      command: Command::ShowLocations.to_string(),
+      arguments: Some(vec![
+        serde_json::to_value(locations).unwrap_or_default(),
+      ]),
+    }
+  }
+
+  /// A lens listing the recipes in the project that depend on `recipe`.
+  fn used_by_lens(
+    &self,
+    views: &[ProjectView],
+    recipe: &Recipe,
+  ) -> Option<lsp::CodeLens> {
+    let mut dependents = Vec::new();
+
+    for view in views {
+      let resolves_here =
+        view.find_recipe(&recipe.name.value).is_some_and(|located| {
+          located.uri == self.document.uri
+            && located.value.range == recipe.range
+        });
+
+      if !resolves_here {
+        continue;
+      }
+
+      for dependent in view.document().recipes() {
+        if dependent
+          .dependencies
+          .iter()
+          .any(|dependency| dependency.name.value == recipe.name.value)
+          && !dependents
+            .iter()
+            .any(|(name, _)| *name == dependent.name.value)
+        {
+          dependents.push((
+            dependent.name.value,
+            lsp::Location {
+              uri: view.document().uri.clone(),
+              range: dependent.name.range,
+            },
+          ));
+        }
+      }
+    }
+
+    if dependents.is_empty() {
+      return None;
+    }
+
+    let (names, locations) =
+      dependents.into_iter().unzip::<_, _, Vec<_>, Vec<_>>();
+
+    Some(lsp::CodeLens {
+      range: recipe.name.range,
+      command: Some(Self::show_locations(
+        format!("used by: {}", names.join(", ")),
+        &locations,
+      )),
+      data: None,
+    })
+  }
+
+  /// A view for every document in each project whose import scope
+  /// contains `document`, or just `document` itself when there are none.
+  ///
+  /// Every opened justfile is the root of its own project, so the same
+  /// document may be seen through several projects.
+  fn views<'b>(
+    workspace: &'b Workspace,
+    document: &'b Document,
+  ) -> Vec<ProjectView<'b>> {
+    let mut views = Vec::new();
+
+    for project in workspace.projects.values().filter(|project| {
+      project
+        .import_scope
+        .documents()
+        .iter()
+        .any(|scope_document| scope_document.uri == document.uri)
+    }) {
+      for scope_document in project.import_scope.documents() {
+        let scoped_document = if scope_document.uri == document.uri {
+          document
+        } else if let Some(scoped_document) =
+          workspace.documents.get(&scope_document.uri)
+        {
+          scoped_document
+        } else {
+          continue;
+        };
+
+        views.push(ProjectView::new(
+          scoped_document,
+          &project.import_scope,
+          &workspace.documents,
+        ));
+      }
+    }
+
+    if views.is_empty() {
+      views.push(ProjectView::from(document));
+    }
+
+    views
+  }
+}
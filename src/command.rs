@@ -2,12 +2,20 @@ use super::*;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum Command {
+  DryRunRecipe,
+  Evaluate,
   RunRecipe,
+  ShowLocations,
 }
 
 impl Command {
   pub(crate) fn all() -> Vec<String> {
-    vec![Command::RunRecipe.to_string()]
+    vec![
+      Command::DryRunRecipe.to_string(),
+      Command::Evaluate.to_string(),
+      Command::RunRecipe.to_string(),
+      Command::ShowLocations.to_string(),
+    ]
   }
 }
 
@@ -17,7 +25,10 @@ impl Display for Command {
       f,
       "{}",
       match self {
+        Command::DryRunRecipe => "just-lsp.dry_run_recipe",
+        Command::Evaluate => "just-lsp.evaluate",
         Command::RunRecipe => "just-lsp.run_recipe",
+        Command::ShowLocations => "just-lsp.show_locations",
       }
     )
   }
@@ -28,7 +39,10 @@ impl TryFrom<&str> for Command {
 
   fn try_from(value: &str) -> Result<Self, Self::Error> {
     match value {
+      "just-lsp.dry_run_recipe" => Ok(Command::DryRunRecipe),
+      "just-lsp.evaluate" => Ok(Command::Evaluate),
       "just-lsp.run_recipe" => Ok(Command::RunRecipe),
+      "just-lsp.show_locations" => Ok(Command::ShowLocations),
       _ => Err(anyhow!("Unknown command: {value}")),
     }
   }
@@ -2,17 +2,69 @@ use super::*;
 
 #[derive(Clone, Debug, Default, Deserialize)]
 pub struct Config {
+  #[serde(default, rename = "custom-rules")]
+  pub custom_rules: Vec<CustomRule>,
   #[serde(default)]
   pub formatting: FormattingConfig,
   #[serde(default)]
+  pub hover: HoverConfig,
+  #[serde(default)]
   pub rules: HashMap<String, RuleConfig>,
 }
 
 impl Config {
+  /// Parse configuration from `value`, dropping invalid custom rules and
+  /// returning a message explaining each one dropped.
+  ///
+  /// # Errors
+  ///
+  /// Returns a [`serde_json::Error`] if `value` isn't a valid configuration.
+  pub fn load(
+    value: serde_json::Value,
+  ) -> std::result::Result<(Self, Vec<String>), serde_json::Error> {
+    let mut config = serde_json::from_value::<Self>(value)?;
+
+    let errors = config.validate();
+
+    Ok((config, errors))
+  }
+
   #[must_use]
   pub fn rule_config(&self, id: &str) -> RuleConfig {
     self.rules.get(id).cloned().unwrap_or_default()
   }
+
+  /// Drop custom rules whose query doesn't compile, or whose id is already
+  /// taken by a built-in rule or an earlier custom rule, returning a message
+  /// explaining each one dropped.
+  pub fn validate(&mut self) -> Vec<String> {
+    let mut ids = inventory::iter::<&dyn Rule>
+      .into_iter()
+      .map(|rule| rule.id().to_string())
+      .collect::<HashSet<_>>();
+
+    let mut errors = Vec::new();
+
+    self.custom_rules.retain(|rule| {
+      if let Some(error) = rule.query.error() {
+        errors.push(format!(
+          "Invalid query for custom rule `{}`: {error}",
+          rule.id
+        ));
+      } else if !ids.insert(rule.id.clone()) {
+        errors.push(format!(
+          "Custom rule `{}` has the same id as another rule",
+          rule.id
+        ));
+      } else {
+        return true;
+      }
+
+      false
+    });
+
+    errors
+  }
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
@@ -20,6 +72,14 @@ pub struct FormattingConfig {
   pub indentation: Option<String>,
 }
 
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+pub struct HoverConfig {
+  /// Fall back to `just --evaluate` for variables whose value can't be
+  /// determined statically.
+  #[serde(default)]
+  pub evaluate: bool,
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
 #[serde(rename_all = "kebab-case")]
 pub enum RuleLevel {
@@ -118,6 +178,67 @@ mod tests {
     );
   }
 
+  #[test]
+  fn validate_drops_invalid_custom_rules() {
+    let mut config: Config = serde_json::from_value(json!({
+      "custom-rules": [
+        {
+          "id": "bad",
+          "message": "Bad",
+          "query": "(nonexistent_node)"
+        },
+        {
+          "id": "unused-variables",
+          "message": "Clashes",
+          "query": "(recipe) @diagnostic"
+        },
+        {
+          "id": "good",
+          "message": "Good",
+          "query": "(recipe) @diagnostic"
+        },
+        {
+          "id": "good",
+          "message": "Again",
+          "query": "(alias) @diagnostic"
+        }
+      ]
+    }))
+    .unwrap();
+
+    assert_eq!(
+      config.validate(),
+      [
+        "Invalid query for custom rule `bad`: Query error at 1:2. Invalid node type \"nonexistent_node\"",
+        "Custom rule `unused-variables` has the same id as another rule",
+        "Custom rule `good` has the same id as another rule",
+      ]
+    );
+
+    assert_eq!(
+      config
+        .custom_rules
+        .iter()
+        .map(|rule| rule.message.as_str())
+        .collect::<Vec<_>>(),
+      ["Good"]
+    );
+  }
+
+  #[test]
+  fn parses_hover_config() {
+    let config: Config = serde_json::from_value(json!({
+      "hover": {
+        "evaluate": true
+      }
+    }))
+    .unwrap();
+
+    assert!(config.hover.evaluate);
+
+    assert!(!Config::default().hover.evaluate);
+  }
+
   #[test]
   fn off_level_produces_none_severity() {
     let config: Config = serde_json::from_value(json!({
@@ -135,6 +256,50 @@ mod tests {
     );
   }
 
+  #[test]
+  fn parses_custom_rules() {
+    let config: Config = serde_json::from_value(json!({
+      "custom-rules": [
+        {
+          "id": "foo",
+          "message": "bar",
+          "query": "(recipe) @diagnostic"
+        },
+        {
+          "id": "baz",
+          "message": "qux",
+          "name": "Qux",
+          "query": "(alias) @diagnostic",
+          "severity": "error",
+          "fix": "quux"
+        }
+      ]
+    }))
+    .unwrap();
+
+    assert_eq!(
+      config.custom_rules,
+      vec![
+        CustomRule {
+          fix: None,
+          id: "foo".into(),
+          message: "bar".into(),
+          name: None,
+          query: CustomQuery::new("(recipe) @diagnostic"),
+          severity: RuleLevel::Warning,
+        },
+        CustomRule {
+          fix: Some("quux".into()),
+          id: "baz".into(),
+          message: "qux".into(),
+          name: Some("Qux".into()),
+          query: CustomQuery::new("(alias) @diagnostic"),
+          severity: RuleLevel::Error,
+        },
+      ]
+    );
+  }
+
   #[test]
   fn parses_rule_config_from_string() {
     let config: Config = serde_json::from_value(json!({
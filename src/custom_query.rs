@@ -0,0 +1,71 @@
+use {super::*, std::sync::Arc, tree_sitter::Query};
+
+/// The tree-sitter query of a custom rule, compiled against the just grammar
+/// once, when the config is loaded.
+#[derive(Clone, Debug)]
+pub struct CustomQuery {
+  compiled: std::result::Result<Arc<Query>, String>,
+  pub source: String,
+}
+
+impl CustomQuery {
+  /// The error the query failed to compile with, if any.
+  #[must_use]
+  pub fn error(&self) -> Option<&str> {
+    self.compiled.as_ref().err().map(String::as_str)
+  }
+
+  #[must_use]
+  pub fn new(source: impl Into<String>) -> Self {
+    let source = source.into();
+
+    Self {
+      // SAFETY: tree_sitter_just returns a valid static tree-sitter language.
+      compiled: Query::new(&unsafe { tree_sitter_just() }, &source)
+        .map(Arc::new)
+        .map_err(|error| error.to_string()),
+      source,
+    }
+  }
+
+  pub(crate) fn query(&self) -> Option<&Query> {
+    self.compiled.as_deref().ok()
+  }
+}
+
+impl<'de> Deserialize<'de> for CustomQuery {
+  fn deserialize<D: serde::Deserializer<'de>>(
+    deserializer: D,
+  ) -> std::result::Result<Self, D::Error> {
+    Ok(Self::new(String::deserialize(deserializer)?))
+  }
+}
+
+impl Eq for CustomQuery {}
+
+impl PartialEq for CustomQuery {
+  fn eq(&self, other: &Self) -> bool {
+    self.source == other.source
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn invalid_query() {
+    assert_eq!(
+      CustomQuery::new("(nonexistent_node)").error(),
+      Some("Query error at 1:2. Invalid node type \"nonexistent_node\"")
+    );
+  }
+
+  #[test]
+  fn valid_query() {
+    let query = CustomQuery::new("(recipe) @diagnostic");
+
+    assert_eq!(query.error(), None);
+    assert!(query.query().is_some());
+  }
+}
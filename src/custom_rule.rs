@@ -0,0 +1,217 @@
+use {
+  super::*,
+  tree_sitter::{QueryCursor, StreamingIterator},
+};
+
+struct Match {
+  captures: HashMap<String, String>,
+  range: lsp::Range,
+}
+
+/// A user-defined lint rule, written as a tree-sitter query against the just
+/// grammar.
+///
+/// Each match of `query` produces one diagnostic on the node captured as
+/// `@diagnostic`, or on the first captured node if the query has no such
+/// capture. `message` and `fix` may reference captures as `@name`, which are
+/// replaced with the captured source text; `@@` produces a literal `@`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct CustomRule {
+  /// Replacement text for the reported node, offered as a quickfix.
+  #[serde(default)]
+  pub fix: Option<String>,
+  pub id: String,
+  pub message: String,
+  /// A short label for the rule, shown as the title of its diagnostics on the
+  /// command line. Defaults to the rule's id.
+  #[serde(default)]
+  pub name: Option<String>,
+  pub query: CustomQuery,
+  #[serde(default = "CustomRule::default_severity")]
+  pub severity: RuleLevel,
+}
+
+impl CustomRule {
+  const DIAGNOSTIC_CAPTURE: &'static str = "diagnostic";
+
+  fn default_severity() -> RuleLevel {
+    RuleLevel::Warning
+  }
+
+  /// Run the query against `document`, returning one diagnostic per match.
+  #[must_use]
+  pub fn diagnostics(&self, document: &Document) -> Vec<Diagnostic> {
+    self
+      .matches(document)
+      .into_iter()
+      .map(|Match { captures, range }| {
+        Diagnostic::new(
+          Self::interpolate(&self.message, &captures),
+          range,
+          self.severity.into(),
+        )
+      })
+      .collect()
+  }
+
+  /// The short label of the rule, or its id if it has none.
+  #[must_use]
+  pub fn display(&self) -> &str {
+    self.name.as_deref().unwrap_or(&self.id)
+  }
+
+  /// Whether the rule is enabled, taking its configured level override into
+  /// account.
+  #[must_use]
+  pub fn enabled(&self, config: &RuleConfig) -> bool {
+    config.level().unwrap_or(self.severity) != RuleLevel::Off
+  }
+
+  fn interpolate(template: &str, captures: &HashMap<String, String>) -> String {
+    let mut output = String::new();
+
+    let mut characters = template.chars().peekable();
+
+    while let Some(character) = characters.next() {
+      if character != '@' {
+        output.push(character);
+        continue;
+      }
+
+      if characters.next_if_eq(&'@').is_some() {
+        output.push('@');
+        continue;
+      }
+
+      let mut name = String::new();
+
+      while let Some(character) = characters.next_if(|character| {
+        character.is_ascii_alphanumeric() || matches!(character, '_' | '-')
+      }) {
+        name.push(character);
+      }
+
+      if let Some(text) = captures.get(&name) {
+        output.push_str(text);
+      } else {
+        output.push('@');
+        output.push_str(&name);
+      }
+    }
+
+    output
+  }
+
+  /// Queries that failed to compile match nothing; the error is reported
+  /// when the config is loaded.
+  fn matches(&self, document: &Document) -> Vec<Match> {
+    let (Some(tree), Some(query)) = (&document.tree, self.query.query()) else {
+      return Vec::new();
+    };
+
+    let names = query.capture_names();
+
+    let source = document.content.to_string();
+
+    let mut cursor = QueryCursor::new();
+
+    let mut query_matches =
+      cursor.matches(query, tree.root_node(), source.as_bytes());
+
+    let mut matches = Vec::new();
+
+    while let Some(query_match) = query_matches.next() {
+      let Some(reported) = query_match
+        .captures
+        .iter()
+        .find(|capture| {
+          names[capture.index as usize] == Self::DIAGNOSTIC_CAPTURE
+        })
+        .or_else(|| query_match.captures.first())
+      else {
+        continue;
+      };
+
+      let captures = query_match
+        .captures
+        .iter()
+        .map(|capture| {
+          (
+            names[capture.index as usize].to_string(),
+            document.get_node_text(&capture.node),
+          )
+        })
+        .collect();
+
+      matches.push(Match {
+        captures,
+        range: reported.node.get_range(document),
+      });
+    }
+
+    matches
+  }
+
+  /// Return a quickfix per match, if the rule has a `fix` template.
+  #[must_use]
+  pub fn quickfixes(&self, document: &Document) -> Vec<Quickfix> {
+    let Some(fix) = &self.fix else {
+      return Vec::new();
+    };
+
+    self
+      .matches(document)
+      .into_iter()
+      .map(|Match { captures, range }| Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range,
+          new_text: Self::interpolate(fix, &captures),
+        }],
+        range,
+        safe: false,
+        title: format!("Apply fix for `{}`", self.id),
+        uri: None,
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, pretty_assertions::assert_eq};
+
+  fn captures(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+      .iter()
+      .map(|(name, text)| ((*name).to_string(), (*text).to_string()))
+      .collect()
+  }
+
+  #[test]
+  fn interpolate_escaped_at() {
+    assert_eq!(
+      CustomRule::interpolate("@@foo", &captures(&[("foo", "bar")])),
+      "@foo"
+    );
+  }
+
+  #[test]
+  fn interpolate_known_capture() {
+    assert_eq!(
+      CustomRule::interpolate(
+        "Recipe `@name` is missing a doc.",
+        &captures(&[("name", "build")])
+      ),
+      "Recipe `build` is missing a doc."
+    );
+  }
+
+  #[test]
+  fn interpolate_unknown_capture() {
+    assert_eq!(
+      CustomRule::interpolate("@foo and @bar", &captures(&[("foo", "baz")])),
+      "baz and @bar"
+    );
+  }
+}
@@ -10,6 +10,9 @@ pub struct Diagnostic {
   pub message: String,
   /// The range in the source code where the diagnostic applies.
   pub range: lsp::Range,
+  /// Other locations relevant to the diagnostic, possibly in other
+  /// documents.
+  pub related: Vec<lsp::DiagnosticRelatedInformation>,
   /// The severity level of the diagnostic.
   pub severity: lsp::DiagnosticSeverity,
 }
@@ -29,10 +32,31 @@ impl Diagnostic {
       id: String::new(),
       message: message.into(),
       range,
+      related: Vec::new(),
       severity,
     }
   }
 
+  #[must_use]
+  pub fn related(
+    self,
+    uri: lsp::Url,
+    range: lsp::Range,
+    message: impl Into<String>,
+  ) -> Self {
+    Self {
+      related: self
+        .related
+        .into_iter()
+        .chain([lsp::DiagnosticRelatedInformation {
+          location: lsp::Location { uri, range },
+          message: message.into(),
+        }])
+        .collect(),
+      ..self
+    }
+  }
+
   pub fn warning(message: impl Into<String>, range: lsp::Range) -> Self {
     Self::new(message, range, lsp::DiagnosticSeverity::WARNING)
   }
@@ -44,6 +68,7 @@ impl From<Diagnostic> for lsp::Diagnostic {
       code: Some(lsp::NumberOrString::String(value.id)),
       message: value.message,
       range: value.range,
+      related_information: (!value.related.is_empty()).then_some(value.related),
       severity: Some(value.severity),
       source: Some("just-lsp".to_string()),
       ..Default::default()
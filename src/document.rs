@@ -134,6 +134,16 @@ impl Document {
       .collect()
   }
 
+  /// The last path segment of the document's URI, for use in messages.
+  #[must_use]
+  pub fn file_name(&self) -> &str {
+    self
+      .uri
+      .path_segments()
+      .and_then(Iterator::last)
+      .unwrap_or(self.uri.as_str())
+  }
+
   #[must_use]
   pub fn find_function(&self, name: &str) -> Option<Function> {
     self
@@ -477,7 +487,10 @@ impl Document {
                           .find("dependency_expression")
                           .and_then(|node| node.child_by_field_name("name"))
                       })
-                      .map(|node| self.get_node_text(&node))
+                      .map(|node| TextNode {
+                        value: self.get_node_text(&node),
+                        range: node.get_range(self),
+                      })
                     else {
                       continue;
                     };
@@ -2309,7 +2322,10 @@ mod tests {
         },
         attributes: vec![],
         dependencies: vec![Dependency {
-          name: "foo".into(),
+          name: TextNode {
+            value: "foo".into(),
+            range: lsp::Range::at(3, 5, 3, 8),
+          },
           arguments: vec![],
           mapped: None,
           phase: DependencyPhase::Prior,
@@ -2344,7 +2360,10 @@ mod tests {
         },
         attributes: vec![],
         dependencies: vec![Dependency {
-          name: "foo".into(),
+          name: TextNode {
+            value: "foo".into(),
+            range: lsp::Range::at(3, 6, 3, 9),
+          },
           arguments: vec![
             DependencyArgument {
               value: "'value1'".into(),
@@ -2408,7 +2427,10 @@ mod tests {
         },
         attributes: vec![],
         dependencies: vec![Dependency {
-          name: "bar".into(),
+          name: TextNode {
+            value: "bar".into(),
+            range: lsp::Range::at(3, 12, 3, 15),
+          },
           arguments: vec![
             DependencyArgument {
               value: "args".into(),
@@ -2464,7 +2486,10 @@ mod tests {
         },
         attributes: vec![],
         dependencies: vec![Dependency {
-          name: "tools::foo".into(),
+          name: TextNode {
+            value: "tools::foo".into(),
+            range: lsp::Range::at(6, 5, 6, 15),
+          },
           arguments: vec![],
           mapped: None,
           phase: DependencyPhase::Prior,
@@ -2503,14 +2528,20 @@ mod tests {
         attributes: vec![],
         dependencies: vec![
           Dependency {
-            name: "foo".into(),
+            name: TextNode {
+              value: "foo".into(),
+              range: lsp::Range::at(6, 5, 6, 8),
+            },
             arguments: vec![],
             mapped: None,
             phase: DependencyPhase::Prior,
             range: lsp::Range::at(6, 5, 6, 8),
           },
           Dependency {
-            name: "bar".into(),
+            name: TextNode {
+              value: "bar".into(),
+              range: lsp::Range::at(6, 9, 6, 12),
+            },
             arguments: vec![],
             mapped: None,
             phase: DependencyPhase::Prior,
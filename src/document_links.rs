@@ -0,0 +1,448 @@
+use super::*;
+
+/// Links to the files and directories named by literal paths in settings,
+/// attributes and function arguments, and to the URLs in comments and
+/// `[doc]` strings.
+///
+/// Import and module links depend on project resolution and are produced
+/// by the server.
+pub(crate) struct DocumentLinks<'a> {
+  document: &'a Document,
+}
+
+impl<'a> DocumentLinks<'a> {
+  const PATH_FUNCTIONS: [&'static str; 4] =
+    ["blake3_file", "path_exists", "read", "sha256_file"];
+
+  const PATH_SETTINGS: [&'static str; 3] =
+    ["dotenv-path", "tempdir", "working-directory"];
+
+  /// Paths in `[working-directory]` and `[script]` arguments, and URLs in
+  /// `[doc]` arguments.
+  fn attributes(&self, root: Node) -> Vec<lsp::DocumentLink> {
+    let mut links = Vec::new();
+
+    for attribute in root.find_all("attribute") {
+      let mut cursor = attribute.walk();
+
+      let mut name = String::new();
+
+      for child in attribute.named_children(&mut cursor) {
+        if child.kind() == "identifier" {
+          name = self.document.get_node_text(&child);
+          continue;
+        }
+
+        match name.as_str() {
+          "doc" => links.extend(self.string_urls(child)),
+          "script" => links.extend(
+            self.path(child).filter(|_| self.script_file_exists(child)),
+          ),
+          "working-directory" => links.extend(self.path(child)),
+          _ => {}
+        }
+      }
+    }
+
+    links
+  }
+
+  /// URLs in comments.
+  fn comments(&self, root: Node) -> Vec<lsp::DocumentLink> {
+    root
+      .find_all("comment")
+      .into_iter()
+      .flat_map(|comment| {
+        let text = self.document.get_node_text(&comment);
+
+        Self::urls(&text)
+          .into_iter()
+          .filter_map(|range| {
+            let start = comment.start_byte() + range.start;
+            let end = comment.start_byte() + range.end;
+
+            Self::url_link(
+              &text[range],
+              lsp::Range {
+                start: self.document.content.byte_to_lsp_position(start),
+                end: self.document.content.byte_to_lsp_position(end),
+              },
+            )
+          })
+          .collect::<Vec<_>>()
+      })
+      .collect()
+  }
+
+  /// `dotenv-filename` names a file that just looks for in the justfile's
+  /// directory and its ancestors, so link the first one that exists.
+  fn dotenv_filename(&self, node: Node) -> Option<lsp::DocumentLink> {
+    let literal = StringLiteral::new(self.document, node)?;
+
+    if literal.text.is_empty() {
+      return None;
+    }
+
+    let path = self.document.uri.to_file_path().ok()?;
+
+    let path = path
+      .parent()?
+      .ancestors()
+      .map(|directory| directory.join(&literal.text))
+      .find(|path| path.is_file())?;
+
+    self.link(node, &path)
+  }
+
+  /// Paths passed as the first argument of filesystem functions.
+  fn functions(&self, root: Node) -> Vec<lsp::DocumentLink> {
+    root
+      .find_all("function_call")
+      .into_iter()
+      .filter(|call| {
+        call.child_by_field_name("name").is_some_and(|name| {
+          Self::PATH_FUNCTIONS
+            .contains(&self.document.get_node_text(&name).as_str())
+        })
+      })
+      .filter_map(|call| {
+        self.path(call.child_by_field_name("arguments")?.named_child(0)?)
+      })
+      .collect()
+  }
+
+  /// A link covering `node`, targeting `path`.
+  fn link(&self, node: Node, path: &Path) -> Option<lsp::DocumentLink> {
+    Some(lsp::DocumentLink {
+      range: node.get_range(self.document),
+      target: Some(lsp::Url::from_file_path(path).ok()?),
+      tooltip: Some(path.display().to_string()),
+      data: None,
+    })
+  }
+
+  /// Every link in the document, ordered by position.
+  pub(crate) fn links(&self) -> Vec<lsp::DocumentLink> {
+    let Some(tree) = self.document.tree.as_ref() else {
+      return Vec::new();
+    };
+
+    let root = tree.root_node();
+
+    let mut links = self.attributes(root);
+
+    links.extend(self.comments(root));
+    links.extend(self.functions(root));
+    links.extend(self.settings(root));
+
+    links.sort_by_key(|link| (link.range.start, link.range.end));
+
+    links
+  }
+
+  pub(crate) fn new(document: &'a Document) -> Self {
+    Self { document }
+  }
+
+  /// A link covering the string literal `node`, targeting its value
+  /// resolved against the justfile's directory.
+  fn path(&self, node: Node) -> Option<lsp::DocumentLink> {
+    self.link(node, &self.resolve(node)?)
+  }
+
+  /// The value of the string literal `node` joined onto the justfile's
+  /// directory, unless it is empty.
+  fn resolve(&self, node: Node) -> Option<PathBuf> {
+    let literal = StringLiteral::new(self.document, node)?;
+
+    if literal.text.is_empty() {
+      return None;
+    }
+
+    let path = self.document.uri.to_file_path().ok()?;
+
+    Some(path.parent()?.join(&literal.text).lexiclean())
+  }
+
+  /// `[script]` arguments name the interpreter and its flags as well as
+  /// files, so only link those that exist.
+  fn script_file_exists(&self, node: Node) -> bool {
+    self.resolve(node).is_some_and(|path| path.is_file())
+  }
+
+  /// Paths in settings that name files or directories.
+  fn settings(&self, root: Node) -> Vec<lsp::DocumentLink> {
+    root
+      .find_all("setting")
+      .into_iter()
+      .filter_map(|setting| {
+        let name = self
+          .document
+          .get_node_text(&setting.child_by_field_name("left")?);
+
+        let mut cursor = setting.walk();
+
+        let value = setting
+          .children_by_field_name("right", &mut cursor)
+          .find(|child| matches!(child.kind(), "expression" | "string"))?;
+
+        if name == "dotenv-filename" {
+          self.dotenv_filename(value)
+        } else if Self::PATH_SETTINGS.contains(&name.as_str()) {
+          self.path(value)
+        } else {
+          None
+        }
+      })
+      .collect()
+  }
+
+  /// URLs in the string literal `node`.
+  fn string_urls(&self, node: Node) -> Vec<lsp::DocumentLink> {
+    let Some(literal) = StringLiteral::new(self.document, node) else {
+      return Vec::new();
+    };
+
+    Self::urls(&literal.text)
+      .into_iter()
+      .filter_map(|range| {
+        Self::url_link(
+          &literal.text[range.clone()],
+          literal.range(self.document, range.start, range.end),
+        )
+      })
+      .collect()
+  }
+
+  fn url_link(url: &str, range: lsp::Range) -> Option<lsp::DocumentLink> {
+    Some(lsp::DocumentLink {
+      range,
+      target: Some(lsp::Url::parse(url).ok()?),
+      tooltip: None,
+      data: None,
+    })
+  }
+
+  /// The byte ranges of `http` and `https` URLs in `text`, without trailing
+  /// punctuation or an unbalanced closing parenthesis.
+  fn urls(text: &str) -> Vec<Range<usize>> {
+    let mut urls = Vec::new();
+
+    let mut offset = 0;
+
+    while let Some(start) = ["http://", "https://"]
+      .iter()
+      .filter_map(|scheme| text[offset..].find(scheme))
+      .min()
+      .map(|start| offset + start)
+    {
+      let mut end = text[start..]
+        .find(|c: char| c.is_whitespace() || "<>\"'`".contains(c))
+        .map_or(text.len(), |length| start + length);
+
+      loop {
+        let url = &text[start..end];
+
+        if url.ends_with(['.', ',', ';', ':', '!', '?'])
+          || (url.ends_with(')')
+            && url.matches(')').count() > url.matches('(').count())
+        {
+          end -= 1;
+        } else {
+          break;
+        }
+      }
+
+      if !text[start..end].ends_with("://") {
+        urls.push(start..end);
+      }
+
+      offset = end.max(start + 1);
+    }
+
+    urls
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, indoc::indoc, pretty_assertions::assert_eq};
+
+  fn links(content: &str) -> Vec<(lsp::Range, String)> {
+    let document = Document::new(
+      content,
+      lsp::Url::from_file_path(if cfg!(windows) {
+        "C:\\foo\\justfile"
+      } else {
+        "/foo/justfile"
+      })
+      .unwrap(),
+    )
+    .unwrap();
+
+    DocumentLinks::new(&document)
+      .links()
+      .into_iter()
+      .map(|link| (link.range, link.target.unwrap().to_string()))
+      .collect()
+  }
+
+  fn path(relative: &str) -> String {
+    let base = if cfg!(windows) { "C:\\foo" } else { "/foo" };
+
+    lsp::Url::from_file_path(Path::new(base).join(relative).lexiclean())
+      .unwrap()
+      .to_string()
+  }
+
+  #[test]
+  fn comment_urls() {
+    assert_eq!(
+      links(indoc! {
+        "
+        # See https://just.systems/man/en/.
+        x := 'a' # (docs: http://example.com/a_(b))
+        "
+      }),
+      [
+        (
+          lsp::Range::at(0, 6, 0, 34),
+          "https://just.systems/man/en/".into()
+        ),
+        (
+          lsp::Range::at(1, 18, 1, 42),
+          "http://example.com/a_(b)".into()
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn dotenv_filename_searches_ancestors() {
+    let tempdir = tempfile::tempdir().unwrap();
+
+    let directory = tempdir.path().join("sub");
+
+    fs::create_dir(&directory).unwrap();
+
+    fs::write(tempdir.path().join(".env"), "").unwrap();
+
+    let document = Document::new(
+      "set dotenv-filename := '.env'\nset dotenv-path := '.env'\n",
+      lsp::Url::from_file_path(directory.join("justfile")).unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+      DocumentLinks::new(&document)
+        .links()
+        .into_iter()
+        .map(|link| (link.range, link.target.unwrap()))
+        .collect::<Vec<_>>(),
+      [
+        (
+          lsp::Range::at(0, 23, 0, 29),
+          lsp::Url::from_file_path(tempdir.path().join(".env")).unwrap()
+        ),
+        (
+          lsp::Range::at(1, 19, 1, 25),
+          lsp::Url::from_file_path(directory.join(".env")).unwrap()
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn doc_attribute_urls() {
+    assert_eq!(
+      links(indoc! {
+        "
+        [doc('Deploy, see https://example.com')]
+        deploy:
+        "
+      }),
+      [(lsp::Range::at(0, 18, 0, 37), "https://example.com/".into())]
+    );
+  }
+
+  #[test]
+  fn empty_paths_are_ignored() {
+    assert_eq!(links("set tempdir := ''\nx := read('')\n"), []);
+  }
+
+  #[test]
+  fn function_arguments() {
+    assert_eq!(
+      links(indoc! {
+        "
+        a := read('a.txt')
+        b := path_exists('../b')
+        c := sha256_file('c') + blake3_file(\"d\")
+        d := env('e')
+        "
+      }),
+      [
+        (lsp::Range::at(0, 10, 0, 17), path("a.txt")),
+        (lsp::Range::at(1, 17, 1, 23), path("../b")),
+        (lsp::Range::at(2, 17, 2, 20), path("c")),
+        (lsp::Range::at(2, 36, 2, 39), path("d")),
+      ]
+    );
+  }
+
+  #[test]
+  fn script_attribute_links_existing_files_only() {
+    assert_eq!(
+      links(indoc! {
+        "
+        [script('python3', 'missing.py')]
+        foo:
+          print(1)
+        "
+      }),
+      []
+    );
+  }
+
+  #[test]
+  fn settings() {
+    assert_eq!(
+      links(indoc! {
+        "
+        set dotenv-filename := '.env.local'
+        set dotenv-path := 'config/.env'
+        set tempdir := 'tmp'
+        set working-directory := 'src'
+        set shell := ['bash', '-c']
+        "
+      }),
+      [
+        (lsp::Range::at(1, 19, 1, 32), path("config/.env")),
+        (lsp::Range::at(2, 15, 2, 20), path("tmp")),
+        (lsp::Range::at(3, 25, 3, 30), path("src")),
+      ]
+    );
+  }
+
+  #[test]
+  fn urls() {
+    assert_eq!(
+      DocumentLinks::urls("see https://a.com, http://b.com/x. and http://"),
+      [4..17, 19..33]
+    );
+  }
+
+  #[test]
+  fn working_directory_attribute() {
+    assert_eq!(
+      links(indoc! {
+        "
+        [working-directory('build')]
+        foo:
+          make
+        "
+      }),
+      [(lsp::Range::at(0, 19, 0, 26), path("build"))]
+    );
+  }
+}
@@ -0,0 +1,90 @@
+use super::*;
+
+/// A language that recipe bodies and commands can be written in, and that
+/// can be parsed with one of the vendored grammars.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EmbeddedLanguage {
+  Python,
+  Shell,
+}
+
+impl EmbeddedLanguage {
+  const SHELLS: [&'static str; 7] =
+    ["ash", "bash", "dash", "ksh", "mksh", "sh", "zsh"];
+
+  /// The language of the interpreter that `command` runs, looking through
+  /// `env` and its flags.
+  #[must_use]
+  pub fn from_command<'a>(
+    command: impl IntoIterator<Item = &'a str>,
+  ) -> Option<Self> {
+    let mut words = command.into_iter();
+
+    let mut program = Self::program(words.next()?);
+
+    if program == "env" {
+      program = Self::program(words.find(|word| !word.starts_with('-'))?);
+    }
+
+    if Self::SHELLS.contains(&program) {
+      return Some(Self::Shell);
+    }
+
+    let python = program == "uv"
+      || program.strip_prefix("python").is_some_and(|version| {
+        version
+          .chars()
+          .all(|character| character.is_ascii_digit() || character == '.')
+      });
+
+    python.then_some(Self::Python)
+  }
+
+  #[must_use]
+  pub fn language(self) -> Language {
+    match self {
+      // SAFETY: tree_sitter_python returns a static language definition.
+      Self::Python => unsafe { tree_sitter_python() },
+      // SAFETY: tree_sitter_bash returns a static language definition.
+      Self::Shell => unsafe { tree_sitter_bash() },
+    }
+  }
+
+  fn program(path: &str) -> &str {
+    Path::new(path)
+      .file_name()
+      .and_then(|name| name.to_str())
+      .unwrap_or(path)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn from_command() {
+    #[track_caller]
+    fn case(command: &str, expected: Option<EmbeddedLanguage>) {
+      assert_eq!(
+        EmbeddedLanguage::from_command(command.split_whitespace()),
+        expected
+      );
+    }
+
+    case("bash -cu", Some(EmbeddedLanguage::Shell));
+    case("/bin/sh", Some(EmbeddedLanguage::Shell));
+    case("/usr/bin/env bash", Some(EmbeddedLanguage::Shell));
+    case("/usr/bin/env -S zsh -e", Some(EmbeddedLanguage::Shell));
+    case("/usr/bin/env python3", Some(EmbeddedLanguage::Python));
+    case("python3.12", Some(EmbeddedLanguage::Python));
+    case(
+      "/usr/bin/env -S uv run --script",
+      Some(EmbeddedLanguage::Python),
+    );
+    case("pythonista", None);
+    case("pwsh -Command", None);
+    case("nu", None);
+    case("", None);
+  }
+}
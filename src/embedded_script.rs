@@ -0,0 +1,316 @@
+use super::*;
+
+/// A piece of a document written in another language, such as a recipe line,
+/// a script recipe body or a backtick, with interpolations masked and the
+/// document byte offset of each byte of its text.
+#[derive(Debug)]
+pub struct EmbeddedScript {
+  pub language: EmbeddedLanguage,
+  offsets: Vec<usize>,
+  pub text: String,
+}
+
+impl EmbeddedScript {
+  /// Every embedded script in `document` whose interpreter can be
+  /// determined and has a vendored grammar.
+  #[must_use]
+  pub fn all(document: &Document) -> Vec<Self> {
+    let Some(tree) = document.tree.as_ref() else {
+      return Vec::new();
+    };
+
+    let root = tree.root_node();
+
+    let masked = Self::mask(document, root);
+
+    let shell = Self::interpreter(document, root, "shell", "sh");
+
+    let script_interpreter =
+      Self::interpreter(document, root, "script-interpreter", "sh");
+
+    let default_script = document.settings().iter().any(|setting| {
+      setting.name.value == "default-script"
+        && matches!(setting.kind, SettingKind::Boolean(true))
+    });
+
+    let mut scripts = Vec::new();
+
+    for body in root.find_all("recipe_body") {
+      let Some(recipe) = body.get_recipe(document) else {
+        continue;
+      };
+
+      let script = if let Some(shebang) = body.child_by_field_name("shebang") {
+        let shebang = document.get_node_text(&shebang);
+
+        Some(EmbeddedLanguage::from_command(
+          shebang.trim_start_matches("#!").split_whitespace(),
+        ))
+      } else if let Some(attribute) = recipe.find_attribute("script") {
+        Some(match attribute.arguments.first() {
+          Some(argument) => argument
+            .value
+            .literal()
+            .and_then(|command| EmbeddedLanguage::from_command([&*command])),
+          None => script_interpreter,
+        })
+      } else if default_script && !recipe.has_attribute("shell") {
+        Some(script_interpreter)
+      } else {
+        None
+      };
+
+      match script {
+        Some(language) => {
+          scripts.extend(language.and_then(|language| {
+            Self::body(document, &masked, body, language)
+          }));
+        }
+        None => {
+          if let Some(language) = shell {
+            scripts.extend(Self::lines(&masked, body, language));
+          }
+        }
+      }
+    }
+
+    let Some(language) = shell else {
+      return scripts;
+    };
+
+    for command in root.find_all("command_body") {
+      scripts.push(Self::slice(
+        &masked,
+        command.start_byte(),
+        command.end_byte(),
+        language,
+      ));
+    }
+
+    for call in root.find_all("function_call") {
+      if call
+        .child_by_field_name("name")
+        .is_none_or(|name| document.get_node_text(&name) != "shell")
+      {
+        continue;
+      }
+
+      let Some(literal) = call
+        .child_by_field_name("arguments")
+        .and_then(|arguments| arguments.named_child(0))
+        .and_then(|argument| StringLiteral::new(document, argument))
+      else {
+        continue;
+      };
+
+      scripts.push(Self {
+        language,
+        offsets: (0..=literal.text.len())
+          .map(|index| literal.offset(index))
+          .collect(),
+        text: literal.text,
+      });
+    }
+
+    scripts
+  }
+
+  /// The whole body of a script or shebang recipe, with its common
+  /// indentation removed.
+  fn body(
+    document: &Document,
+    masked: &str,
+    body: Node,
+    language: EmbeddedLanguage,
+  ) -> Option<Self> {
+    let mut cursor = body.walk();
+
+    let lines = body
+      .children(&mut cursor)
+      .filter(|child| child.kind() == "recipe_line")
+      .collect::<Vec<_>>();
+
+    let (first, last) = (lines.first()?, lines.last()?);
+
+    let rows = (first.start_position().row..=last.end_position().row)
+      .map(|row| {
+        let start = document.content.line_to_byte(row);
+
+        let line = masked[start..]
+          .split('\n')
+          .next()
+          .unwrap_or_default()
+          .trim_end_matches('\r');
+
+        (start, line)
+      })
+      .collect::<Vec<_>>();
+
+    let indentation = rows
+      .iter()
+      .filter(|(_, line)| !line.trim().is_empty())
+      .map(|(_, line)| line.len() - line.trim_start().len())
+      .min()
+      .unwrap_or_default();
+
+    let mut script = Self {
+      language,
+      offsets: Vec::new(),
+      text: String::new(),
+    };
+
+    for (start, line) in rows {
+      let (start, line) = match line.get(indentation..) {
+        Some(line) => (start + indentation, line),
+        None => (start + line.len(), ""),
+      };
+
+      script.text.push_str(line);
+      script.text.push('\n');
+
+      script
+        .offsets
+        .extend((0..=line.len()).map(|index| start + index));
+    }
+
+    script.offsets.push(last.end_byte());
+
+    Some(script)
+  }
+
+  /// The language of the interpreter configured by setting `name`, or of
+  /// `default` when it's unset, ignoring settings only for Windows.
+  fn interpreter(
+    document: &Document,
+    root: Node,
+    name: &str,
+    default: &str,
+  ) -> Option<EmbeddedLanguage> {
+    let setting = root.find_all("setting").into_iter().find(|node| {
+      Setting::from_node(node, document).is_some_and(|setting| {
+        setting.name.value == name
+          && !setting
+            .attributes
+            .iter()
+            .any(|attribute| attribute.name.value == "windows")
+      })
+    });
+
+    let Some(setting) = setting else {
+      return EmbeddedLanguage::from_command(default.split_whitespace());
+    };
+
+    let command = setting
+      .find("string")
+      .and_then(|string| StringLiteral::new(document, string))?;
+
+    EmbeddedLanguage::from_command(command.text.split_whitespace())
+  }
+
+  /// Each line of a linewise recipe body, joining lines continued with a
+  /// trailing backslash.
+  fn lines(masked: &str, body: Node, language: EmbeddedLanguage) -> Vec<Self> {
+    let mut cursor = body.walk();
+
+    let mut scripts = Vec::new();
+
+    let (mut start, mut end) = (None, 0);
+
+    for line in body.children(&mut cursor) {
+      if line.kind() != "recipe_line" {
+        continue;
+      }
+
+      end = line.end_byte();
+
+      let first = *start.get_or_insert(line.start_byte());
+
+      if masked[..end].ends_with('\\') {
+        continue;
+      }
+
+      scripts.push(Self::slice(masked, first, end, language));
+
+      start = None;
+    }
+
+    if let Some(first) = start {
+      scripts.push(Self::slice(masked, first, end, language));
+    }
+
+    scripts
+  }
+
+  /// The document text, with interpolations in recipe bodies and backticks
+  /// replaced by underscores and recipe line prefixes replaced by spaces, so
+  /// that byte offsets are preserved.
+  fn mask(document: &Document, root: Node) -> String {
+    let mut bytes = document.content.to_string().into_bytes();
+
+    for interpolation in root.find_all("interpolation") {
+      if !interpolation.has_any_parent(&["recipe_body", "command_body"]) {
+        continue;
+      }
+
+      for byte in
+        &mut bytes[interpolation.start_byte()..interpolation.end_byte()]
+      {
+        if *byte != b'\n' {
+          *byte = b'_';
+        }
+      }
+    }
+
+    for prefix in root.find_all("recipe_line_prefix") {
+      bytes[prefix.start_byte()..prefix.end_byte()].fill(b' ');
+    }
+
+    String::from_utf8(bytes).unwrap_or_default()
+  }
+
+  /// Parse the script with its language's grammar.
+  #[must_use]
+  pub fn parse(&self, parser: &mut Parser) -> Option<Tree> {
+    parser.set_language(&self.language.language()).ok()?;
+
+    parser.parse(&self.text, None)
+  }
+
+  /// The document range covering bytes `start..end` of the script text.
+  #[must_use]
+  pub fn range(
+    &self,
+    document: &Document,
+    start: usize,
+    end: usize,
+  ) -> lsp::Range {
+    let offset = |index: usize| self.offsets[index.min(self.offsets.len() - 1)];
+
+    lsp::Range {
+      start: document.content.byte_to_lsp_position(offset(start)),
+      end: document.content.byte_to_lsp_position(offset(end)),
+    }
+  }
+
+  /// The language of the shell that linewise recipes and backticks in
+  /// `document` run with.
+  #[must_use]
+  pub fn shell(document: &Document) -> Option<EmbeddedLanguage> {
+    let tree = document.tree.as_ref()?;
+
+    Self::interpreter(document, tree.root_node(), "shell", "sh")
+  }
+
+  fn slice(
+    masked: &str,
+    start: usize,
+    end: usize,
+    language: EmbeddedLanguage,
+  ) -> Self {
+    Self {
+      language,
+      offsets: (start..=end).collect(),
+      text: masked[start..end].to_string(),
+    }
+  }
+}
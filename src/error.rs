@@ -10,4 +10,6 @@ pub enum Error {
   Io(#[from] std::io::Error),
   #[error(transparent)]
   LanguageError(#[from] tree_sitter::LanguageError),
+  #[error(transparent)]
+  QueryError(#[from] tree_sitter::QueryError),
 }
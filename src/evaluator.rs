@@ -0,0 +1,629 @@
+use {
+  super::*,
+  heck::{
+    ToKebabCase, ToLowerCamelCase, ToShoutyKebabCase, ToShoutySnakeCase,
+    ToSnakeCase, ToTitleCase, ToUpperCamelCase,
+  },
+  regex::Regex,
+  std::fmt::Write,
+};
+
+/// Statically evaluates just expressions.
+///
+/// Evaluation covers string literals, format strings, concatenation, path
+/// joins, conditionals, regex matches, variable references resolved through
+/// the project view, and builtins whose result depends only on their
+/// arguments. Anything whose value depends on the environment, such as
+/// backticks, `env`, `os` or recipe parameters, evaluates to `None`.
+#[derive(Debug)]
+pub struct Evaluator<'a> {
+  home: Option<PathBuf>,
+  view: &'a ProjectView<'a>,
+}
+
+impl<'a> Evaluator<'a> {
+  fn builtin(
+    &self,
+    document: &Document,
+    name: &str,
+    arguments: &[String],
+  ) -> Option<String> {
+    let canonical = BUILTINS.iter().find_map(|builtin| match builtin {
+      Builtin::Function {
+        name: builtin_name,
+        aliases,
+        ..
+      } if *builtin_name == name || aliases.contains(&name) => {
+        Some(*builtin_name)
+      }
+      _ => None,
+    })?;
+
+    let value = match (canonical, arguments) {
+      ("append", [suffix, words]) => words
+        .split_whitespace()
+        .map(|word| format!("{word}{suffix}"))
+        .collect::<Vec<_>>()
+        .join(" "),
+      ("capitalize", [value]) => {
+        let mut characters = value.chars();
+
+        characters.next().map_or_else(String::new, |first| {
+          first
+            .to_uppercase()
+            .chain(characters.flat_map(char::to_lowercase))
+            .collect()
+        })
+      }
+      ("clean", [path]) => Path::new(path).lexiclean().to_str()?.to_string(),
+      ("encode_uri_component", [value]) => Self::encode_uri_component(value),
+      ("extension", [path]) => {
+        Path::new(path).extension()?.to_str()?.to_string()
+      }
+      ("file_name", [path]) => {
+        Path::new(path).file_name()?.to_str()?.to_string()
+      }
+      ("file_stem", [path]) => {
+        Path::new(path).file_stem()?.to_str()?.to_string()
+      }
+      ("join", [base, rest @ ..]) if !rest.is_empty() => rest
+        .iter()
+        .fold(PathBuf::from(base), |path, component| path.join(component))
+        .to_str()?
+        .to_string(),
+      ("home_directory", []) => self.home.as_ref()?.to_str()?.to_string(),
+      ("justfile", []) => Self::path(self.view.root())?,
+      ("justfile_directory", []) => Self::directory(self.view.root())?,
+      ("kebabcase", [value]) => value.to_kebab_case(),
+      ("lowercamelcase", [value]) => value.to_lower_camel_case(),
+      ("lowercase", [value]) => value.to_lowercase(),
+      ("parent_directory", [path]) => {
+        Path::new(path).parent()?.to_str()?.to_string()
+      }
+      ("prepend", [prefix, words]) => words
+        .split_whitespace()
+        .map(|word| format!("{prefix}{word}"))
+        .collect::<Vec<_>>()
+        .join(" "),
+      ("quote", [value]) => format!("'{}'", value.replace('\'', "'\\''")),
+      ("replace", [value, from, to]) => value.replace(from.as_str(), to),
+      ("replace_regex", [value, regex, replacement]) => Regex::new(regex)
+        .ok()?
+        .replace_all(value, replacement.as_str())
+        .into_owned(),
+      ("shoutykebabcase", [value]) => value.to_shouty_kebab_case(),
+      ("shoutysnakecase", [value]) => value.to_shouty_snake_case(),
+      ("snakecase", [value]) => value.to_snake_case(),
+      ("source_directory", []) => Self::directory(document)?,
+      ("source_file", []) => Self::path(document)?,
+      ("titlecase", [value]) => value.to_title_case(),
+      ("trim", [value]) => value.trim().to_string(),
+      ("trim_end", [value]) => value.trim_end().to_string(),
+      ("trim_end_match", [value, suffix]) => value
+        .strip_suffix(suffix.as_str())
+        .unwrap_or(value)
+        .to_string(),
+      ("trim_end_matches", [value, suffix]) => {
+        value.trim_end_matches(suffix.as_str()).to_string()
+      }
+      ("trim_start", [value]) => value.trim_start().to_string(),
+      ("trim_start_match", [value, prefix]) => value
+        .strip_prefix(prefix.as_str())
+        .unwrap_or(value)
+        .to_string(),
+      ("trim_start_matches", [value, prefix]) => {
+        value.trim_start_matches(prefix.as_str()).to_string()
+      }
+      ("uppercamelcase", [value]) => value.to_upper_camel_case(),
+      ("uppercase", [value]) => value.to_uppercase(),
+      ("without_extension", [path]) => {
+        let path = Path::new(path);
+
+        path.parent()?.join(path.file_stem()?).to_str()?.to_string()
+      }
+      _ => return None,
+    };
+
+    Some(value)
+  }
+
+  fn condition(
+    &self,
+    document: &Document,
+    node: Node,
+    stack: &mut Vec<String>,
+  ) -> Option<bool> {
+    let expression = if node.kind() == "condition" {
+      node.named_child(0)?
+    } else {
+      node
+    };
+
+    let mut cursor = expression.walk();
+
+    let children = expression.children(&mut cursor).collect::<Vec<_>>();
+
+    let [lhs, operator, rhs] = children.as_slice() else {
+      return None;
+    };
+
+    let lhs = self.expression(document, *lhs, stack)?;
+
+    let rhs = self.expression(document, *rhs, stack)?;
+
+    match operator.kind() {
+      "==" => Some(lhs == rhs),
+      "!=" => Some(lhs != rhs),
+      "=~" => Some(Regex::new(&rhs).ok()?.is_match(&lhs)),
+      "!~" => Some(!Regex::new(&rhs).ok()?.is_match(&lhs)),
+      _ => None,
+    }
+  }
+
+  fn directory(document: &Document) -> Option<String> {
+    Some(
+      document
+        .uri
+        .to_file_path()
+        .ok()?
+        .parent()?
+        .to_str()?
+        .to_string(),
+    )
+  }
+
+  fn encode_uri_component(value: &str) -> String {
+    let mut encoded = String::new();
+
+    for byte in value.bytes() {
+      if byte.is_ascii_alphanumeric()
+        || matches!(
+          byte,
+          b'-' | b'_' | b'.' | b'!' | b'~' | b'*' | b'\'' | b'(' | b')'
+        )
+      {
+        encoded.push(char::from(byte));
+      } else {
+        write!(encoded, "%{byte:02X}").ok();
+      }
+    }
+
+    encoded
+  }
+
+  /// Evaluate the expression `node` from `document`, returning `None` if its
+  /// value can't be determined statically.
+  #[must_use]
+  pub fn evaluate(&self, document: &Document, node: Node) -> Option<String> {
+    self.expression(document, node, &mut Vec::new())
+  }
+
+  /// Evaluate the value of the variable `name`, as resolved through the
+  /// project view.
+  #[must_use]
+  pub fn evaluate_variable(&self, name: &str) -> Option<String> {
+    self.variable(name, &mut Vec::new())
+  }
+
+  fn expression(
+    &self,
+    document: &Document,
+    node: Node,
+    stack: &mut Vec<String>,
+  ) -> Option<String> {
+    match node.kind() {
+      "expression" => {
+        let mut cursor = node.walk();
+
+        let children = node
+          .children(&mut cursor)
+          .filter(|child| child.kind() != "comment")
+          .collect::<Vec<_>>();
+
+        match children.as_slice() {
+          [slash, rest @ ..] if slash.kind() == "/" => {
+            Some(format!("/{}", self.operation(document, rest, stack)?))
+          }
+          children => self.operation(document, children, stack),
+        }
+      }
+      "function_call" => {
+        let name = document.get_node_text(&node.child_by_field_name("name")?);
+
+        let arguments = match node.child_by_field_name("arguments") {
+          Some(arguments) => {
+            let mut cursor = arguments.walk();
+
+            arguments
+              .named_children(&mut cursor)
+              .map(|argument| self.expression(document, argument, stack))
+              .collect::<Option<Vec<_>>>()?
+          }
+          None => Vec::new(),
+        };
+
+        self.builtin(document, &name, &arguments)
+      }
+      "identifier" => {
+        let name = document.get_node_text(&node);
+
+        let shadowed =
+          node.get_recipe(document).is_some_and(|recipe| {
+            recipe
+              .parameters
+              .iter()
+              .any(|parameter| parameter.name == name)
+          }) || node.get_function(document).is_some_and(|function| {
+            function
+              .parameters
+              .iter()
+              .any(|parameter| parameter.value == name)
+          });
+
+        if shadowed {
+          return None;
+        }
+
+        self.variable(&name, stack)
+      }
+      "if_expression" => {
+        if self.condition(document, node.named_child(0)?, stack)? {
+          return self.expression(
+            document,
+            node.child_by_field_name("body")?,
+            stack,
+          );
+        }
+
+        let mut cursor = node.walk();
+
+        let alternatives = node
+          .children_by_field_name("alternative", &mut cursor)
+          .collect::<Vec<_>>();
+
+        for alternative in alternatives {
+          let body = alternative.child_by_field_name("body")?;
+
+          if alternative.kind() == "else_clause" {
+            return self.expression(document, body, stack);
+          }
+
+          if self.condition(document, alternative.named_child(0)?, stack)? {
+            return self.expression(document, body, stack);
+          }
+        }
+
+        None
+      }
+      "regex_literal" => self.expression(document, node.named_child(0)?, stack),
+      "string" => match node.named_child(0) {
+        Some(format) if format.kind() == "format_string" => {
+          self.format_string(document, format, stack)
+        }
+        _ => document.get_node_text(&node).literal(),
+      },
+      "value" => {
+        let mut cursor = node.walk();
+
+        let inner = node
+          .named_children(&mut cursor)
+          .find(|child| child.kind() != "comment")?;
+
+        self.expression(document, inner, stack)
+      }
+      _ => None,
+    }
+  }
+
+  fn format_string(
+    &self,
+    document: &Document,
+    node: Node,
+    stack: &mut Vec<String>,
+  ) -> Option<String> {
+    let mut cursor = node.walk();
+
+    let children = node.children(&mut cursor).collect::<Vec<_>>();
+
+    let [open, inner @ .., close] = children.as_slice() else {
+      return None;
+    };
+
+    let raw = match open.kind() {
+      "f\"" => false,
+      "f'" => true,
+      _ => return None,
+    };
+
+    let source = document.content.to_string();
+
+    let mut value = String::new();
+
+    let mut offset = open.end_byte();
+
+    for child in inner.iter().chain(once(close)) {
+      value.push_str(source.get(offset..child.start_byte())?);
+
+      match child.kind() {
+        "escape_sequence" if document.get_node_text(child) == "{{{{" => {
+          value.push_str("{{");
+        }
+        "escape_sequence" if !raw => {
+          value.push_str(
+            &format!("\"{}\"", document.get_node_text(child)).literal()?,
+          );
+        }
+        "interpolation" => {
+          value.push_str(&self.expression(
+            document,
+            child.named_child(0)?,
+            stack,
+          )?);
+        }
+        _ => {}
+      }
+
+      offset = child.end_byte();
+    }
+
+    Some(value)
+  }
+
+  /// Resolve `home_directory()` to `home` rather than treating it as
+  /// unknown.
+  #[must_use]
+  pub fn home(self, home: Option<PathBuf>) -> Self {
+    Self { home, ..self }
+  }
+
+  #[must_use]
+  pub fn new(view: &'a ProjectView<'a>) -> Self {
+    Self { home: None, view }
+  }
+
+  fn operation(
+    &self,
+    document: &Document,
+    children: &[Node],
+    stack: &mut Vec<String>,
+  ) -> Option<String> {
+    match children {
+      [inner] => self.expression(document, *inner, stack),
+      [lhs, operator, rhs] => {
+        let lhs = self.expression(document, *lhs, stack)?;
+
+        match operator.kind() {
+          "+" => Some(lhs + &self.expression(document, *rhs, stack)?),
+          "/" => {
+            Some(format!("{lhs}/{}", self.expression(document, *rhs, stack)?))
+          }
+          "&&" if lhs.is_empty() => Some(lhs),
+          "&&" => self.expression(document, *rhs, stack),
+          "||" if lhs.is_empty() => self.expression(document, *rhs, stack),
+          "||" => Some(lhs),
+          _ => None,
+        }
+      }
+      _ => None,
+    }
+  }
+
+  fn path(document: &Document) -> Option<String> {
+    Some(document.uri.to_file_path().ok()?.to_str()?.to_string())
+  }
+
+  fn variable(&self, name: &str, stack: &mut Vec<String>) -> Option<String> {
+    if stack.iter().any(|visiting| visiting == name) {
+      return None;
+    }
+
+    let located = self.view.find_variable(name)?;
+
+    let document = self.view.find_document(&located.uri)?;
+
+    let root = document.tree.as_ref()?.root_node();
+
+    let assignment =
+      root.find_all("assignment").into_iter().find(|assignment| {
+        assignment.child_by_field_name("left").is_some_and(|left| {
+          left.get_range(document) == located.value.name.range
+        })
+      })?;
+
+    stack.push(name.to_string());
+
+    let value = self.expression(
+      document,
+      assignment.child_by_field_name("right")?,
+      stack,
+    );
+
+    stack.pop();
+
+    value
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, indoc::indoc, pretty_assertions::assert_eq};
+
+  #[track_caller]
+  fn case(content: &str, expected: Option<&str>) {
+    let document = Document::from(content);
+
+    let view = ProjectView::from(&document);
+
+    assert_eq!(
+      Evaluator::new(&view).evaluate_variable("foo").as_deref(),
+      expected
+    );
+  }
+
+  #[test]
+  fn backticks_are_unknown() {
+    case("foo := `cat VERSION`\n", None);
+  }
+
+  #[test]
+  fn case_conversion() {
+    case("foo := kebabcase('FooBar')\n", Some("foo-bar"));
+    case("foo := shoutysnakecase('foo bar')\n", Some("FOO_BAR"));
+    case("foo := uppercase('foo')\n", Some("FOO"));
+    case("foo := capitalize('fOO')\n", Some("Foo"));
+  }
+
+  #[test]
+  fn concatenation() {
+    case("foo := 'a' + \"b\\tc\"\n", Some("ab\tc"));
+  }
+
+  #[test]
+  fn conditionals() {
+    case(
+      "foo := if 'a' == 'b' { 'x' } else if 'c' != 'd' { 'y' } else { 'z' }\n",
+      Some("y"),
+    );
+    case(
+      "foo := if 'abc' =~ '^a.c$' { 'x' } else { 'y' }\n",
+      Some("x"),
+    );
+  }
+
+  #[test]
+  fn cyclic_variables_are_unknown() {
+    case("foo := bar\nbar := foo\n", None);
+  }
+
+  #[test]
+  fn environment_dependent_conditions_are_unknown() {
+    case("foo := if os() == 'linux' { 'x' } else { 'y' }\n", None);
+  }
+
+  #[test]
+  fn environment_dependent_functions_are_unknown() {
+    case("foo := env('HOME')\n", None);
+    case("foo := uuid()\n", None);
+  }
+
+  #[test]
+  fn format_strings() {
+    case("bar := 'b'\nfoo := f'a{{ bar }}c'\n", Some("abc"));
+    case("bar := 'b'\nfoo := f\"a\\t{{ bar }}\"\n", Some("a\tb"));
+    case("foo := f'a{{{{b'\n", Some("a{{b"));
+    case("foo := f\"a{{{{b\"\n", Some("a{{b"));
+    case("bar := 'b'\nfoo := f'{{{{{{ bar }}}}'\n", Some("{{b}}"));
+    case("foo := f'a{b}c'\n", Some("a{b}c"));
+    case("foo := f'a\\tb'\n", Some("a\\tb"));
+    case("bar := 'b'\nfoo := f'\\n{{ bar }}\\t'\n", Some("\\nb\\t"));
+  }
+
+  #[test]
+  fn home_directory() {
+    let document = Document::from("foo := home_directory() / 'x.just'\n");
+
+    let view = ProjectView::from(&document);
+
+    assert_eq!(Evaluator::new(&view).evaluate_variable("foo"), None);
+
+    assert_eq!(
+      Evaluator::new(&view)
+        .home(Some(PathBuf::from("/home/foo")))
+        .evaluate_variable("foo")
+        .as_deref(),
+      Some("/home/foo/x.just")
+    );
+  }
+
+  #[test]
+  fn justfile_paths() {
+    case("foo := justfile()\n", Some("/test.just"));
+    case("foo := source_directory() + 'bin'\n", Some("/bin"));
+  }
+
+  #[test]
+  fn path_functions() {
+    case("foo := file_stem('a/b.tar.gz')\n", Some("b.tar"));
+    case("foo := without_extension('a/b.txt')\n", Some("a/b"));
+    case("foo := join('a', 'b', 'c')\n", Some("a/b/c"));
+    case("foo := clean('a/./b/../c')\n", Some("a/c"));
+  }
+
+  #[test]
+  fn path_joins() {
+    case(
+      "dist := 'target'\nfoo := dist / 'bin' + '.exe'\n",
+      Some("target/bin.exe"),
+    );
+    case("foo := / 'usr' / 'bin'\n", Some("/usr/bin"));
+  }
+
+  #[test]
+  fn quote() {
+    case("foo := quote(\"it's\")\n", Some("'it'\\''s'"));
+  }
+
+  #[test]
+  fn recipe_parameters_shadow_variables() {
+    let document = Document::from(indoc! {
+      "
+      foo := 'a'
+
+      bar foo:
+        echo {{ foo }}
+      "
+    });
+
+    let view = ProjectView::from(&document);
+
+    let node = document
+      .node_at_position(lsp::Position::new(3, 10))
+      .unwrap();
+
+    assert_eq!(node.kind(), "identifier");
+
+    assert_eq!(Evaluator::new(&view).evaluate(&document, node), None);
+  }
+
+  #[test]
+  fn replace() {
+    case("foo := replace('aXbX', 'X', '-')\n", Some("a-b-"));
+    case(
+      "foo := replace_regex('a1b22', '[0-9]+', '#')\n",
+      Some("a#b#"),
+    );
+  }
+
+  #[test]
+  fn string_literals() {
+    case("foo := 'bar'\n", Some("bar"));
+    case("foo := \"bar\\n\"\n", Some("bar\n"));
+    case("foo := x'$HOME'\n", None);
+  }
+
+  #[test]
+  fn trim() {
+    case("foo := trim('  a  ')\n", Some("a"));
+    case(
+      "foo := trim_end_match('a.txt.txt', '.txt')\n",
+      Some("a.txt"),
+    );
+    case("foo := trim_start_matches('xxa', 'x')\n", Some("a"));
+  }
+
+  #[test]
+  fn undefined_variables_are_unknown() {
+    case("foo := bar\n", None);
+  }
+
+  #[test]
+  fn variable_references() {
+    case("bar := 'b'\nfoo := 'a' + bar\n", Some("ab"));
+  }
+
+  #[test]
+  fn word_functions() {
+    case("foo := append('.c', 'a b')\n", Some("a.c b.c"));
+    case("foo := prepend('-I', 'a b')\n", Some("-Ia -Ib"));
+  }
+}
@@ -0,0 +1,377 @@
+use super::*;
+
+/// The foldable regions of a justfile: recipes and their attribute stacks,
+/// script bodies, function bodies, multi-line expressions, and runs of
+/// comments, imports and settings.
+pub(crate) struct FoldingRanges<'a> {
+  document: &'a Document,
+}
+
+impl<'a> FoldingRanges<'a> {
+  /// Attribute stacks spanning several lines above a recipe.
+  fn attributes(root: Node) -> Vec<lsp::FoldingRange> {
+    root
+      .find_all("recipe")
+      .into_iter()
+      .filter_map(|recipe| {
+        let mut cursor = recipe.walk();
+
+        let attributes = recipe
+          .children(&mut cursor)
+          .filter(|child| child.kind() == "attribute")
+          .collect::<Vec<_>>();
+
+        Self::fold(
+          *attributes.first()?,
+          *attributes.last()?,
+          lsp::FoldingRangeKind::Region,
+        )
+      })
+      .collect()
+  }
+
+  /// Runs of top-level items of the given `kinds` on consecutive lines.
+  fn blocks(
+    root: Node,
+    kinds: &[&str],
+    kind: &lsp::FoldingRangeKind,
+  ) -> Vec<lsp::FoldingRange> {
+    let mut runs = Vec::<(Node, Node)>::new();
+
+    let mut cursor = root.walk();
+
+    let mut previous: Option<Node> = None;
+
+    for child in root.named_children(&mut cursor) {
+      let contiguous = previous.is_some_and(|previous| {
+        kinds.contains(&previous.kind())
+          && child.start_position().row == Self::last_row(previous) + 1
+      });
+
+      if kinds.contains(&child.kind()) {
+        match runs.last_mut() {
+          Some((_, end)) if contiguous => *end = child,
+          _ => runs.push((child, child)),
+        }
+      }
+
+      previous = Some(child);
+    }
+
+    runs
+      .into_iter()
+      .filter(|(start, end)| start.id() != end.id())
+      .filter_map(|(start, end)| Self::fold(start, end, kind.clone()))
+      .collect()
+  }
+
+  /// Consecutive comments that each occupy a whole line.
+  fn comments(&self, root: Node) -> Vec<lsp::FoldingRange> {
+    let mut runs = Vec::<(Node, Node)>::new();
+
+    for comment in root.find_all("comment") {
+      let line = self
+        .document
+        .content
+        .line(comment.start_position().row)
+        .to_string();
+
+      if !line[..comment.start_position().column].trim().is_empty() {
+        continue;
+      }
+
+      match runs.last_mut() {
+        Some((_, end))
+          if comment.start_position().row == Self::last_row(*end) + 1 =>
+        {
+          *end = comment;
+        }
+        _ => runs.push((comment, comment)),
+      }
+    }
+
+    runs
+      .into_iter()
+      .filter_map(|(start, end)| {
+        Self::fold(start, end, lsp::FoldingRangeKind::Comment)
+      })
+      .collect()
+  }
+
+  /// Conditionals and list literals that span several lines.
+  fn expressions(root: Node) -> Vec<lsp::FoldingRange> {
+    root
+      .find_all("if_expression,list_literal")
+      .into_iter()
+      .filter_map(|expression| {
+        Self::fold(expression, expression, lsp::FoldingRangeKind::Region)
+      })
+      .collect()
+  }
+
+  /// A fold from the first line of `start` to the last line of `end`, if
+  /// they differ.
+  fn fold(
+    start: Node,
+    end: Node,
+    kind: lsp::FoldingRangeKind,
+  ) -> Option<lsp::FoldingRange> {
+    let start_row = start.start_position().row;
+
+    let end_row = Self::last_row(end);
+
+    if end_row <= start_row {
+      return None;
+    }
+
+    Some(lsp::FoldingRange {
+      start_line: u32::try_from(start_row).ok()?,
+      end_line: u32::try_from(end_row).ok()?,
+      kind: Some(kind),
+      ..Default::default()
+    })
+  }
+
+  /// User-defined function bodies, folded from the function's name.
+  fn functions(root: Node) -> Vec<lsp::FoldingRange> {
+    root
+      .find_all("function_definition")
+      .into_iter()
+      .filter_map(|function| {
+        Self::fold(
+          function.child_by_field_name("name")?,
+          function,
+          lsp::FoldingRangeKind::Region,
+        )
+      })
+      .collect()
+  }
+
+  /// The last row `node` has text on, since nodes that end with a newline
+  /// end at the start of the following row.
+  fn last_row(node: Node) -> usize {
+    let end = node.end_position();
+
+    if end.column == 0 && end.row > node.start_position().row {
+      end.row - 1
+    } else {
+      end.row
+    }
+  }
+
+  pub(crate) fn new(document: &'a Document) -> Self {
+    Self { document }
+  }
+
+  /// Every folding range in the document, ordered by start line.
+  pub(crate) fn ranges(&self) -> Vec<lsp::FoldingRange> {
+    let Some(tree) = self.document.tree.as_ref() else {
+      return Vec::new();
+    };
+
+    let root = tree.root_node();
+
+    let mut ranges = self.recipes(root);
+
+    ranges.extend(Self::attributes(root));
+    ranges.extend(self.comments(root));
+    ranges.extend(Self::blocks(
+      root,
+      &["import", "module"],
+      &lsp::FoldingRangeKind::Imports,
+    ));
+    ranges.extend(Self::blocks(
+      root,
+      &["setting"],
+      &lsp::FoldingRangeKind::Region,
+    ));
+    ranges.extend(Self::expressions(root));
+    ranges.extend(Self::functions(root));
+    ranges.extend(Self::scripts(root));
+
+    ranges.sort_by_key(|range| (range.start_line, range.end_line));
+
+    ranges
+  }
+
+  /// Recipes, folded from their header to the line before the next item.
+  fn recipes(&self, root: Node) -> Vec<lsp::FoldingRange> {
+    root
+      .find_all("recipe")
+      .into_iter()
+      .filter_map(|recipe| {
+        let start_line = recipe
+          .find("recipe_header")?
+          .get_range(self.document)
+          .start
+          .line;
+
+        let end_line = recipe.get_range(self.document).end.line;
+
+        Some(lsp::FoldingRange {
+          start_line,
+          end_line: end_line.saturating_sub(1).max(start_line),
+          kind: Some(lsp::FoldingRangeKind::Region),
+          ..Default::default()
+        })
+      })
+      .collect()
+  }
+
+  /// Shebang recipe bodies, folded from the shebang to the last line.
+  fn scripts(root: Node) -> Vec<lsp::FoldingRange> {
+    root
+      .find_all("recipe_body")
+      .into_iter()
+      .filter_map(|body| {
+        let shebang = body.child_by_field_name("shebang")?;
+
+        let mut cursor = body.walk();
+
+        let last = body
+          .children(&mut cursor)
+          .filter(|child| child.kind() == "recipe_line")
+          .last()?;
+
+        Self::fold(shebang, last, lsp::FoldingRangeKind::Region)
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, indoc::indoc, pretty_assertions::assert_eq};
+
+  fn folds(content: &str) -> Vec<(u32, u32, &'static str)> {
+    FoldingRanges::new(&Document::from(content))
+      .ranges()
+      .into_iter()
+      .map(|range| {
+        (
+          range.start_line,
+          range.end_line,
+          match range.kind {
+            Some(lsp::FoldingRangeKind::Comment) => "comment",
+            Some(lsp::FoldingRangeKind::Imports) => "imports",
+            _ => "region",
+          },
+        )
+      })
+      .collect()
+  }
+
+  #[test]
+  fn attribute_stack() {
+    assert_eq!(
+      folds(indoc! {
+        "
+        [private]
+        [no-cd]
+        [group('dev')]
+        foo:
+          echo foo
+        "
+      }),
+      [(0, 2, "region"), (3, 4, "region")]
+    );
+  }
+
+  #[test]
+  fn comment_block() {
+    assert_eq!(
+      folds(indoc! {
+        "
+        # one
+        # two
+        # three
+        x := 'a' # trailing
+        # lone
+        "
+      }),
+      [(0, 2, "comment")]
+    );
+  }
+
+  #[test]
+  fn function_body() {
+    assert_eq!(
+      folds(indoc! {
+        "
+        greet(name) := \\
+          'hello ' + \\
+          name
+        "
+      }),
+      [(0, 2, "region")]
+    );
+  }
+
+  #[test]
+  fn imports_and_modules() {
+    assert_eq!(
+      folds(indoc! {
+        "
+        import 'a.just'
+        import? 'b.just'
+        mod c
+
+        mod d
+        "
+      }),
+      [(0, 2, "imports")]
+    );
+  }
+
+  #[test]
+  fn multiline_expressions() {
+    assert_eq!(
+      folds(indoc! {
+        "
+        x := if os() == 'linux' {
+          'a'
+        } else {
+          'b'
+        }
+
+        set shell := [
+          'bash',
+          '-c',
+        ]
+        "
+      }),
+      [(0, 4, "region"), (6, 9, "region")]
+    );
+  }
+
+  #[test]
+  fn settings_block() {
+    assert_eq!(
+      folds(indoc! {
+        "
+        set quiet
+        set dotenv-load
+        set export
+
+        set positional-arguments
+        "
+      }),
+      [(0, 2, "region")]
+    );
+  }
+
+  #[test]
+  fn shebang_body() {
+    assert_eq!(
+      folds(indoc! {
+        "
+        foo:
+          #!/usr/bin/env bash
+          set -euo pipefail
+          echo foo
+        "
+      }),
+      [(0, 3, "region"), (1, 3, "region")]
+    );
+  }
+}
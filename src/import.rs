@@ -16,8 +16,16 @@ impl Import {
 
   #[must_use]
   pub fn resolve(&self, base_uri: &lsp::Url) -> Option<PathBuf> {
-    let raw = self.path.value.trim_matches(|c| c == '\'' || c == '"');
+    Self::resolve_path(
+      self.path.value.trim_matches(|c| c == '\'' || c == '"'),
+      base_uri,
+    )
+  }
 
+  /// Resolve an import path, relative to the document at `base_uri` unless
+  /// it's absolute or starts with `~/`.
+  #[must_use]
+  pub fn resolve_path(raw: &str, base_uri: &lsp::Url) -> Option<PathBuf> {
     if raw.is_empty() {
       return None;
     }
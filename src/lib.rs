@@ -7,11 +7,11 @@ use {
   serde::{Deserialize, Serialize},
   std::{
     cmp::Reverse,
-    collections::{HashMap, HashSet, hash_map::Entry},
+    collections::{HashMap, HashSet, VecDeque, hash_map::Entry},
     fmt::{self, Debug, Display, Formatter},
     fs,
     iter::{once, successors},
-    ops::{ControlFlow, RangeInclusive},
+    ops::{ControlFlow, Range, RangeInclusive},
     path::{Path, PathBuf},
     process,
     sync::OnceLock,
@@ -24,13 +24,16 @@ use {
 pub use {
   alias::Alias,
   analyzer::Analyzer,
+  argument_format::ArgumentFormat,
   attribute::Attribute,
   attribute_kind::AttributeKind,
   attribute_target::AttributeTarget,
   builtin::Builtin,
   builtins::BUILTINS,
-  config::{Config, FormattingConfig, RuleConfig, RuleLevel},
+  config::{Config, FormattingConfig, HoverConfig, RuleConfig, RuleLevel},
   count::Count,
+  custom_query::CustomQuery,
+  custom_rule::CustomRule,
   dependency::Dependency,
   dependency_argument::DependencyArgument,
   dependency_phase::DependencyPhase,
@@ -38,7 +41,10 @@ pub use {
   diagnostic::Diagnostic,
   document::Document,
   document_store::DocumentStore,
+  embedded_language::EmbeddedLanguage,
+  embedded_script::EmbeddedScript,
   error::Error,
+  evaluator::Evaluator,
   function::Function,
   function_call::FunctionCall,
   function_kind::FunctionKind,
@@ -50,6 +56,7 @@ pub use {
   located::Located,
   module::Module,
   node_ext::NodeExt,
+  outline::Outline,
   parameter::{Parameter, ParameterJson, ParameterKind, VariadicType},
   point_ext::PointExt,
   position_ext::PositionExt,
@@ -63,6 +70,7 @@ pub use {
   quickfixer::Quickfixer,
   range_ext::RangeExt,
   recipe::Recipe,
+  recipe_json::RecipeJson,
   rope_ext::{Edit, Position as RopePosition, RopeExt},
   rule::Rule,
   rule_context::RuleContext,
@@ -70,14 +78,19 @@ pub use {
   setting::Setting,
   setting_kind::SettingKind,
   str_ext::StrExt,
+  string_literal::StringLiteral,
+  suggestions::Suggestions,
   text_node::TextNode,
+  type_checker::TypeChecker,
   unexport::Unexport,
+  value_type::ValueType,
   variable::Variable,
   workspace::Workspace,
 };
 
 mod alias;
 mod analyzer;
+mod argument_format;
 mod attribute;
 mod attribute_kind;
 mod attribute_target;
@@ -85,6 +98,8 @@ mod builtin;
 mod builtins;
 mod config;
 mod count;
+mod custom_query;
+mod custom_rule;
 mod dependency;
 mod dependency_argument;
 mod dependency_phase;
@@ -93,7 +108,10 @@ mod diagnostic;
 mod document;
 mod document_entry;
 mod document_store;
+mod embedded_language;
+mod embedded_script;
 mod error;
+mod evaluator;
 mod function;
 mod function_call;
 mod function_kind;
@@ -105,6 +123,7 @@ mod import_scope_document;
 mod located;
 mod module;
 mod node_ext;
+mod outline;
 mod parameter;
 mod point_ext;
 mod position_ext;
@@ -119,6 +138,7 @@ mod quickfix;
 mod quickfixer;
 mod range_ext;
 mod recipe;
+mod recipe_json;
 mod rope_ext;
 mod rule;
 mod rule_context;
@@ -126,14 +146,21 @@ mod scope;
 mod setting;
 mod setting_kind;
 mod str_ext;
+mod string_literal;
+mod suggestions;
 mod text_node;
+mod type_checker;
 mod unexport;
+mod value_type;
 mod variable;
 mod workspace;
 
 type Result<T = ()> = std::result::Result<T, Error>;
 
-// SAFETY: tree_sitter_just returns a static language definition.
+// SAFETY: tree_sitter_bash, tree_sitter_just and tree_sitter_python return
+// static language definitions.
 unsafe extern "C" {
+  pub fn tree_sitter_bash() -> Language;
   pub fn tree_sitter_just() -> Language;
+  pub fn tree_sitter_python() -> Language;
 }
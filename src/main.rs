@@ -3,40 +3,57 @@ use {
   arguments::Arguments,
   ariadne::{Color, Label, Report, ReportKind, sources},
   clap::{Parser, builder::styling},
+  code_lenses::CodeLenses,
   command::Command,
+  document_links::DocumentLinks,
+  folding_ranges::FoldingRanges,
   just_lsp::*,
+  lexiclean::Lexiclean,
+  recipe_catalog::RecipeCatalog,
+  recipe_runs::{
+    RecipeOutput, RecipeOutputAppended, RecipeOutputParams, RecipeRuns,
+  },
   resolver::Resolver,
   ropey::Rope,
-  serde::Serialize,
+  serde::{Deserialize, Serialize},
   serde_json::Value,
   server::Server,
   std::{
     backtrace::BacktraceStatus,
-    collections::{BTreeSet, HashMap},
+    collections::{BTreeSet, HashMap, VecDeque},
     env,
     fmt::{self, Debug, Display, Formatter},
     fs,
     io::{self, stderr},
-    path::PathBuf,
+    iter::once,
+    ops::Range,
+    path::{Path, PathBuf},
     process,
     sync::{Arc, LazyLock, atomic::AtomicBool},
-    time::Instant,
+    time::{Duration, Instant},
   },
   subcommand::Subcommand,
   symbol::Symbol,
   tokenizer::Tokenizer,
   tokio::{io::AsyncBufReadExt, sync::RwLock},
   tokio_stream::{StreamExt, wrappers::LinesStream},
-  tower_lsp::{Client, LanguageServer, LspService, jsonrpc, lsp_types as lsp},
+  tower_lsp::{
+    Client, ClientSocket, LanguageServer, LspService, jsonrpc, lsp_types as lsp,
+  },
   tracing::{Level, info, warn},
-  tree_sitter::Node,
+  tree_sitter::{Language, Node},
   tree_sitter_highlight::{
     Highlight, HighlightConfiguration, HighlightEvent, Highlighter,
   },
 };
 
 mod arguments;
+mod code_lenses;
 mod command;
+mod document_links;
+mod folding_ranges;
+mod recipe_catalog;
+mod recipe_runs;
 mod resolver;
 mod server;
 mod subcommand;
@@ -16,41 +16,87 @@ impl Module {
     (".justfile", false),
   ];
 
+  fn base_dir(base_uri: &lsp::Url) -> Option<PathBuf> {
+    Some(base_uri.to_file_path().ok()?.parent()?.to_path_buf())
+  }
+
+  /// Every existing source file that could back this module, in order of
+  /// preference.
+  ///
+  /// `just` refuses to load a module with more than one candidate, so
+  /// callers can use this to detect ambiguous layouts.
+  #[must_use]
+  pub fn candidates(&self, base_uri: &lsp::Url) -> Vec<PathBuf> {
+    let Some(base_dir) = Self::base_dir(base_uri) else {
+      return Vec::new();
+    };
+
+    if self.path.is_some() {
+      return self
+        .resolve(base_uri)
+        .filter(|path| path.is_file())
+        .into_iter()
+        .collect();
+    }
+
+    let name = &self.name.value;
+
+    let name_just = format!("{name}.just");
+
+    Self::find_files(&base_dir, &[(&name_just, true)])
+      .into_iter()
+      .chain(Self::find_files(
+        &base_dir.join(name),
+        &Self::DIRECTORY_CANDIDATES,
+      ))
+      .collect()
+  }
+
   fn find_file(
     directory: &Path,
     candidates: &[(&str, bool)],
   ) -> Option<PathBuf> {
-    let entries = fs::read_dir(directory)
-      .ok()?
+    Self::find_files(directory, candidates).into_iter().next()
+  }
+
+  fn find_files(directory: &Path, candidates: &[(&str, bool)]) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(directory) else {
+      return Vec::new();
+    };
+
+    let entries = entries
       .filter_map(std::result::Result::ok)
       .collect::<Vec<_>>();
 
-    candidates.iter().find_map(|(candidate, case_sensitive)| {
-      entries.iter().find_map(|entry| {
-        let path = entry.path();
+    candidates
+      .iter()
+      .flat_map(|(candidate, case_sensitive)| {
+        entries.iter().filter_map(|entry| {
+          let path = entry.path();
 
-        let name = entry.file_name();
+          let name = entry.file_name();
 
-        let name = name.to_str()?;
+          let name = name.to_str()?;
 
-        let matches = if *case_sensitive {
-          name == *candidate
-        } else {
-          name.eq_ignore_ascii_case(candidate)
-        };
+          let matches = if *case_sensitive {
+            name == *candidate
+          } else {
+            name.eq_ignore_ascii_case(candidate)
+          };
 
-        if path.is_file() && matches {
-          Some(path)
-        } else {
-          None
-        }
+          if path.is_file() && matches {
+            Some(path)
+          } else {
+            None
+          }
+        })
       })
-    })
+      .collect()
   }
 
   #[must_use]
   pub fn resolve(&self, base_uri: &lsp::Url) -> Option<PathBuf> {
-    let base_dir = base_uri.to_file_path().ok()?.parent()?.to_path_buf();
+    let base_dir = Self::base_dir(base_uri)?;
 
     if let Some(path_node) = &self.path {
       let raw = path_node.value.trim_matches(|c| c == '\'' || c == '"');
@@ -103,6 +149,40 @@ mod tests {
     }
   }
 
+  #[test]
+  fn candidates_include_every_existing_file() {
+    let directory = Builder::new().prefix("just-lsp").tempdir().unwrap();
+
+    fs::write(directory.path().join("foo.just"), "").unwrap();
+    fs::create_dir(directory.path().join("foo")).unwrap();
+    fs::write(directory.path().join("foo/mod.just"), "").unwrap();
+
+    let base =
+      lsp::Url::from_file_path(directory.path().join("justfile")).unwrap();
+
+    assert_eq!(
+      module("foo", None).candidates(&base),
+      [
+        directory.path().join("foo.just"),
+        directory.path().join("foo/mod.just"),
+      ],
+    );
+  }
+
+  #[test]
+  fn candidates_skip_missing_explicit_path() {
+    let directory = Builder::new().prefix("just-lsp").tempdir().unwrap();
+
+    let base =
+      lsp::Url::from_file_path(directory.path().join("justfile")).unwrap();
+
+    assert!(
+      module("foo", Some("'bar.just'"))
+        .candidates(&base)
+        .is_empty()
+    );
+  }
+
   #[test]
   fn empty_explicit_path_returns_none() {
     let directory = Builder::new().prefix("just-lsp").tempdir().unwrap();
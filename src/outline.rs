@@ -0,0 +1,433 @@
+use super::*;
+
+/// The hierarchical document symbols of a justfile: modules and groups as
+/// containers, recipes with their parameters, and every other top-level
+/// item.
+pub struct Outline<'a> {
+  document: &'a Document,
+}
+
+impl<'a> Outline<'a> {
+  fn alias(alias: Alias) -> lsp::DocumentSymbol {
+    Self::symbol(
+      alias.name.value,
+      Some(format!("alias for {}", alias.value.value)),
+      lsp::SymbolKind::FUNCTION,
+      alias.range,
+      alias.name.range,
+    )
+  }
+
+  fn function(function: Function) -> lsp::DocumentSymbol {
+    let parameters = function
+      .parameters
+      .iter()
+      .map(|parameter| parameter.value.as_str())
+      .collect::<Vec<_>>()
+      .join(", ");
+
+    Self::symbol(
+      function.name.value,
+      Some(format!("({parameters})")),
+      lsp::SymbolKind::FUNCTION,
+      function.range,
+      function.name.range,
+    )
+  }
+
+  /// A container for the recipes in `[group]` named `name`, spanning all of
+  /// them.
+  fn group(
+    name: String,
+    selection_range: lsp::Range,
+    recipes: Vec<lsp::DocumentSymbol>,
+  ) -> lsp::DocumentSymbol {
+    let range = lsp::Range {
+      start: recipes
+        .iter()
+        .map(|recipe| recipe.range.start)
+        .min()
+        .unwrap_or(selection_range.start),
+      end: recipes
+        .iter()
+        .map(|recipe| recipe.range.end)
+        .max()
+        .unwrap_or(selection_range.end),
+    };
+
+    lsp::DocumentSymbol {
+      children: Some(recipes),
+      ..Self::symbol(
+        name,
+        Some("group".into()),
+        lsp::SymbolKind::PACKAGE,
+        range,
+        selection_range,
+      )
+    }
+  }
+
+  fn import(import: Import) -> lsp::DocumentSymbol {
+    let name = match import.path.value.literal() {
+      Some(path) => path,
+      None => import.path.value,
+    };
+
+    Self::symbol(
+      name,
+      Some(
+        if import.optional {
+          "optional import"
+        } else {
+          "import"
+        }
+        .into(),
+      ),
+      lsp::SymbolKind::FILE,
+      import.range,
+      import.path.range,
+    )
+  }
+
+  fn module(module: Module) -> lsp::DocumentSymbol {
+    let detail = match &module.path {
+      Some(path) => format!("module {}", path.value),
+      None => "module".into(),
+    };
+
+    Self::symbol(
+      module.name.value,
+      Some(if module.optional {
+        format!("optional {detail}")
+      } else {
+        detail
+      }),
+      lsp::SymbolKind::NAMESPACE,
+      module.range,
+      module.name.range,
+    )
+  }
+
+  #[must_use]
+  pub fn new(document: &'a Document) -> Self {
+    Self { document }
+  }
+
+  fn parameter(parameter: &Parameter) -> lsp::DocumentSymbol {
+    Self::symbol(
+      parameter.name.clone(),
+      parameter
+        .default_value
+        .as_ref()
+        .map(|value| format!("= {value}")),
+      lsp::SymbolKind::VARIABLE,
+      parameter.range,
+      parameter.range,
+    )
+  }
+
+  fn recipe(recipe: &Recipe) -> lsp::DocumentSymbol {
+    let signature = recipe
+      .parameters
+      .iter()
+      .map(|parameter| parameter.content.as_str())
+      .collect::<Vec<_>>()
+      .join(" ");
+
+    let private = (recipe.name.value.starts_with('_')
+      || recipe.has_attribute("private"))
+    .then_some("[private]");
+
+    let doc = recipe
+      .find_attribute("doc")
+      .and_then(|attribute| attribute.arguments.first())
+      .and_then(|argument| argument.value.literal());
+
+    let detail = [Some(signature.as_str()), private, doc.as_deref()]
+      .into_iter()
+      .flatten()
+      .filter(|part| !part.is_empty())
+      .collect::<Vec<_>>()
+      .join(" ");
+
+    let parameters = recipe
+      .parameters
+      .iter()
+      .map(Self::parameter)
+      .collect::<Vec<_>>();
+
+    lsp::DocumentSymbol {
+      children: (!parameters.is_empty()).then_some(parameters),
+      ..Self::symbol(
+        recipe.name.value.clone(),
+        (!detail.is_empty()).then_some(detail),
+        lsp::SymbolKind::FUNCTION,
+        recipe.range,
+        recipe.name.range,
+      )
+    }
+  }
+
+  /// Recipes, nested under a container for each `[group]` they belong to.
+  fn recipes(&self) -> Vec<lsp::DocumentSymbol> {
+    let mut symbols = Vec::new();
+
+    let mut groups =
+      Vec::<(String, lsp::Range, Vec<lsp::DocumentSymbol>)>::new();
+
+    for recipe in self.document.recipes() {
+      let memberships = recipe
+        .attributes
+        .iter()
+        .filter(|attribute| attribute.name.value == "group")
+        .filter_map(|attribute| {
+          let argument = attribute.arguments.first()?;
+          Some((argument.value.literal()?, argument.range))
+        })
+        .collect::<Vec<_>>();
+
+      if memberships.is_empty() {
+        symbols.push(Self::recipe(&recipe));
+        continue;
+      }
+
+      for (name, range) in memberships {
+        let symbol = Self::recipe(&recipe);
+
+        match groups.iter_mut().find(|(group, _, _)| *group == name) {
+          Some((_, _, recipes)) => recipes.push(symbol),
+          None => groups.push((name, range, vec![symbol])),
+        }
+      }
+    }
+
+    symbols.extend(
+      groups
+        .into_iter()
+        .map(|(name, range, recipes)| Self::group(name, range, recipes)),
+    );
+
+    symbols
+  }
+
+  fn setting(setting: Setting) -> lsp::DocumentSymbol {
+    let deprecated = BUILTINS.iter().any(|builtin| {
+      matches!(
+        builtin,
+        Builtin::Setting {
+          name,
+          deprecated: Some(_),
+          ..
+        } if *name == setting.name.value
+      )
+    });
+
+    lsp::DocumentSymbol {
+      tags: deprecated.then(|| vec![lsp::SymbolTag::DEPRECATED]),
+      ..Self::symbol(
+        setting.name.value,
+        Some(setting.kind.to_string()),
+        lsp::SymbolKind::PROPERTY,
+        setting.range,
+        setting.range,
+      )
+    }
+  }
+
+  fn sort(symbols: &mut [lsp::DocumentSymbol]) {
+    symbols.sort_by_key(|symbol| symbol.range.start);
+
+    for symbol in symbols {
+      if let Some(children) = symbol.children.as_mut() {
+        Self::sort(children);
+      }
+    }
+  }
+
+  fn symbol(
+    name: String,
+    detail: Option<String>,
+    kind: lsp::SymbolKind,
+    range: lsp::Range,
+    selection_range: lsp::Range,
+  ) -> lsp::DocumentSymbol {
+    #[allow(deprecated)]
+    lsp::DocumentSymbol {
+      name,
+      detail,
+      kind,
+      tags: None,
+      deprecated: None,
+      range,
+      selection_range,
+      children: None,
+    }
+  }
+
+  /// Every symbol in the document, sorted by position at each level.
+  #[must_use]
+  pub fn symbols(&self) -> Vec<lsp::DocumentSymbol> {
+    let document = self.document;
+
+    let mut symbols = self.recipes();
+
+    symbols.extend(document.aliases().into_iter().map(Self::alias));
+    symbols.extend(document.variables().into_iter().map(Self::variable));
+    symbols.extend(document.functions().into_iter().map(Self::function));
+    symbols.extend(document.settings().into_iter().map(Self::setting));
+    symbols.extend(document.unexports().into_iter().map(Self::unexport));
+    symbols.extend(document.imports().into_iter().map(Self::import));
+    symbols.extend(document.modules().into_iter().map(Self::module));
+
+    Self::sort(&mut symbols);
+
+    symbols
+  }
+
+  fn unexport(unexport: Unexport) -> lsp::DocumentSymbol {
+    Self::symbol(
+      unexport.name.value,
+      Some("unexport".into()),
+      lsp::SymbolKind::VARIABLE,
+      unexport.range,
+      unexport.name.range,
+    )
+  }
+
+  fn variable(variable: Variable) -> lsp::DocumentSymbol {
+    Self::symbol(
+      variable.name.value,
+      None,
+      lsp::SymbolKind::VARIABLE,
+      variable.range,
+      variable.name.range,
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, indoc::indoc, pretty_assertions::assert_eq};
+
+  fn outline(content: &str) -> Vec<String> {
+    fn render(
+      symbols: &[lsp::DocumentSymbol],
+      depth: usize,
+      lines: &mut Vec<String>,
+    ) {
+      for symbol in symbols {
+        let detail = symbol
+          .detail
+          .as_ref()
+          .map(|detail| format!(" ({detail})"))
+          .unwrap_or_default();
+
+        let deprecated = if symbol.tags.is_some() {
+          " deprecated"
+        } else {
+          ""
+        };
+
+        lines.push(format!(
+          "{}{:?} {}{detail}{deprecated}",
+          "  ".repeat(depth),
+          symbol.kind,
+          symbol.name
+        ));
+
+        render(
+          symbol.children.as_deref().unwrap_or_default(),
+          depth + 1,
+          lines,
+        );
+      }
+    }
+
+    let document = Document::from(content);
+
+    let mut lines = Vec::new();
+
+    render(&Outline::new(&document).symbols(), 0, &mut lines);
+
+    lines
+  }
+
+  #[test]
+  fn deprecated_setting() {
+    assert_eq!(
+      outline("set windows-powershell := true\n"),
+      ["Property windows-powershell (boolean) deprecated"]
+    );
+  }
+
+  #[test]
+  fn groups() {
+    assert_eq!(
+      outline(indoc! {
+        "
+        [group('dev')]
+        build:
+
+        test:
+
+        [group('dev')]
+        [group('ci')]
+        lint:
+        "
+      }),
+      [
+        "Package dev (group)",
+        "  Function build",
+        "  Function lint",
+        "Function test",
+        "Package ci (group)",
+        "  Function lint",
+      ]
+    );
+  }
+
+  #[test]
+  fn imports_modules_and_unexports() {
+    assert_eq!(
+      outline(indoc! {
+        "
+        import 'foo.just'
+        import? 'bar.just'
+        mod baz
+        mod? qux 'qux/mod.just'
+        unexport FOO
+        "
+      }),
+      [
+        "File foo.just (import)",
+        "File bar.just (optional import)",
+        "Namespace baz (module)",
+        "Namespace qux (optional module 'qux/mod.just')",
+        "Variable FOO (unexport)",
+      ]
+    );
+  }
+
+  #[test]
+  fn recipe_signature_and_parameters() {
+    assert_eq!(
+      outline(indoc! {
+        "
+        [private]
+        [doc('Build the project')]
+        build target mode='debug' *flags:
+          cargo build
+
+        _helper:
+        "
+      }),
+      [
+        "Function build (target mode='debug' *flags [private] Build the project)",
+        "  Variable target",
+        "  Variable mode (= 'debug')",
+        "  Variable flags",
+        "Function _helper ([private])",
+      ]
+    );
+  }
+}
@@ -6,7 +6,8 @@ pub enum ParameterKind {
   Variadic(VariadicType),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum VariadicType {
   OneOrMore,
   ZeroOrMore,
@@ -16,6 +17,8 @@ pub enum VariadicType {
 pub struct ParameterJson {
   pub default_value: Option<String>,
   pub name: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub variadic: Option<VariadicType>,
 }
 
 impl From<Parameter> for ParameterJson {
@@ -23,6 +26,10 @@ impl From<Parameter> for ParameterJson {
     ParameterJson {
       name: parameter.name,
       default_value: parameter.default_value,
+      variadic: match parameter.kind {
+        ParameterKind::Normal => None,
+        ParameterKind::Variadic(variadic) => Some(variadic),
+      },
     }
   }
 }
@@ -3,7 +3,9 @@ use super::*;
 #[derive(Clone, Debug, PartialEq)]
 pub enum ProjectDependencyTarget {
   Ambiguous(Vec<lsp::Url>),
-  Cycle,
+  /// An import of a document that is still being loaded further up the
+  /// chain of imports.
+  Cycle(lsp::Url),
   Dynamic,
   Missing,
   Resolved(lsp::Url),
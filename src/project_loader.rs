@@ -25,6 +25,46 @@ impl<'a> ProjectLoader<'a> {
     Ok(())
   }
 
+  /// Statically evaluate the path of an import that isn't a plain string
+  /// literal, using the variables of the documents loaded so far.
+  fn evaluate_import_path(
+    &self,
+    source: &lsp::Url,
+    import: &Import,
+  ) -> Option<String> {
+    let document = self.documents.get(source)?;
+
+    let node = document.node_at_position(import.path.range.start)?;
+
+    let string = if node.kind() == "string" {
+      node
+    } else {
+      node.get_parent("string")?
+    };
+
+    let mut loaded = self
+      .active
+      .iter()
+      .chain(&self.expanded)
+      .filter(|uri| **uri != self.project.root && *uri != source)
+      .collect::<Vec<_>>();
+
+    loaded.sort();
+    loaded.dedup();
+
+    let view = ProjectView::from_documents(
+      document,
+      once(&self.project.root)
+        .chain((*source != self.project.root).then_some(source))
+        .chain(loaded)
+        .filter_map(|uri| self.documents.get(uri)),
+    );
+
+    Evaluator::new(&view)
+      .home(dirs::home_dir())
+      .evaluate(document, string)
+  }
+
   /// # Errors
   ///
   /// Returns an [`Error`] if the root document cannot be loaded.
@@ -53,11 +93,17 @@ impl<'a> ProjectLoader<'a> {
     source: &lsp::Url,
     import: &Import,
   ) -> Result<ProjectDependencyTarget> {
-    if import.is_dynamic() {
-      return Ok(ProjectDependencyTarget::Dynamic);
-    }
+    let path = if import.is_dynamic() {
+      let Some(raw) = self.evaluate_import_path(source, import) else {
+        return Ok(ProjectDependencyTarget::Dynamic);
+      };
+
+      Import::resolve_path(&raw, source)
+    } else {
+      import.resolve(source)
+    };
 
-    let Some(path) = import.resolve(source) else {
+    let Some(path) = path else {
       return Ok(ProjectDependencyTarget::Missing);
     };
 
@@ -70,7 +116,7 @@ impl<'a> ProjectLoader<'a> {
     self.project.add_dependent(&uri, source);
 
     if self.active.contains(&uri) {
-      return Ok(ProjectDependencyTarget::Cycle);
+      return Ok(ProjectDependencyTarget::Cycle(uri));
     }
 
     if self.documents.load(&uri).is_err() {
@@ -176,6 +222,7 @@ mod tests {
         imported_documents: project
           .imported_documents(&test.documents)
           .collect(),
+        project: Some(&project),
       }
       .analyze()
       .is_empty()
@@ -368,7 +415,7 @@ mod tests {
         .collect::<Vec<_>>(),
       [
         ProjectDependencyTarget::Resolved(baz.clone()),
-        ProjectDependencyTarget::Cycle,
+        ProjectDependencyTarget::Cycle(test.root.clone()),
       ]
     );
 
@@ -404,4 +451,49 @@ mod tests {
     assert_eq!(project.dependents[&bar], HashSet::from([test.root.clone()]));
     assert_eq!(project.dependents[&test.root], HashSet::from([bar]));
   }
+
+  #[test]
+  fn resolves_statically_evaluable_imports() {
+    let mut test = Test::new(indoc! {
+      "
+      shared := 'shared'
+
+      import f'{{ justfile_directory() / \"ci.just\" }}'
+      import f'{{ shared + \"/x.just\" }}'
+      import f'{{ env(\"DIR\") }}/y.just'
+      "
+    })
+    .file(
+      "ci.just",
+      "import f'{{ source_directory() }}/shared/z.just'",
+    )
+    .file("shared/x.just", "")
+    .file("shared/z.just", "");
+
+    let ci = test.uri("ci.just");
+    let x = test.uri("shared/x.just");
+    let z = test.uri("shared/z.just");
+
+    let project = test.load();
+
+    assert_eq!(
+      project.dependencies[&test.root]
+        .iter()
+        .map(|dependency| dependency.target.clone())
+        .collect::<Vec<_>>(),
+      [
+        ProjectDependencyTarget::Resolved(ci.clone()),
+        ProjectDependencyTarget::Resolved(x),
+        ProjectDependencyTarget::Dynamic,
+      ]
+    );
+
+    assert_eq!(
+      project.dependencies[&ci]
+        .iter()
+        .map(|dependency| dependency.target.clone())
+        .collect::<Vec<_>>(),
+      [ProjectDependencyTarget::Resolved(z)]
+    );
+  }
 }
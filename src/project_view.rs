@@ -44,6 +44,16 @@ impl<'a> ProjectView<'a> {
       })
   }
 
+  /// The document in the view with the given `uri`.
+  #[must_use]
+  pub fn find_document(&self, uri: &lsp::Url) -> Option<&'a Document> {
+    self
+      .documents
+      .iter()
+      .find(|document| document.document.uri == *uri)
+      .map(|document| document.document)
+  }
+
   #[must_use]
   pub fn find_function(&self, name: &str) -> Option<Located<Function>> {
     self.find(
@@ -64,6 +74,16 @@ impl<'a> ProjectView<'a> {
     )
   }
 
+  #[must_use]
+  pub fn find_setting(&self, name: &str) -> Option<Located<Setting>> {
+    self.find(
+      name,
+      Document::settings,
+      |setting| &setting.name.value,
+      |setting| setting.range.start,
+    )
+  }
+
   #[must_use]
   pub fn find_variable(&self, name: &str) -> Option<Located<Variable>> {
     self.find(
@@ -74,6 +94,28 @@ impl<'a> ProjectView<'a> {
     )
   }
 
+  /// A view over `documents` in precedence order, for when the import
+  /// scope isn't known yet, such as while a project is being loaded. The
+  /// first document is treated as the root.
+  #[must_use]
+  pub fn from_documents(
+    document: &'a Document,
+    documents: impl IntoIterator<Item = &'a Document>,
+  ) -> Self {
+    Self {
+      document,
+      documents: documents
+        .into_iter()
+        .enumerate()
+        .map(|(index, document)| ProjectViewDocument {
+          document,
+          load_depth: index,
+          traversal_order: index,
+        })
+        .collect(),
+    }
+  }
+
   #[must_use]
   pub fn new(
     document: &'a Document,
@@ -103,6 +145,17 @@ impl<'a> ProjectView<'a> {
       documents,
     }
   }
+
+  /// The root justfile of the project, falling back to the viewed document
+  /// if the root isn't loaded.
+  #[must_use]
+  pub fn root(&self) -> &'a Document {
+    self
+      .documents
+      .iter()
+      .find(|document| document.load_depth == 0)
+      .map_or(self.document, |document| document.document)
+  }
 }
 
 impl<'a> From<&'a Document> for ProjectView<'a> {
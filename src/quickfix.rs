@@ -2,21 +2,224 @@ use super::*;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Quickfix {
+  /// Whether the document at `uri` should be created before the edits are
+  /// applied.
+  pub create: bool,
   pub edits: Vec<lsp::TextEdit>,
   pub range: lsp::Range,
+  /// Whether the quickfix can be applied without user review, for example as
+  /// part of a `source.fixAll` action on save.
+  pub safe: bool,
   pub title: String,
+  /// The document the edits apply to, when it isn't the one the quickfix was
+  /// requested for.
+  pub uri: Option<lsp::Url>,
 }
 
 impl Quickfix {
+  /// Create an empty document at `uri`.
+  #[must_use]
+  pub fn create_file(
+    uri: lsp::Url,
+    range: lsp::Range,
+    title: impl Into<String>,
+  ) -> Self {
+    Self {
+      create: true,
+      edits: Vec::new(),
+      range,
+      safe: false,
+      title: title.into(),
+      uri: Some(uri),
+    }
+  }
+
+  /// Append a stub recipe for a missing dependency to the end of `document`,
+  /// with one parameter per dependency argument.
+  ///
+  /// Arguments that are plain identifiers lend their name to the parameter,
+  /// everything else gets a positional `argN` name.
+  #[must_use]
+  pub fn create_recipe(document: &Document, dependency: &Dependency) -> Self {
+    let mut parameters = Vec::<String>::new();
+
+    for (index, argument) in dependency.arguments.iter().enumerate() {
+      let identifier = argument
+        .value
+        .chars()
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && argument.value.chars().all(|character| {
+          character.is_ascii_alphanumeric() || matches!(character, '_' | '-')
+        });
+
+      parameters.push(if identifier && !parameters.contains(&argument.value) {
+        argument.value.clone()
+      } else {
+        format!("arg{}", index + 1)
+      });
+    }
+
+    let header = once(dependency.name.value.as_str())
+      .chain(parameters.iter().map(String::as_str))
+      .collect::<Vec<_>>()
+      .join(" ");
+
+    let length = document.content.len_chars();
+
+    let separator = if length == 0 {
+      ""
+    } else if document.content.char(length - 1) == '\n' {
+      "\n"
+    } else {
+      "\n\n"
+    };
+
+    let end = document
+      .content
+      .byte_to_lsp_position(document.content.len_bytes());
+
+    Self {
+      create: false,
+      edits: vec![lsp::TextEdit {
+        range: lsp::Range { start: end, end },
+        new_text: format!("{separator}{header}:\n"),
+      }],
+      range: dependency.range,
+      safe: false,
+      title: format!("Create recipe `{}`", dependency.name.value),
+      uri: None,
+    }
+  }
+
+  /// Insert an empty assignment for `name` after the last assignment in
+  /// `document`, or after its leading shebang and comments if it has none.
+  #[must_use]
+  pub fn create_variable(
+    document: &Document,
+    name: &str,
+    range: lsp::Range,
+  ) -> Self {
+    let last = document
+      .variables()
+      .into_iter()
+      .map(|variable| variable.range)
+      .max_by_key(|range| range.end);
+
+    let assignment = format!("{name} := \"\"");
+
+    let title = format!("Create variable `{name}`");
+
+    let quickfix = Self::line_after(document, last, &assignment, title);
+
+    Self {
+      range,
+      safe: false,
+      uri: Some(document.uri.clone()),
+      ..quickfix
+    }
+  }
+
+  /// The line after the shebang and comment lines at the start of
+  /// `document`, and whether that line is blank.
+  ///
+  /// Comments directly above an item document it, so only a shebang is
+  /// skipped in that case.
+  fn header(document: &Document) -> Option<(u32, bool)> {
+    let line = |index: usize| {
+      (index < document.content.len_lines())
+        .then(|| document.content.line(index).to_string())
+        .filter(|line| !line.is_empty())
+    };
+
+    let mut end = 0;
+
+    while line(end).is_some_and(|line| line.trim_start().starts_with('#')) {
+      end += 1;
+    }
+
+    let blank = line(end).is_some_and(|line| line.trim().is_empty());
+
+    if !blank && line(end).is_some() {
+      end = usize::from(line(0).is_some_and(|line| line.starts_with("#!")));
+    }
+
+    if end == 0 {
+      return None;
+    }
+
+    Some((u32::try_from(end).ok()?, blank))
+  }
+
+  #[must_use]
+  pub fn insertion(
+    position: lsp::Position,
+    new_text: impl Into<String>,
+    title: impl Into<String>,
+  ) -> Self {
+    let range = lsp::Range {
+      start: position,
+      end: position,
+    };
+
+    Self {
+      create: false,
+      edits: vec![lsp::TextEdit {
+        range,
+        new_text: new_text.into(),
+      }],
+      range,
+      safe: true,
+      title: title.into(),
+      uri: None,
+    }
+  }
+
+  /// Insert `line` on its own line after the item spanning `previous`, or,
+  /// if there is no such item, followed by a blank line after the leading
+  /// shebang and comments of `document`.
+  #[must_use]
+  pub fn line_after(
+    document: &Document,
+    previous: Option<lsp::Range>,
+    line: &str,
+    title: impl Into<String>,
+  ) -> Self {
+    match (previous, Self::header(document)) {
+      (Some(previous), _) if previous.end.character == 0 => {
+        Self::insertion(previous.end, format!("{line}\n"), title)
+      }
+      (Some(previous), _) => {
+        Self::insertion(previous.end, format!("\n{line}"), title)
+      }
+      (None, Some((end, true))) => Self::insertion(
+        lsp::Position::new(end + 1, 0),
+        format!("{line}\n\n"),
+        title,
+      ),
+      (None, Some((end, false))) => Self::insertion(
+        lsp::Position::new(end, 0),
+        format!("\n{line}\n\n"),
+        title,
+      ),
+      (None, None) => {
+        Self::insertion(lsp::Position::default(), format!("{line}\n\n"), title)
+      }
+    }
+  }
+
   #[must_use]
   pub fn removal(range: lsp::Range, title: impl Into<String>) -> Self {
     Self {
+      create: false,
       edits: vec![lsp::TextEdit {
         range,
         new_text: String::new(),
       }],
       range,
+      safe: true,
       title: title.into(),
+      uri: None,
     }
   }
 
@@ -25,15 +228,33 @@ impl Quickfix {
     let replacement = replacement.into();
 
     Self {
+      create: false,
       edits: vec![lsp::TextEdit {
         range: name.range,
         new_text: replacement.clone(),
       }],
       range: name.range,
+      safe: true,
       title: format!("Replace `{}` with `{replacement}`", name.value),
+      uri: None,
     }
   }
 
+  #[must_use]
+  pub fn safe(self, safe: bool) -> Self {
+    Self { safe, ..self }
+  }
+
+  /// Whether applying `other` would make the same change as this quickfix,
+  /// regardless of which diagnostic each one is attached to.
+  #[must_use]
+  pub fn same_edit(&self, other: &Self) -> bool {
+    self.create == other.create
+      && self.edits == other.edits
+      && self.title == other.title
+      && self.uri == other.uri
+  }
+
   #[must_use]
   pub fn setting_attribute(
     setting: &Setting,
@@ -49,15 +270,58 @@ impl Quickfix {
     let line = line.replacen(&setting.name.value, replacement, 1);
 
     Self {
+      create: false,
       edits: vec![lsp::TextEdit {
         range: setting.range,
         new_text: format!("[{attribute}]\n{line}"),
       }],
       range: setting.name.range,
+      safe: true,
       title: format!(
         "Replace `{}` with `[{attribute}] set {replacement}`",
         setting.name.value
       ),
+      uri: None,
+    }
+  }
+
+  /// Remove `range` along with the spaces or tabs preceding it on the same
+  /// line, so removing an item from a space-separated list leaves no gap.
+  #[must_use]
+  pub fn token_removal(
+    document: &Document,
+    range: lsp::Range,
+    title: impl Into<String>,
+  ) -> Self {
+    let line = range.start.line as usize;
+
+    let mut start = range.start;
+
+    if line < document.content.len_lines() {
+      let mut column = 0;
+
+      let mut whitespace = 0;
+
+      for character in document.content.line(line).chars() {
+        if column >= range.start.character {
+          break;
+        }
+
+        whitespace = if matches!(character, ' ' | '\t') {
+          whitespace + 1
+        } else {
+          0
+        };
+
+        column += u32::try_from(character.len_utf16()).unwrap_or(u32::MAX);
+      }
+
+      start.character = start.character.saturating_sub(whitespace);
+    }
+
+    Self {
+      range,
+      ..Self::removal(lsp::Range { start, ..range }, title)
     }
   }
 }
@@ -2,12 +2,17 @@ use super::*;
 
 pub struct Quickfixer<'a> {
   pub config: Option<&'a Config>,
+  /// Whether the client can apply `CreateFile` resource operations, without
+  /// which quickfixes creating a file aren't offered.
+  pub creates_files: bool,
   pub document: &'a Document,
   pub imported_documents: Vec<&'a Document>,
   pub parameters: &'a lsp::CodeActionParams,
 }
 
-impl Quickfixer<'_> {
+impl<'a> Quickfixer<'a> {
+  pub const FIX_ALL: &'static str = "source.fixAll.just-lsp";
+
   fn action(&self, code: &str, quickfix: Quickfix) -> lsp::CodeActionOrCommand {
     let diagnostics = self
       .parameters
@@ -24,45 +29,205 @@ impl Quickfixer<'_> {
       .cloned()
       .collect::<Vec<_>>();
 
+    let uri = self.uri(&quickfix).clone();
+
+    let edit = if quickfix.create {
+      Self::create(uri, quickfix.edits)
+    } else {
+      lsp::WorkspaceEdit {
+        changes: Some(HashMap::from([(uri, quickfix.edits)])),
+        ..Default::default()
+      }
+    };
+
     lsp::CodeActionOrCommand::CodeAction(lsp::CodeAction {
       title: quickfix.title,
       kind: Some(lsp::CodeActionKind::QUICKFIX),
       diagnostics: (!diagnostics.is_empty()).then_some(diagnostics),
-      edit: Some(lsp::WorkspaceEdit {
-        changes: Some(HashMap::from([(
-          self.parameters.text_document.uri.clone(),
-          quickfix.edits,
-        )])),
-        ..Default::default()
-      }),
+      edit: Some(edit),
       ..Default::default()
     })
   }
 
   #[must_use]
   pub fn collect(&self) -> Vec<lsp::CodeActionOrCommand> {
-    let context =
-      RuleContext::new(self.document, self.imported_documents.iter().copied());
+    let quickfixes = self.quickfixes();
+
+    let mut actions = Vec::new();
+
+    if self.requested(&lsp::CodeActionKind::QUICKFIX) {
+      let mut offered = Vec::<&Quickfix>::new();
+
+      for (id, quickfix) in &quickfixes {
+        // Several diagnostics under the range can share one fix, such as
+        // adding `set lists`, which only needs to be offered once.
+        if !quickfix.range.overlaps(self.parameters.range)
+          || offered.iter().any(|other| other.same_edit(quickfix))
+        {
+          continue;
+        }
+
+        offered.push(quickfix);
+
+        actions.push(self.action(id, quickfix.clone()));
+      }
+    }
+
+    // Fixing everything is never relevant to the cursor, so it's only offered
+    // to clients asking for it by kind, such as on save.
+    if self.parameters.context.only.is_some()
+      && self.requested(&lsp::CodeActionKind::new(Self::FIX_ALL))
+      && let Some(action) = self.fix_all(&quickfixes)
+    {
+      actions.push(action);
+    }
+
+    actions
+  }
+
+  /// A workspace edit that creates the document at `uri`, leaving an
+  /// existing file untouched, and then applies `edits` to it.
+  fn create(uri: lsp::Url, edits: Vec<lsp::TextEdit>) -> lsp::WorkspaceEdit {
+    let mut operations = vec![lsp::DocumentChangeOperation::Op(
+      lsp::ResourceOp::Create(lsp::CreateFile {
+        uri: uri.clone(),
+        options: Some(lsp::CreateFileOptions {
+          overwrite: Some(false),
+          ignore_if_exists: Some(true),
+        }),
+        annotation_id: None,
+      }),
+    )];
+
+    if !edits.is_empty() {
+      operations.push(lsp::DocumentChangeOperation::Edit(
+        lsp::TextDocumentEdit {
+          text_document: lsp::OptionalVersionedTextDocumentIdentifier {
+            uri,
+            version: None,
+          },
+          edits: edits.into_iter().map(lsp::OneOf::Left).collect(),
+        },
+      ));
+    }
+
+    lsp::WorkspaceEdit {
+      document_changes: Some(lsp::DocumentChanges::Operations(operations)),
+      ..Default::default()
+    }
+  }
+
+  /// Merge every safe quickfix in the document into a single action.
+  ///
+  /// Quickfixes whose edits overlap an edit that has already been accepted
+  /// are skipped, since applying both would produce a conflicting
+  /// `WorkspaceEdit`.
+  fn fix_all(
+    &self,
+    quickfixes: &[(&str, Quickfix)],
+  ) -> Option<lsp::CodeActionOrCommand> {
+    let mut changes = HashMap::<lsp::Url, Vec<lsp::TextEdit>>::new();
+
+    for (_, quickfix) in quickfixes.iter().filter(|(_, quickfix)| quickfix.safe)
+    {
+      let edits = changes.entry(self.uri(quickfix).clone()).or_default();
+
+      let conflicts = quickfix.edits.iter().any(|edit| {
+        edits
+          .iter()
+          .any(|accepted| accepted.range.overlaps(edit.range))
+      });
+
+      if !conflicts {
+        edits.extend(quickfix.edits.iter().cloned());
+      }
+    }
+
+    changes.retain(|_, edits| !edits.is_empty());
+
+    if changes.is_empty() {
+      return None;
+    }
+
+    for edits in changes.values_mut() {
+      edits.sort_by_key(|edit| (edit.range.start, edit.range.end));
+    }
+
+    Some(lsp::CodeActionOrCommand::CodeAction(lsp::CodeAction {
+      title: "Fix all auto-fixable problems".into(),
+      kind: Some(lsp::CodeActionKind::new(Self::FIX_ALL)),
+      edit: Some(lsp::WorkspaceEdit {
+        changes: Some(changes),
+        ..Default::default()
+      }),
+      ..Default::default()
+    }))
+  }
+
+  fn quickfixes(&self) -> Vec<(&'a str, Quickfix)> {
+    let context = RuleContext::new(
+      self.document,
+      self.imported_documents.iter().copied(),
+      None,
+    );
 
     inventory::iter::<&dyn Rule>
       .into_iter()
       .filter(|rule| {
-        self
-          .config
-          .unwrap_or(&Config::default())
-          .rule_config(rule.id())
-          .level()
-          != Some(RuleLevel::Off)
+        rule.enabled(
+          &self
+            .config
+            .unwrap_or(&Config::default())
+            .rule_config(rule.id()),
+        )
       })
       .flat_map(|rule| {
         rule
           .quickfixes(&context)
           .into_iter()
-          .filter(|quickfix| quickfix.range.overlaps(self.parameters.range))
-          .map(|quickfix| self.action(rule.id(), quickfix))
+          .filter(|quickfix| self.creates_files || !quickfix.create)
+          .map(|quickfix| (rule.id(), quickfix))
       })
+      .chain(
+        self
+          .config
+          .into_iter()
+          .flat_map(|config| {
+            config
+              .custom_rules
+              .iter()
+              .filter(|rule| rule.enabled(&config.rule_config(&rule.id)))
+          })
+          .flat_map(|rule| {
+            rule
+              .quickfixes(self.document)
+              .into_iter()
+              .map(|quickfix| (rule.id.as_str(), quickfix))
+          }),
+      )
       .collect()
   }
+
+  /// Whether actions of `kind` were requested, honoring the hierarchical
+  /// `only` filter from the client.
+  fn requested(&self, kind: &lsp::CodeActionKind) -> bool {
+    self.parameters.context.only.as_ref().is_none_or(|only| {
+      only.iter().any(|requested| {
+        kind.as_str() == requested.as_str()
+          || kind
+            .as_str()
+            .strip_prefix(requested.as_str())
+            .is_some_and(|rest| rest.starts_with('.'))
+      })
+    })
+  }
+
+  fn uri<'b>(&'b self, quickfix: &'b Quickfix) -> &'b lsp::Url {
+    quickfix
+      .uri
+      .as_ref()
+      .unwrap_or(&self.parameters.text_document.uri)
+  }
 }
 
 #[cfg(test)]
@@ -72,7 +237,11 @@ mod tests {
   #[derive(Debug)]
   struct Test {
     config: Config,
+    creates_files: bool,
     document: Document,
+    fix_all: Option<Vec<lsp::TextEdit>>,
+    imported: Vec<Document>,
+    only: Option<Vec<lsp::CodeActionKind>>,
     quickfixes: Vec<Quickfix>,
     range: lsp::Range,
   }
@@ -82,15 +251,53 @@ mod tests {
       Self { config, ..self }
     }
 
+    fn creates_files(self) -> Self {
+      Self {
+        creates_files: true,
+        ..self
+      }
+    }
+
+    fn fix_all(self, edits: Vec<lsp::TextEdit>) -> Self {
+      Self {
+        fix_all: Some(edits),
+        ..self
+      }
+    }
+
+    fn import(self, uri: &str, content: &str) -> Self {
+      Self {
+        imported: self
+          .imported
+          .into_iter()
+          .chain([
+            Document::new(content, lsp::Url::parse(uri).unwrap()).unwrap()
+          ])
+          .collect(),
+        ..self
+      }
+    }
+
     fn new(content: &str) -> Self {
       Self {
         config: Config::default(),
+        creates_files: false,
         document: Document::from(content),
+        fix_all: None,
+        imported: Vec::new(),
+        only: None,
         quickfixes: Vec::new(),
         range: lsp::Range::at(0, 0, 0, 0),
       }
     }
 
+    fn only(self, kind: &str) -> Self {
+      Self {
+        only: Some(vec![lsp::CodeActionKind::from(kind.to_string())]),
+        ..self
+      }
+    }
+
     fn quickfix(self, quickfix: Quickfix) -> Self {
       Self {
         quickfixes: self.quickfixes.into_iter().chain([quickfix]).collect(),
@@ -105,7 +312,11 @@ mod tests {
     fn run(self) {
       let Test {
         config,
+        creates_files,
         document,
+        fix_all,
+        imported,
+        only,
         quickfixes,
         range,
       } = self;
@@ -117,6 +328,7 @@ mod tests {
         range,
         context: lsp::CodeActionContext {
           diagnostics: Vec::new(),
+          only,
           ..Default::default()
         },
         work_done_progress_params: lsp::WorkDoneProgressParams::default(),
@@ -125,17 +337,71 @@ mod tests {
 
       let actions = Quickfixer {
         config: Some(&config),
+        creates_files,
         document: &document,
-        imported_documents: Vec::new(),
+        imported_documents: imported.iter().collect(),
         parameters: &parameters,
       }
-      .collect();
+      .collect()
+      .into_iter()
+      .map(|action| {
+        let lsp::CodeActionOrCommand::CodeAction(action) = action else {
+          unreachable!("expected CodeAction");
+        };
+
+        action
+      });
+
+      let (fix_all_actions, actions) =
+        actions.partition::<Vec<_>, _>(|action| {
+          action.kind == Some(lsp::CodeActionKind::new(Quickfixer::FIX_ALL))
+        });
+
+      if let Some(edits) = fix_all {
+        assert_eq!(
+          fix_all_actions,
+          if edits.is_empty() {
+            Vec::new()
+          } else {
+            vec![lsp::CodeAction {
+              title: "Fix all auto-fixable problems".to_string(),
+              kind: Some(lsp::CodeActionKind::new(Quickfixer::FIX_ALL)),
+              edit: Some(lsp::WorkspaceEdit {
+                changes: Some(HashMap::from([(document.uri.clone(), edits)])),
+                ..Default::default()
+              }),
+              ..Default::default()
+            }]
+          }
+        );
+      }
 
       assert_eq!(actions.len(), quickfixes.len());
 
       for (action, quickfix) in actions.into_iter().zip(quickfixes) {
-        let lsp::CodeActionOrCommand::CodeAction(action) = action else {
-          unreachable!("expected CodeAction");
+        let uri = quickfix.uri.unwrap_or_else(|| document.uri.clone());
+
+        let edit = if quickfix.create {
+          lsp::WorkspaceEdit {
+            document_changes: Some(lsp::DocumentChanges::Operations(vec![
+              lsp::DocumentChangeOperation::Op(lsp::ResourceOp::Create(
+                lsp::CreateFile {
+                  uri,
+                  options: Some(lsp::CreateFileOptions {
+                    overwrite: Some(false),
+                    ignore_if_exists: Some(true),
+                  }),
+                  annotation_id: None,
+                },
+              )),
+            ])),
+            ..Default::default()
+          }
+        } else {
+          lsp::WorkspaceEdit {
+            changes: Some(HashMap::from([(uri, quickfix.edits)])),
+            ..Default::default()
+          }
         };
 
         assert_eq!(
@@ -144,13 +410,7 @@ mod tests {
             title: quickfix.title,
             kind: Some(lsp::CodeActionKind::QUICKFIX),
             diagnostics: None,
-            edit: Some(lsp::WorkspaceEdit {
-              changes: Some(HashMap::from([(
-                document.uri.clone(),
-                quickfix.edits,
-              )])),
-              ..Default::default()
-            }),
+            edit: Some(edit),
             ..Default::default()
           }
         );
@@ -158,6 +418,331 @@ mod tests {
     }
   }
 
+  #[test]
+  fn adds_script_attribute() {
+    Test::new("[extension('.py')]\nfoo:\n  echo\n")
+      .range(lsp::Range::at(0, 2, 0, 2))
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(0, 0, 0, 0),
+          new_text: "[script]\n".to_string(),
+        }],
+        range: lsp::Range::at(0, 0, 1, 0),
+        safe: false,
+        title: "Add `[script]`".to_string(),
+        uri: None,
+      })
+      .run();
+  }
+
+  #[test]
+  fn adds_set_lists_after_settings() {
+    Test::new("set export\n\nfoo := 'a' || 'b'\n")
+      .range(lsp::Range::at(2, 12, 2, 12))
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(1, 0, 1, 0),
+          new_text: "set lists\n".to_string(),
+        }],
+        range: lsp::Range::at(2, 11, 2, 13),
+        safe: false,
+        title: "Add `set lists`".to_string(),
+        uri: None,
+      })
+      .run();
+  }
+
+  #[test]
+  fn adds_set_lists_below_shebang() {
+    Test::new("#!/usr/bin/env just --justfile\n\nfoo := 'a' || 'b'\n")
+      .range(lsp::Range::at(2, 12, 2, 12))
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(2, 0, 2, 0),
+          new_text: "set lists\n\n".to_string(),
+        }],
+        range: lsp::Range::at(2, 11, 2, 13),
+        safe: false,
+        title: "Add `set lists`".to_string(),
+        uri: None,
+      })
+      .run();
+  }
+
+  #[test]
+  fn adds_set_lists_once_for_several_diagnostics() {
+    Test::new("set export\n\nfoo := 'a' || 'b'\nbar := 'c' || 'd'\n")
+      .range(lsp::Range::at(2, 0, 4, 0))
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(1, 0, 1, 0),
+          new_text: "set lists\n".to_string(),
+        }],
+        range: lsp::Range::at(2, 11, 2, 13),
+        safe: false,
+        title: "Add `set lists`".to_string(),
+        uri: None,
+      })
+      .run();
+  }
+
+  #[test]
+  fn applies_custom_rule_fix_template() {
+    let config = serde_json::from_value::<Config>(serde_json::json!({
+      "custom-rules": [{
+        "id": "prefer-canonicalize",
+        "message": "Use `canonicalize` instead of `@name`",
+        "query": "((function_call name: (identifier) @name arguments: (sequence) @args) @diagnostic (#eq? @name \"absolute_path\"))",
+        "fix": "canonicalize(@args)"
+      }]
+    }))
+    .unwrap();
+
+    Test::new("foo := absolute_path('bar')\n\nbaz:\n  echo {{ foo }}\n")
+      .config(config)
+      .range(lsp::Range::at(0, 8, 0, 8))
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(0, 7, 0, 27),
+          new_text: "canonicalize('bar')".to_string(),
+        }],
+        range: lsp::Range::at(0, 7, 0, 27),
+        safe: false,
+        title: "Apply fix for `prefer-canonicalize`".to_string(),
+        uri: None,
+      })
+      .run();
+  }
+
+  #[test]
+  fn creates_missing_module_file() {
+    let tempdir = tempfile::tempdir().unwrap();
+
+    let path = tempdir.path().join("justfile");
+
+    fs::write(&path, "mod foo").unwrap();
+
+    Test {
+      document: Document::new(
+        "mod foo",
+        lsp::Url::from_file_path(&path).unwrap(),
+      )
+      .unwrap(),
+      ..Test::new("")
+    }
+    .creates_files()
+    .range(lsp::Range::at(0, 4, 0, 4))
+    .quickfix(Quickfix::create_file(
+      lsp::Url::from_file_path(tempdir.path().join("foo.just")).unwrap(),
+      lsp::Range::at(0, 4, 0, 7),
+      "Create `foo.just`",
+    ))
+    .run();
+  }
+
+  #[test]
+  fn creates_missing_module_file_only_if_client_supports_it() {
+    let tempdir = tempfile::tempdir().unwrap();
+
+    let path = tempdir.path().join("justfile");
+
+    fs::write(&path, "mod foo").unwrap();
+
+    Test {
+      document: Document::new(
+        "mod foo",
+        lsp::Url::from_file_path(&path).unwrap(),
+      )
+      .unwrap(),
+      ..Test::new("")
+    }
+    .range(lsp::Range::at(0, 4, 0, 4))
+    .run();
+  }
+
+  #[test]
+  fn creates_recipe_with_parameters() {
+    Test::new("test: (deploy target 'prod')")
+      .range(lsp::Range::at(0, 7, 0, 7))
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(0, 28, 0, 28),
+          new_text: "\n\ndeploy target arg2:\n".to_string(),
+        }],
+        range: lsp::Range::at(0, 6, 0, 28),
+        safe: false,
+        title: "Create recipe `deploy`".to_string(),
+        uri: None,
+      })
+      .run();
+  }
+
+  #[test]
+  fn creates_variable_after_last_assignment() {
+    Test::new("foo := 'a'\nbar := 'b'\n\nbuild:\n  echo {{ qux }}\n")
+      .range(lsp::Range::at(4, 11, 4, 11))
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(2, 0, 2, 0),
+          new_text: "qux := \"\"\n".to_string(),
+        }],
+        range: lsp::Range::at(4, 10, 4, 13),
+        safe: false,
+        title: "Create variable `qux`".to_string(),
+        uri: None,
+      })
+      .run();
+  }
+
+  #[test]
+  fn creates_variable_at_start_without_assignments() {
+    Test::new("build:\n  echo {{ qux }}\n")
+      .range(lsp::Range::at(1, 11, 1, 11))
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(0, 0, 0, 0),
+          new_text: "qux := \"\"\n\n".to_string(),
+        }],
+        range: lsp::Range::at(1, 10, 1, 13),
+        safe: false,
+        title: "Create variable `qux`".to_string(),
+        uri: None,
+      })
+      .run();
+  }
+
+  #[test]
+  fn creates_variable_after_leading_comments() {
+    Test::new("#!/usr/bin/env just\n# Tasks\n\nbuild:\n  echo {{ qux }}\n")
+      .range(lsp::Range::at(4, 11, 4, 11))
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(3, 0, 3, 0),
+          new_text: "qux := \"\"\n\n".to_string(),
+        }],
+        range: lsp::Range::at(4, 10, 4, 13),
+        safe: false,
+        title: "Create variable `qux`".to_string(),
+        uri: None,
+      })
+      .run();
+  }
+
+  #[test]
+  fn creates_variable_after_shebang_above_recipe_comment() {
+    Test::new("#!/usr/bin/env just\n# Build\nbuild:\n  echo {{ qux }}\n")
+      .range(lsp::Range::at(3, 11, 3, 11))
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(1, 0, 1, 0),
+          new_text: "\nqux := \"\"\n\n".to_string(),
+        }],
+        range: lsp::Range::at(3, 10, 3, 13),
+        safe: false,
+        title: "Create variable `qux`".to_string(),
+        uri: None,
+      })
+      .run();
+  }
+
+  #[test]
+  fn creates_variable_in_imported_document() {
+    Test::new("import 'vars.just'\n\nbuild:\n  echo {{ qux }}\n")
+      .import("file:///vars.just", "foo := 'a'\n")
+      .range(lsp::Range::at(3, 11, 3, 11))
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(0, 0, 0, 0),
+          new_text: "qux := \"\"\n\n".to_string(),
+        }],
+        range: lsp::Range::at(3, 10, 3, 13),
+        safe: false,
+        title: "Create variable `qux`".to_string(),
+        uri: None,
+      })
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(1, 0, 1, 0),
+          new_text: "qux := \"\"\n".to_string(),
+        }],
+        range: lsp::Range::at(3, 10, 3, 13),
+        safe: false,
+        title: "Create variable `qux` in `vars.just`".to_string(),
+        uri: Some(lsp::Url::parse("file:///vars.just").unwrap()),
+      })
+      .run();
+  }
+
+  #[test]
+  fn exports_or_removes_unused_parameter() {
+    Test::new("foo bar:\n  echo foo\n")
+      .range(lsp::Range::at(0, 5, 0, 5))
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(0, 3, 0, 7),
+          new_text: String::new(),
+        }],
+        range: lsp::Range::at(0, 4, 0, 7),
+        safe: false,
+        title: "Remove parameter `bar`".to_string(),
+        uri: None,
+      })
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(0, 4, 0, 4),
+          new_text: "$".to_string(),
+        }],
+        range: lsp::Range::at(0, 4, 0, 7),
+        safe: false,
+        title: "Export parameter `bar` with `$`".to_string(),
+        uri: None,
+      })
+      .run();
+  }
+
+  #[test]
+  fn exports_unused_variadic_parameter_after_kleene() {
+    Test::new("foo *args:\n  echo foo\n")
+      .range(lsp::Range::at(0, 6, 0, 6))
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(0, 3, 0, 9),
+          new_text: String::new(),
+        }],
+        range: lsp::Range::at(0, 4, 0, 9),
+        safe: false,
+        title: "Remove parameter `args`".to_string(),
+        uri: None,
+      })
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(0, 5, 0, 5),
+          new_text: "$".to_string(),
+        }],
+        range: lsp::Range::at(0, 4, 0, 9),
+        safe: false,
+        title: "Export parameter `args` with `$`".to_string(),
+        uri: None,
+      })
+      .run();
+  }
+
   #[test]
   fn filters_multiple_calls_by_range() {
     Test::new(
@@ -165,16 +750,107 @@ mod tests {
     )
     .range(lsp::Range::at(0, 10, 0, 10))
     .quickfix(Quickfix {
+      create: false,
       edits: vec![lsp::TextEdit {
         range: lsp::Range::at(0, 7, 0, 14),
         new_text: "env".to_string(),
       }],
       range: lsp::Range::at(0, 7, 0, 14),
+      safe: true,
       title: "Replace `env_var` with `env`".to_string(),
+      uri: None,
     })
     .run();
   }
 
+  #[test]
+  fn fix_all_ignores_range() {
+    Test::new(
+      "foo := env_var(\"A\")\nbar := env_var_or_default(\"B\", \"C\")\n",
+    )
+    .range(lsp::Range::at(0, 10, 0, 10))
+    .only("source.fixAll")
+    .fix_all(vec![
+      lsp::TextEdit {
+        range: lsp::Range::at(0, 7, 0, 14),
+        new_text: "env".to_string(),
+      },
+      lsp::TextEdit {
+        range: lsp::Range::at(1, 7, 1, 25),
+        new_text: "env".to_string(),
+      },
+    ])
+    .run();
+  }
+
+  #[test]
+  fn fix_all_merges_quickfixes_from_multiple_rules() {
+    Test::new("set windows-powershell := true\n[parallel]\nfoo: bar\nbar:\n")
+      .only(Quickfixer::FIX_ALL)
+      .fix_all(vec![
+        lsp::TextEdit {
+          range: lsp::Range::at(0, 4, 0, 22),
+          new_text: "windows-shell".to_string(),
+        },
+        lsp::TextEdit {
+          range: lsp::Range::at(1, 0, 2, 0),
+          new_text: String::new(),
+        },
+      ])
+      .run();
+  }
+
+  #[test]
+  fn fix_all_not_offered_without_quickfixes() {
+    Test::new("foo := env(\"A\")\n")
+      .only(Quickfixer::FIX_ALL)
+      .fix_all(Vec::new())
+      .run();
+  }
+
+  #[test]
+  fn fix_all_requires_explicit_only() {
+    Test::new("foo := env_var(\"A\")\n")
+      .range(lsp::Range::at(0, 10, 0, 10))
+      .fix_all(Vec::new())
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(0, 7, 0, 14),
+          new_text: "env".to_string(),
+        }],
+        range: lsp::Range::at(0, 7, 0, 14),
+        safe: true,
+        title: "Replace `env_var` with `env`".to_string(),
+        uri: None,
+      })
+      .run();
+  }
+
+  #[test]
+  fn fix_all_skips_indentation_normalization() {
+    Test::new("foo:\n  echo a\n\techo b\nbar:\n  echo a\n    echo b\n")
+      .only(Quickfixer::FIX_ALL)
+      .fix_all(Vec::new())
+      .run();
+  }
+
+  #[test]
+  fn fix_all_skips_disabled_rules() {
+    let config = serde_json::from_value::<Config>(serde_json::json!({
+      "rules": {
+        "deprecated-function": "off"
+      }
+    }))
+    .unwrap();
+
+    Test::new("foo := env_var(\"A\")\n")
+      .config(config)
+      .only(Quickfixer::FIX_ALL)
+      .fix_all(Vec::new())
+      .run();
+  }
+
   #[test]
   fn ignores_setting_outside_range() {
     Test::new("set windows-powershell := true\nset export := true\n")
@@ -182,17 +858,108 @@ mod tests {
       .run();
   }
 
+  #[test]
+  fn normalizes_inconsistent_indentation() {
+    Test::new("foo:\n  echo a\n    echo b\n")
+      .range(lsp::Range::at(2, 0, 2, 0))
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(2, 0, 2, 4),
+          new_text: "  ".to_string(),
+        }],
+        range: lsp::Range::at(2, 0, 2, 4),
+        safe: false,
+        title: "Normalize indentation of recipe `foo`".to_string(),
+        uri: None,
+      })
+      .run();
+  }
+
+  #[test]
+  fn normalizes_mixed_indentation() {
+    Test::new("foo:\n  echo a\n\techo b\n")
+      .range(lsp::Range::at(2, 0, 2, 0))
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(2, 0, 2, 1),
+          new_text: "  ".to_string(),
+        }],
+        range: lsp::Range::at(2, 0, 2, 1),
+        safe: false,
+        title: "Normalize indentation of recipe `foo`".to_string(),
+        uri: None,
+      })
+      .run();
+  }
+
+  #[test]
+  fn only_quickfix_excludes_fix_all() {
+    Test::new("foo := env_var(\"A\")\n")
+      .range(lsp::Range::at(0, 10, 0, 10))
+      .only("quickfix")
+      .fix_all(Vec::new())
+      .quickfix(Quickfix::replacement(
+        &TextNode {
+          range: lsp::Range::at(0, 7, 0, 14),
+          value: "env_var".to_string(),
+        },
+        "env",
+      ))
+      .run();
+  }
+
+  #[test]
+  fn removes_duplicate_dependency() {
+    Test::new("bar:\n\nfoo: bar bar\n")
+      .range(lsp::Range::at(2, 10, 2, 10))
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(2, 8, 2, 12),
+          new_text: String::new(),
+        }],
+        range: lsp::Range::at(2, 9, 2, 12),
+        safe: true,
+        title: "Remove duplicate dependency `bar`".to_string(),
+        uri: None,
+      })
+      .run();
+  }
+
   #[test]
   fn removes_parallel_attribute() {
     Test::new("[parallel]\nfoo: bar\nbar:\n")
       .range(lsp::Range::at(0, 0, 1, 0))
       .quickfix(Quickfix {
+        create: false,
         edits: vec![lsp::TextEdit {
           range: lsp::Range::at(0, 0, 1, 0),
           new_text: String::new(),
         }],
         range: lsp::Range::at(0, 0, 1, 0),
+        safe: true,
         title: "Remove `[parallel]`".to_string(),
+        uri: None,
+      })
+      .run();
+  }
+
+  #[test]
+  fn removes_unused_variable() {
+    Test::new("foo := 'a'\n")
+      .range(lsp::Range::at(0, 1, 0, 1))
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(0, 0, 1, 0),
+          new_text: String::new(),
+        }],
+        range: lsp::Range::at(0, 0, 0, 3),
+        safe: false,
+        title: "Remove unused variable `foo`".to_string(),
+        uri: None,
       })
       .run();
   }
@@ -202,12 +969,15 @@ mod tests {
     Test::new("set windows-powershell := true\n")
       .range(lsp::Range::at(0, 4, 0, 4))
       .quickfix(Quickfix {
+        create: false,
         edits: vec![lsp::TextEdit {
           range: lsp::Range::at(0, 4, 0, 22),
           new_text: "windows-shell".to_string(),
         }],
         range: lsp::Range::at(0, 4, 0, 22),
+        safe: true,
         title: "Replace `windows-powershell` with `windows-shell`".to_string(),
+        uri: None,
       })
       .run();
   }
@@ -219,6 +989,7 @@ mod tests {
     )
     .range(lsp::Range::at(0, 4, 0, 4))
     .quickfix(Quickfix {
+      create: false,
       edits: vec![lsp::TextEdit {
         range: lsp::Range::at(0, 0, 1, 0),
         new_text:
@@ -226,11 +997,40 @@ mod tests {
             .to_string(),
       }],
       range: lsp::Range::at(0, 4, 0, 17),
+      safe: true,
       title: "Replace `windows-shell` with `[windows] set shell`".to_string(),
+      uri: None,
     })
     .run();
   }
 
+  #[test]
+  fn shell_injection_wraps_parameter_in_quote() {
+    let config = serde_json::from_value::<Config>(serde_json::json!({
+      "rules": {
+        "shell-injection": "warning"
+      }
+    }))
+    .unwrap();
+
+    Test::new("deploy dir:\n  rm -rf {{dir}}\n")
+      .config(config)
+      .range(lsp::Range::at(1, 12, 1, 12))
+      .fix_all(Vec::new())
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(1, 11, 1, 14),
+          new_text: "quote(dir)".to_string(),
+        }],
+        range: lsp::Range::at(1, 9, 1, 16),
+        safe: false,
+        title: "Wrap `dir` in `quote()`".to_string(),
+        uri: None,
+      })
+      .run();
+  }
+
   #[test]
   fn skips_disabled_rules() {
     let config = serde_json::from_value::<Config>(serde_json::json!({
@@ -254,4 +1054,55 @@ mod tests {
     .range(lsp::Range::at(2, 4, 2, 4))
     .run();
   }
+
+  #[test]
+  fn suggests_platform_value_spelling() {
+    Test::new(
+      "set export\n\nfoo := if os() == 'darwin' { 'a' } else { 'b' }\n",
+    )
+    .range(lsp::Range::at(2, 20, 2, 20))
+    .fix_all(Vec::new())
+    .quickfix(Quickfix {
+      create: false,
+      edits: vec![lsp::TextEdit {
+        range: lsp::Range::at(2, 19, 2, 25),
+        new_text: "macos".to_string(),
+      }],
+      range: lsp::Range::at(2, 19, 2, 25),
+      safe: false,
+      title: "Replace `darwin` with `macos`".to_string(),
+      uri: None,
+    })
+    .run();
+  }
+
+  #[test]
+  fn suggests_similar_recipe_name() {
+    Test::new("build:\n\ntest: buidl\n")
+      .range(lsp::Range::at(2, 7, 2, 7))
+      .fix_all(Vec::new())
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(2, 6, 2, 11),
+          new_text: "build".to_string(),
+        }],
+        range: lsp::Range::at(2, 6, 2, 11),
+        safe: false,
+        title: "Replace `buidl` with `build`".to_string(),
+        uri: None,
+      })
+      .quickfix(Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(3, 0, 3, 0),
+          new_text: "\nbuidl:\n".to_string(),
+        }],
+        range: lsp::Range::at(2, 6, 2, 11),
+        safe: false,
+        title: "Create recipe `buidl`".to_string(),
+        uri: None,
+      })
+      .run();
+  }
 }
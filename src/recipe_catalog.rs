@@ -0,0 +1,248 @@
+use super::*;
+
+/// Every recipe that can be run from a project's root justfile: those in
+/// the root's import scope that aren't overridden, followed by those of
+/// each module. Modules without a project in the workspace are loaded into
+/// a scratch store, leaving the workspace untouched.
+pub(crate) struct RecipeCatalog<'a> {
+  workspace: &'a Workspace,
+}
+
+impl<'a> RecipeCatalog<'a> {
+  /// The recipes of `project` that aren't overridden, and the modules its
+  /// documents declare, each with its module path.
+  fn namespace(
+    documents: &DocumentStore,
+    project: &Project,
+    module_path: &[String],
+  ) -> (Vec<RecipeJson>, Vec<(Vec<String>, lsp::Url)>) {
+    let mut recipes = Vec::new();
+
+    let mut modules = Vec::new();
+
+    let Some(root) = documents.get(&project.root) else {
+      return (recipes, modules);
+    };
+
+    let view = ProjectView::new(root, &project.import_scope, documents);
+
+    for scope_document in project.import_scope.documents() {
+      let Some(document) = documents.get(&scope_document.uri) else {
+        continue;
+      };
+
+      for recipe in document.recipes() {
+        let effective =
+          view.find_recipe(&recipe.name.value).is_some_and(|located| {
+            located.uri == document.uri && located.value.range == recipe.range
+          });
+
+        if effective {
+          recipes.push(RecipeJson::new(document, recipe, module_path));
+        }
+      }
+
+      for module in document.modules() {
+        if let Some(path) = module.resolve(&document.uri)
+          && let Ok(uri) = lsp::Url::from_file_path(path.lexiclean())
+        {
+          let mut path = module_path.to_vec();
+
+          path.push(module.name.value);
+
+          modules.push((path, uri));
+        }
+      }
+    }
+
+    (recipes, modules)
+  }
+
+  pub(crate) fn new(workspace: &'a Workspace) -> Self {
+    Self { workspace }
+  }
+
+  /// The catalog of the project containing `uri`: the project rooted at it
+  /// if there is one, else the first project importing it, else a project
+  /// loaded with it as the root.
+  pub(crate) fn recipes(self, uri: &lsp::Url) -> Vec<RecipeJson> {
+    let root = if self.workspace.projects.contains_key(uri) {
+      uri.clone()
+    } else {
+      self
+        .workspace
+        .projects
+        .values()
+        .filter(|project| {
+          project
+            .import_scope
+            .documents()
+            .iter()
+            .any(|document| document.uri == *uri)
+        })
+        .map(|project| project.root.clone())
+        .min()
+        .unwrap_or_else(|| uri.clone())
+    };
+
+    let mut recipes = Vec::new();
+
+    let mut scratch = DocumentStore::default();
+
+    let mut queue = VecDeque::from([(Vec::new(), root, Vec::new())]);
+
+    while let Some((module_path, uri, mut ancestors)) = queue.pop_front() {
+      // The same file may be mounted as several modules, but a module that
+      // contains itself is a cycle.
+      if ancestors.contains(&uri) {
+        continue;
+      }
+
+      let (namespace, modules) =
+        if let Some(project) = self.workspace.projects.get(&uri) {
+          Self::namespace(&self.workspace.documents, project, &module_path)
+        } else if let Ok(project) = ProjectLoader::load(&mut scratch, &uri) {
+          Self::namespace(&scratch, &project, &module_path)
+        } else {
+          continue;
+        };
+
+      recipes.extend(namespace);
+
+      ancestors.push(uri);
+
+      queue.extend(
+        modules
+          .into_iter()
+          .map(|(path, uri)| (path, uri, ancestors.clone())),
+      );
+    }
+
+    recipes
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, indoc::indoc, pretty_assertions::assert_eq};
+
+  fn catalog(
+    workspace: &Workspace,
+    root: &lsp::Url,
+  ) -> Vec<(String, String, String)> {
+    RecipeCatalog::new(workspace)
+      .recipes(root)
+      .into_iter()
+      .map(|recipe| {
+        (
+          recipe.module_path.join("::"),
+          recipe.name,
+          recipe
+            .location
+            .uri
+            .path_segments()
+            .and_then(Iterator::last)
+            .unwrap()
+            .to_string(),
+        )
+      })
+      .collect()
+  }
+
+  fn write(tempdir: &Path, path: &str, content: &str) -> lsp::Url {
+    let path = tempdir.join(path);
+
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, content).unwrap();
+
+    lsp::Url::from_file_path(path).unwrap()
+  }
+
+  #[test]
+  fn imports_and_modules() {
+    let tempdir = tempfile::tempdir().unwrap();
+
+    let root = write(
+      tempdir.path(),
+      "justfile",
+      indoc! {
+        "
+        import 'common.just'
+        mod bar
+
+        build:
+        "
+      },
+    );
+
+    write(tempdir.path(), "common.just", "build:\n\nlint:\n");
+
+    write(
+      tempdir.path(),
+      "bar/mod.just",
+      indoc! {
+        "
+        mod baz 'nested.just'
+
+        test:
+        "
+      },
+    );
+
+    write(tempdir.path(), "bar/nested.just", "deploy:\n");
+
+    let mut workspace = Workspace::default();
+
+    workspace.load_project(root.clone()).unwrap();
+
+    assert_eq!(
+      catalog(&workspace, &root),
+      [
+        (String::new(), "build".into(), "justfile".into()),
+        (String::new(), "lint".into(), "common.just".into()),
+        ("bar".into(), "test".into(), "mod.just".into()),
+        ("bar::baz".into(), "deploy".into(), "nested.just".into()),
+      ]
+    );
+  }
+
+  #[test]
+  fn same_file_as_several_modules() {
+    let tempdir = tempfile::tempdir().unwrap();
+
+    let root = write(
+      tempdir.path(),
+      "justfile",
+      indoc! {
+        "
+        mod a 'x.just'
+        mod b 'x.just'
+        "
+      },
+    );
+
+    write(
+      tempdir.path(),
+      "x.just",
+      indoc! {
+        "
+        mod again 'x.just'
+
+        foo:
+        "
+      },
+    );
+
+    let mut workspace = Workspace::default();
+
+    workspace.load_project(root.clone()).unwrap();
+
+    assert_eq!(
+      catalog(&workspace, &root),
+      [
+        ("a".into(), "foo".into(), "x.just".into()),
+        ("b".into(), "foo".into(), "x.just".into()),
+      ]
+    );
+  }
+}
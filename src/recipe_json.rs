@@ -0,0 +1,170 @@
+use super::*;
+
+/// A recipe as reported by the `just-lsp/recipes` request.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct RecipeJson {
+  /// The prompt shown before running, for recipes marked `[confirm]`.
+  pub confirm: Option<String>,
+  pub dependencies: Vec<String>,
+  /// The `[doc]` attribute's text, or else the comment on the line above.
+  pub doc: Option<String>,
+  pub groups: Vec<String>,
+  pub location: lsp::Location,
+  /// The names of the modules containing the recipe, outermost first.
+  pub module_path: Vec<String>,
+  pub name: String,
+  pub parameters: Vec<ParameterJson>,
+  pub private: bool,
+}
+
+impl RecipeJson {
+  /// The comment on the line directly above `recipe` and its attributes.
+  fn comment(document: &Document, recipe: &Recipe) -> Option<String> {
+    let line = recipe.range.start.line.checked_sub(1)?;
+
+    let text = document
+      .content
+      .get_line(usize::try_from(line).ok()?)?
+      .to_string();
+
+    let comment = text.trim().strip_prefix('#')?;
+
+    (!comment.starts_with('!')).then(|| comment.trim().to_string())
+  }
+
+  #[must_use]
+  pub fn new(
+    document: &Document,
+    recipe: Recipe,
+    module_path: &[String],
+  ) -> Self {
+    let attribute_literals = |name: &str| {
+      recipe
+        .attributes
+        .iter()
+        .filter(|attribute| attribute.name.value == name)
+        .filter_map(|attribute| attribute.arguments.first())
+        .filter_map(|argument| argument.value.literal())
+        .collect::<Vec<_>>()
+    };
+
+    let confirm = recipe.has_attribute("confirm").then(|| {
+      attribute_literals("confirm")
+        .pop()
+        .unwrap_or_else(|| format!("Run recipe `{}`?", recipe.name.value))
+    });
+
+    let doc = match recipe.find_attribute("doc") {
+      Some(attribute) => attribute
+        .arguments
+        .first()
+        .and_then(|argument| argument.value.literal()),
+      None => Self::comment(document, &recipe),
+    };
+
+    Self {
+      confirm,
+      dependencies: recipe
+        .dependencies
+        .iter()
+        .map(|dependency| dependency.name.value.clone())
+        .collect(),
+      doc,
+      groups: attribute_literals("group"),
+      location: lsp::Location {
+        uri: document.uri.clone(),
+        range: recipe.range,
+      },
+      module_path: module_path.to_vec(),
+      private: recipe.name.value.starts_with('_')
+        || recipe.has_attribute("private"),
+      name: recipe.name.value,
+      parameters: recipe
+        .parameters
+        .into_iter()
+        .map(ParameterJson::from)
+        .collect(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, indoc::indoc, pretty_assertions::assert_eq};
+
+  fn recipe(content: &str) -> RecipeJson {
+    let document = Document::from(content);
+
+    let recipe = document.recipes().pop().unwrap();
+
+    RecipeJson::new(&document, recipe, &["foo".into()])
+  }
+
+  #[test]
+  fn attributes() {
+    let recipe = recipe(indoc! {
+      "
+      # ignored
+      [private]
+      [group('ci')]
+      [group('release')]
+      [confirm('Really deploy?')]
+      [doc('Deploy the site')]
+      deploy target *flags: build
+      "
+    });
+
+    assert_eq!(recipe.confirm.as_deref(), Some("Really deploy?"));
+    assert_eq!(recipe.dependencies, ["build"]);
+    assert_eq!(recipe.doc.as_deref(), Some("Deploy the site"));
+    assert_eq!(recipe.groups, ["ci", "release"]);
+    assert_eq!(recipe.module_path, ["foo"]);
+    assert_eq!(recipe.name, "deploy");
+    assert!(recipe.private);
+
+    assert_eq!(
+      recipe.parameters,
+      [
+        ParameterJson {
+          default_value: None,
+          name: "target".into(),
+          variadic: None,
+        },
+        ParameterJson {
+          default_value: None,
+          name: "flags".into(),
+          variadic: Some(VariadicType::ZeroOrMore),
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn comment_doc() {
+    let recipe = recipe(indoc! {
+      "
+      # Build everything
+      [confirm]
+      build:
+      "
+    });
+
+    assert_eq!(recipe.confirm.as_deref(), Some("Run recipe `build`?"));
+    assert_eq!(recipe.doc.as_deref(), Some("Build everything"));
+    assert!(!recipe.private);
+  }
+
+  #[test]
+  fn empty_doc_attribute_suppresses_comment() {
+    let recipe = recipe(indoc! {
+      "
+      # Build everything
+      [doc]
+      _build:
+      "
+    });
+
+    assert_eq!(recipe.doc, None);
+    assert!(recipe.private);
+  }
+}
@@ -0,0 +1,220 @@
+use super::*;
+
+/// Sent as output from a run arrives, and once more with `finished` set
+/// when the process exits.
+pub(crate) enum RecipeOutputAppended {}
+
+impl lsp::notification::Notification for RecipeOutputAppended {
+  const METHOD: &'static str = "just-lsp/recipeOutputAppended";
+
+  type Params = RecipeOutputChunk;
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RecipeOutput {
+  /// The exit code of the process, once it has exited with one.
+  pub(crate) exit_code: Option<i32>,
+  pub(crate) finished: bool,
+  /// How many bytes of output were dropped from the start of the
+  /// scrollback to keep it under [`RecipeRuns::MAX_SCROLLBACK`].
+  pub(crate) offset: usize,
+  pub(crate) output: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RecipeOutputChunk {
+  pub(crate) finished: bool,
+  /// The position of `text` in the run's full output.
+  pub(crate) offset: usize,
+  pub(crate) run_id: u64,
+  pub(crate) text: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RecipeOutputParams {
+  pub(crate) run_id: u64,
+}
+
+/// The output of `just` invocations started by commands, retained by run id
+/// so clients can fetch it with `just-lsp/recipeOutput` instead of receiving
+/// edits to a `just-recipe:` virtual document.
+#[derive(Debug, Default)]
+pub(crate) struct RecipeRuns {
+  next_id: u64,
+  runs: HashMap<u64, RecipeOutput>,
+}
+
+impl RecipeRuns {
+  const MAX_RUNS: usize = 32;
+
+  const MAX_SCROLLBACK: usize = 1 << 20;
+
+  /// Appends `text` to the output of run `id`, dropping whole lines from
+  /// the front once the scrollback is full, and returns the chunk to send
+  /// to the client.
+  pub(crate) fn append(&mut self, id: u64, text: &str) -> RecipeOutputChunk {
+    let mut offset = 0;
+
+    if let Some(run) = self.runs.get_mut(&id) {
+      offset = run.offset + run.output.len();
+
+      run.output.push_str(text);
+
+      if run.output.len() > Self::MAX_SCROLLBACK {
+        let excess = run.output.len() - Self::MAX_SCROLLBACK;
+
+        let cut = run.output.as_bytes()[excess..]
+          .iter()
+          .position(|&byte| byte == b'\n')
+          .map_or(run.output.len(), |newline| excess + newline + 1);
+
+        run.output.drain(..cut);
+
+        run.offset += cut;
+      }
+    }
+
+    RecipeOutputChunk {
+      finished: false,
+      offset,
+      run_id: id,
+      text: text.into(),
+    }
+  }
+
+  /// Marks run `id` as finished and returns the final, empty chunk.
+  pub(crate) fn finish(
+    &mut self,
+    id: u64,
+    exit_code: Option<i32>,
+  ) -> RecipeOutputChunk {
+    let mut chunk = self.append(id, "");
+
+    if let Some(run) = self.runs.get_mut(&id) {
+      run.exit_code = exit_code;
+      run.finished = true;
+    }
+
+    chunk.finished = true;
+
+    chunk
+  }
+
+  pub(crate) fn get(&self, id: u64) -> Option<RecipeOutput> {
+    self.runs.get(&id).cloned()
+  }
+
+  /// Registers a new run, forgetting the oldest once more than
+  /// [`RecipeRuns::MAX_RUNS`] are retained.
+  pub(crate) fn start(&mut self) -> u64 {
+    let id = self.next_id;
+
+    self.next_id += 1;
+
+    self.runs.insert(id, RecipeOutput::default());
+
+    if self.runs.len() > Self::MAX_RUNS
+      && let Some(oldest) = self.runs.keys().min().copied()
+    {
+      self.runs.remove(&oldest);
+    }
+
+    id
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, pretty_assertions::assert_eq};
+
+  #[test]
+  fn append_and_finish() {
+    let mut runs = RecipeRuns::default();
+
+    let id = runs.start();
+
+    assert_eq!(runs.append(id, "foo\n").offset, 0);
+    assert_eq!(runs.append(id, "bar\n").offset, 4);
+
+    assert_eq!(
+      runs.finish(id, Some(1)),
+      RecipeOutputChunk {
+        finished: true,
+        offset: 8,
+        run_id: id,
+        text: String::new(),
+      }
+    );
+
+    assert_eq!(
+      runs.get(id),
+      Some(RecipeOutput {
+        exit_code: Some(1),
+        finished: true,
+        offset: 0,
+        output: "foo\nbar\n".into(),
+      })
+    );
+  }
+
+  #[test]
+  fn oldest_runs_are_forgotten() {
+    let mut runs = RecipeRuns::default();
+
+    let first = runs.start();
+
+    for _ in 0..RecipeRuns::MAX_RUNS {
+      runs.start();
+    }
+
+    assert_eq!(runs.get(first), None);
+    assert!(runs.get(first + 1).is_some());
+  }
+
+  #[test]
+  fn scrollback_drops_whole_lines() {
+    let mut runs = RecipeRuns::default();
+
+    let id = runs.start();
+
+    let line = format!("{}\n", "x".repeat(1023));
+
+    for _ in 0..1024 {
+      runs.append(id, &line);
+    }
+
+    runs.append(id, "tail\n");
+
+    let run = runs.get(id).unwrap();
+
+    assert_eq!(run.offset, 1024);
+    assert_eq!(run.output.len(), 1023 * 1024 + 5);
+    assert!(run.output.starts_with('x'));
+    assert!(run.output.ends_with("tail\n"));
+  }
+
+  #[test]
+  fn scrollback_drops_whole_lines_with_multibyte_characters() {
+    let mut runs = RecipeRuns::default();
+
+    let id = runs.start();
+
+    let line = format!("{}x\n", "é".repeat(511));
+
+    for _ in 0..1024 {
+      runs.append(id, &line);
+    }
+
+    runs.append(id, "tail\n");
+
+    let run = runs.get(id).unwrap();
+
+    assert_eq!(run.offset, 1024);
+    assert_eq!(run.output.len(), 1023 * 1024 + 5);
+    assert!(run.output.starts_with('é'));
+    assert!(run.output.ends_with("tail\n"));
+  }
+}
@@ -1,15 +1,85 @@
-use super::*;
+use {super::*, std::fmt::Write};
 
 #[derive(Debug)]
 pub(crate) struct Resolver<'a> {
+  evaluated: Option<(String, String)>,
   view: ProjectView<'a>,
 }
 
 impl<'a> Resolver<'a> {
+  /// How long hover waits for `just --evaluate` before giving up.
+  const EVALUATE_TIMEOUT: Duration = Duration::from_secs(2);
+
+  /// The value of variable `name` as reported by `just --evaluate`, shown
+  /// in hover when it can't be determined statically.
+  #[must_use]
+  pub(crate) fn evaluated(self, name: String, value: String) -> Self {
+    Self {
+      evaluated: Some((name, value)),
+      ..self
+    }
+  }
+
+  /// The root justfile and name of the variable at `identifier`, if its
+  /// value can't be determined statically and has to be found with
+  /// [`Resolver::just_evaluate`].
+  #[must_use]
+  pub(crate) fn evaluation(
+    &self,
+    identifier: &Node,
+  ) -> Option<(PathBuf, String)> {
+    let Symbol::Variable(variable) = self.resolve_symbol(identifier)? else {
+      return None;
+    };
+
+    let name = variable.value.name.value;
+
+    if Evaluator::new(&self.view)
+      .evaluate_variable(&name)
+      .is_some()
+    {
+      return None;
+    }
+
+    Some((self.view.root().uri.to_file_path().ok()?, name))
+  }
+
+  /// Run `just --evaluate name` against `justfile`, killing it if it takes
+  /// longer than [`Resolver::EVALUATE_TIMEOUT`], since backticks in the
+  /// justfile can run arbitrary commands.
+  pub(crate) async fn just_evaluate(
+    justfile: &Path,
+    name: &str,
+  ) -> Option<String> {
+    let output = tokio::process::Command::new("just")
+      .arg("--justfile")
+      .arg(justfile)
+      .arg("--working-directory")
+      .arg(justfile.parent()?)
+      .arg("--evaluate")
+      .arg(name)
+      .stdin(process::Stdio::null())
+      .kill_on_drop(true)
+      .output();
+
+    let output = tokio::time::timeout(Self::EVALUATE_TIMEOUT, output)
+      .await
+      .ok()?
+      .ok()?;
+
+    output
+      .status
+      .success()
+      .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+  }
+
   /// Creates a new `Resolver` bound to the given `ProjectView`.
   #[must_use]
   pub(crate) fn new(view: impl Into<ProjectView<'a>>) -> Self {
-    Self { view: view.into() }
+    Self {
+      evaluated: None,
+      view: view.into(),
+    }
   }
 
   /// Returns the definition site of the symbol that `identifier` refers
@@ -43,6 +113,10 @@ impl<'a> Resolver<'a> {
   /// Builds hover content for the symbol at `identifier`. User-defined
   /// symbols show their source text; builtins show their Markdown
   /// documentation from the static [`BUILTINS`] table.
+  ///
+  /// Variables whose value isn't spelled out by a plain string literal also
+  /// show their evaluated value, and settings show their effective value
+  /// across imports.
   #[must_use]
   pub(crate) fn resolve_identifier_hover(
     &self,
@@ -51,6 +125,20 @@ impl<'a> Resolver<'a> {
     Some(lsp::Hover {
       contents: lsp::HoverContents::Markup(
         match self.resolve_symbol(identifier)? {
+          Symbol::Builtin(
+            builtin @ Builtin::Setting {
+              name,
+              kind: default,
+              ..
+            },
+          ) => lsp::MarkupContent {
+            value: format!(
+              "{}\n{}\n",
+              builtin.description().value,
+              self.setting_value(name, default)
+            ),
+            ..builtin.description()
+          },
           Symbol::Builtin(builtin) => builtin.description(),
           Symbol::Function(function) => lsp::MarkupContent {
             kind: lsp::MarkupKind::PlainText,
@@ -68,10 +156,26 @@ impl<'a> Resolver<'a> {
             kind: lsp::MarkupKind::PlainText,
             value: recipe.value.content,
           },
-          Symbol::Variable(variable) => lsp::MarkupContent {
-            kind: lsp::MarkupKind::PlainText,
-            value: variable.value.content,
-          },
+          Symbol::Variable(variable) => {
+            let mut value = variable.value.content.clone();
+
+            if let Some(evaluated) = self.variable_value(&variable) {
+              if evaluated.contains('\n') {
+                write!(value, "\n\nEvaluates to:\n```\n{evaluated}\n```").ok();
+              } else {
+                write!(value, "\n\nEvaluates to `{evaluated}`").ok();
+              }
+            }
+
+            if let Some(value_type) = self.variable_type(&variable) {
+              write!(value, "\n\nType: {value_type}").ok();
+            }
+
+            lsp::MarkupContent {
+              kind: lsp::MarkupKind::PlainText,
+              value,
+            }
+          }
         },
       ),
       range: Some(identifier.get_range(self.view.document())),
@@ -225,7 +329,7 @@ impl<'a> Resolver<'a> {
   /// assignment, or a parameter name in a recipe header) are looked up
   /// through the document so that callers receive a fully-populated
   /// [`Symbol`] rather than a raw range.
-  fn resolve_symbol(&self, identifier: &Node) -> Option<Symbol> {
+  pub(crate) fn resolve_symbol(&self, identifier: &Node) -> Option<Symbol> {
     let name = self.view.document().get_node_text(identifier);
 
     let parent_kind = identifier.parent()?.kind();
@@ -353,6 +457,100 @@ impl<'a> Resolver<'a> {
         .map(Symbol::Builtin),
     }
   }
+
+  /// Describe the effective value of the setting `name`, taking the
+  /// declaration that wins across imports or falling back to the default
+  /// from the [`SettingKind`] catalog.
+  fn setting_value(&self, name: &str, default: &SettingKind) -> String {
+    let Some(Located {
+      uri,
+      value: setting,
+    }) = self.view.find_setting(name)
+    else {
+      return match default {
+        SettingKind::Boolean(value) => {
+          format!("Effective value: `{value}` (default)")
+        }
+        _ => "Effective value: unset (default)".to_string(),
+      };
+    };
+
+    let value = match &setting.kind {
+      SettingKind::Boolean(value) => value.to_string(),
+      SettingKind::String => self
+        .view
+        .find_document(&uri)
+        .and_then(|document| {
+          let value = document.node_at_position(setting.value.range.start)?;
+
+          let node = value.get_parent("setting")?;
+
+          let mut cursor = node.walk();
+
+          let value = node
+            .children_by_field_name("right", &mut cursor)
+            .find(|child| matches!(child.kind(), "expression" | "string"))?;
+
+          Evaluator::new(&self.view).evaluate(document, value)
+        })
+        .unwrap_or_else(|| setting.value.value.clone()),
+      SettingKind::Array | SettingKind::StringOrArray => {
+        setting.value.value.clone()
+      }
+    };
+
+    if uri == self.view.document().uri {
+      format!("Effective value: `{value}`")
+    } else {
+      format!(
+        "Effective value: `{value}` (set in `{}`)",
+        uri
+          .path_segments()
+          .and_then(|mut segments| segments.next_back())
+          .unwrap_or_else(|| uri.as_str())
+      )
+    }
+  }
+
+  /// The inferred type of `variable`, if `set lists` is enabled and the type
+  /// is known.
+  fn variable_type(&self, variable: &Located<Variable>) -> Option<ValueType> {
+    self
+      .view
+      .find_setting("lists")
+      .is_some_and(|setting| {
+        matches!(setting.value.kind, SettingKind::Boolean(true))
+      })
+      .then(|| {
+        TypeChecker::new(&self.view).infer_variable(&variable.value.name.value)
+      })
+      .flatten()
+  }
+
+  /// The value of `variable`, if it's known and differs from its source
+  /// text.
+  fn variable_value(&self, variable: &Located<Variable>) -> Option<String> {
+    let name = &variable.value.name.value;
+
+    let value =
+      Evaluator::new(&self.view)
+        .evaluate_variable(name)
+        .or_else(|| {
+          self
+            .evaluated
+            .as_ref()
+            .filter(|(evaluated, _)| evaluated == name)
+            .map(|(_, value)| value.clone())
+        })?;
+
+    let literal = variable
+      .value
+      .content
+      .split_once(":=")
+      .and_then(|(_, right)| right.trim().literal());
+
+    (literal.as_ref() != Some(&value)).then_some(value)
+  }
 }
 
 #[cfg(test)]
@@ -635,6 +833,8 @@ mod tests {
             echo $a
             echo $b
           ```
+
+          Effective value: `true`
           "
         }
         .to_string(),
@@ -820,6 +1020,79 @@ mod tests {
     );
   }
 
+  #[test]
+  fn resolve_evaluated_variable_hover() {
+    let document = Document::from(indoc! {
+      "
+      foo:
+        echo {{ bin }}
+
+      dist := 'target'
+      bin := dist / 'bin' + '.exe'
+      "
+    });
+
+    let hover = Resolver::new(&document)
+      .resolve_identifier_hover(
+        &document
+          .tree
+          .as_ref()
+          .unwrap()
+          .root_node()
+          .find("value > identifier")
+          .unwrap(),
+      )
+      .unwrap();
+
+    assert_eq!(
+      hover.contents,
+      lsp::HoverContents::Markup(lsp::MarkupContent {
+        kind: lsp::MarkupKind::PlainText,
+        value: "bin := dist / 'bin' + '.exe'\n\nEvaluates to `target/bin.exe`"
+          .to_string(),
+      })
+    );
+  }
+
+  #[test]
+  fn resolve_just_evaluated_variable_hover() {
+    let document = Document::from(indoc! {
+      "
+      foo:
+        echo {{ os }} {{ dist }}
+
+      dist := 'target'
+      os := `uname`
+      "
+    });
+
+    let root = document.tree.as_ref().unwrap().root_node();
+
+    let identifiers = root.find_all("value > identifier");
+
+    let resolver = Resolver::new(&document);
+
+    assert_eq!(resolver.evaluation(&identifiers[1]), None);
+
+    let (justfile, name) = resolver.evaluation(&identifiers[0]).unwrap();
+
+    assert_eq!(justfile, document.uri.to_file_path().unwrap());
+    assert_eq!(name, "os");
+
+    let hover = resolver
+      .evaluated(name, "Linux".into())
+      .resolve_identifier_hover(&identifiers[0])
+      .unwrap();
+
+    assert_eq!(
+      hover.contents,
+      lsp::HoverContents::Markup(lsp::MarkupContent {
+        kind: lsp::MarkupKind::PlainText,
+        value: "os := `uname`\n\nEvaluates to `Linux`".to_string(),
+      })
+    );
+  }
+
   #[test]
   fn resolve_export_parameter_hover() {
     let document = Document::from(indoc! {
@@ -926,6 +1199,48 @@ mod tests {
     assert_eq!(hover, None);
   }
 
+  #[test]
+  fn resolve_multiline_evaluated_variable_hover() {
+    let document = Document::from(indoc! {
+      r#"
+      foo:
+        echo {{ greeting }}
+
+      greeting := 'say "hi"' + "\nbye"
+      "#
+    });
+
+    let hover = Resolver::new(&document)
+      .resolve_identifier_hover(
+        &document
+          .tree
+          .as_ref()
+          .unwrap()
+          .root_node()
+          .find("value > identifier")
+          .unwrap(),
+      )
+      .unwrap();
+
+    assert_eq!(
+      hover.contents,
+      lsp::HoverContents::Markup(lsp::MarkupContent {
+        kind: lsp::MarkupKind::PlainText,
+        value: indoc! {
+          r#"
+          greeting := 'say "hi"' + "\nbye"
+
+          Evaluates to:
+          ```
+          say "hi"
+          bye
+          ```"#
+        }
+        .to_string(),
+      })
+    );
+  }
+
   #[test]
   fn resolve_parameter_definition() {
     let document = Document::from(indoc! {
@@ -1272,6 +1587,38 @@ mod tests {
     );
   }
 
+  #[test]
+  fn resolve_unknown_variable_value_hover() {
+    let document = Document::from(indoc! {
+      "
+      version := `cat VERSION`
+
+      foo:
+        echo {{ version }}
+      "
+    });
+
+    let hover = Resolver::new(&document)
+      .resolve_identifier_hover(
+        &document
+          .tree
+          .as_ref()
+          .unwrap()
+          .root_node()
+          .find("value > identifier")
+          .unwrap(),
+      )
+      .unwrap();
+
+    assert_eq!(
+      hover.contents,
+      lsp::HoverContents::Markup(lsp::MarkupContent {
+        kind: lsp::MarkupKind::PlainText,
+        value: "version := `cat VERSION`".to_string(),
+      })
+    );
+  }
+
   #[test]
   fn resolve_self_definition() {
     let document = Document::from(indoc! {
@@ -1302,6 +1649,49 @@ mod tests {
     );
   }
 
+  #[test]
+  fn resolve_setting_default_value_hover() {
+    let document = Document::from(indoc! {
+      "
+      set shell := ['bash', '-c']
+      "
+    });
+
+    let resolver = Resolver::new(&document);
+
+    assert_eq!(
+      resolver.setting_value("quiet", &SettingKind::Boolean(false)),
+      "Effective value: `false` (default)"
+    );
+
+    assert_eq!(
+      resolver.setting_value("shell", &SettingKind::StringOrArray),
+      "Effective value: `['bash', '-c']`"
+    );
+
+    assert_eq!(
+      resolver.setting_value("tempdir", &SettingKind::String),
+      "Effective value: unset (default)"
+    );
+  }
+
+  #[test]
+  fn resolve_setting_evaluated_value_hover() {
+    let document = Document::from(indoc! {
+      "
+      dir := 'build'
+
+      set working-directory := dir / 'out'
+      "
+    });
+
+    assert_eq!(
+      Resolver::new(&document)
+        .setting_value("working-directory", &SettingKind::String),
+      "Effective value: `build/out`"
+    );
+  }
+
   #[test]
   fn resolve_shadowed_parameter_default_definition() {
     let document = Document::from(indoc! {
@@ -1892,6 +2282,40 @@ mod tests {
     );
   }
 
+  #[test]
+  fn resolve_variable_type_hover() {
+    let document = Document::from(indoc! {
+      "
+      set lists
+
+      foo:
+        echo {{ files }}
+
+      files := split('a b')
+      "
+    });
+
+    let hover = Resolver::new(&document)
+      .resolve_identifier_hover(
+        &document
+          .tree
+          .as_ref()
+          .unwrap()
+          .root_node()
+          .find("value > identifier")
+          .unwrap(),
+      )
+      .unwrap();
+
+    assert_eq!(
+      hover.contents,
+      lsp::HoverContents::Markup(lsp::MarkupContent {
+        kind: lsp::MarkupKind::PlainText,
+        value: "files := split('a b')\n\nType: list".to_string(),
+      })
+    );
+  }
+
   #[test]
   fn resolve_variadic_parameter_hover() {
     let document = Document::from(indoc! {
@@ -6,6 +6,7 @@ macro_rules! define_rule {
     $name:ident {
       id: $id:literal,
       message: $message:literal,
+      $(enabled($enabled_config:ident) $enabled_body:block,)?
       run($context:ident) $body:block
       $(, quickfixes($quickfix_context:ident) $quickfix_body:block)?
       $(,)?
@@ -15,6 +16,12 @@ macro_rules! define_rule {
     struct $name;
 
     impl Rule for $name {
+      $(
+        fn enabled(&self, $enabled_config: &RuleConfig) -> bool {
+          $enabled_body
+        }
+      )?
+
       fn id(&self) -> &'static str {
         $id
       }
@@ -44,6 +51,7 @@ macro_rules! define_rule {
 inventory::collect!(&'static dyn Rule);
 
 mod alias_recipe_conflict;
+mod ambiguous_modules;
 mod arg_attribute;
 mod attribute_argument_expressions;
 mod attribute_arguments;
@@ -61,6 +69,7 @@ mod duplicate_alias;
 mod duplicate_attribute;
 mod duplicate_dependencies;
 mod duplicate_function;
+mod duplicate_modules;
 mod duplicate_recipes;
 mod duplicate_setting;
 mod duplicate_unexports;
@@ -70,20 +79,29 @@ mod export_unexport_conflict;
 mod extension_without_script;
 mod function_arguments;
 mod function_parameters;
+mod import_collisions;
+mod import_cycles;
 mod inconsistent_indentation;
+mod invalid_builtin_arguments;
 mod invalid_import_path;
+mod invalid_regex;
 mod invalid_setting_kind;
 mod invalid_setting_value;
 mod list_features;
 mod mapped_dependencies;
 mod missing_dependencies;
+mod missing_modules;
 mod missing_recipe_for_alias;
 mod mixed_indentation;
+mod module_recipe_conflict;
 mod parallel_dependencies;
 mod recipe_dependency_cycles;
 mod recipe_parameters;
 mod script_shell_conflict;
+mod shell_injection;
+mod shell_syntax;
 mod syntax;
+mod type_mismatch;
 mod undefined_identifiers;
 mod unknown_attribute;
 mod unknown_function;
@@ -0,0 +1,53 @@
+use super::*;
+
+define_rule! {
+  /// Reports `mod` statements with more than one candidate source file, such
+  /// as both `foo.just` and `foo/mod.just`, which `just` refuses to load.
+  AmbiguousModuleRule {
+    id: "ambiguous-modules",
+    message: "ambiguous module",
+    run(context) {
+      let document = context.document();
+
+      let Some(base_dir) = document
+        .uri
+        .to_file_path()
+        .ok()
+        .and_then(|path| path.parent().map(Path::to_path_buf))
+      else {
+        return Vec::new();
+      };
+
+      let mut diagnostics = Vec::new();
+
+      for module in document.modules() {
+        let candidates = module.candidates(&document.uri);
+
+        if candidates.len() < 2 {
+          continue;
+        }
+
+        let candidates = candidates
+          .iter()
+          .map(|candidate| {
+            format!(
+              "`{}`",
+              candidate.strip_prefix(&base_dir).unwrap_or(candidate).display()
+            )
+          })
+          .collect::<Vec<_>>();
+
+        diagnostics.push(Diagnostic::error(
+          format!(
+            "Found multiple source files for module `{}`: {}",
+            module.name.value,
+            candidates.join(", ")
+          ),
+          module.name.range,
+        ));
+      }
+
+      diagnostics
+    }
+  }
+}
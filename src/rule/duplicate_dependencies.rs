@@ -21,47 +21,72 @@ define_rule! {
     id: "duplicate-dependencies",
     message: "duplicate dependencies",
     run(context) {
-      let mut diagnostics = Vec::new();
+      Self::duplicates(context)
+        .into_iter()
+        .map(|(recipe, dependency)| {
+          let message = if dependency.arguments.is_empty() {
+            format!(
+              "Recipe `{}` lists dependency `{}` more than once; just only runs it once, so it's redundant",
+              recipe.name.value,
+              dependency.name.value
+            )
+          } else {
+            format!(
+              "Recipe `{}` lists dependency `{}` with the same arguments more than once; just only runs it once, so it's redundant",
+              recipe.name.value,
+              dependency.name.value
+            )
+          };
+
+          Diagnostic::warning(message, dependency.range)
+        })
+        .collect()
+    },
+    quickfixes(context) {
+      Self::duplicates(context)
+        .into_iter()
+        .map(|(_, dependency)| {
+          Quickfix::token_removal(
+            context.document(),
+            dependency.range,
+            format!("Remove duplicate dependency `{}`", dependency.name.value),
+          )
+        })
+        .collect()
+    }
+  }
+}
 
-      for recipe in context.recipes() {
-        let mut seen: HashSet<DependencyKey> = HashSet::new();
+impl DuplicateDependenciesRule {
+  fn duplicates<'a>(
+    context: &'a RuleContext<'_>,
+  ) -> Vec<(&'a Recipe, &'a Dependency)> {
+    let mut duplicates = Vec::new();
 
-        for dependency in &recipe.dependencies {
-          let key = DependencyKey {
-            name: dependency.name.clone(),
-            mapped: dependency.mapped.is_some(),
-            phase: dependency.phase,
-            arguments: dependency
-              .arguments
-              .iter()
-              .map(|argument| DependencyArgumentKey {
-                starred: argument.starred.is_some(),
-                value: argument.value.clone(),
-              })
-              .collect(),
-          };
+    for recipe in context.recipes() {
+      let mut seen: HashSet<DependencyKey> = HashSet::new();
 
-          if !seen.insert(key) {
-            let message = if dependency.arguments.is_empty() {
-              format!(
-                "Recipe `{}` lists dependency `{}` more than once; just only runs it once, so it's redundant",
-                recipe.name.value,
-                dependency.name
-              )
-            } else {
-              format!(
-                "Recipe `{}` lists dependency `{}` with the same arguments more than once; just only runs it once, so it's redundant",
-                recipe.name.value,
-                dependency.name
-              )
-            };
+      for dependency in &recipe.dependencies {
+        let key = DependencyKey {
+          name: dependency.name.value.clone(),
+          mapped: dependency.mapped.is_some(),
+          phase: dependency.phase,
+          arguments: dependency
+            .arguments
+            .iter()
+            .map(|argument| DependencyArgumentKey {
+              starred: argument.starred.is_some(),
+              value: argument.value.clone(),
+            })
+            .collect(),
+        };
 
-            diagnostics.push(Diagnostic::warning(message, dependency.range));
-          }
+        if !seen.insert(key) {
+          duplicates.push((recipe, dependency));
         }
       }
-
-      diagnostics
     }
+
+    duplicates
   }
 }
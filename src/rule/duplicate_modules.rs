@@ -0,0 +1,25 @@
+use super::*;
+
+define_rule! {
+  /// Flags `mod` statements that reuse the name of an earlier module.
+  DuplicateModuleRule {
+    id: "duplicate-modules",
+    message: "duplicate module",
+    run(context) {
+      let mut diagnostics = Vec::new();
+
+      let mut seen = HashSet::new();
+
+      for module in context.document().modules() {
+        if !seen.insert(module.name.value.clone()) {
+          diagnostics.push(Diagnostic::error(
+            format!("Duplicate module `{}`", module.name.value),
+            module.range,
+          ));
+        }
+      }
+
+      diagnostics
+    }
+  }
+}
@@ -17,7 +17,7 @@ define_rule! {
 
       let mut groups = HashMap::<String, GroupSet>::new();
 
-      for recipe in context.recipes() {
+      for recipe in context.document().recipes() {
         let current = GroupSet::from_attributes(&recipe.attributes);
 
         let previous = groups
@@ -15,7 +15,7 @@ define_rule! {
 
       let (mut diagnostics, mut groups) = (Vec::new(), HashMap::<String, GroupSet>::new());
 
-      for variable in context.variables() {
+      for variable in context.document().variables() {
         let current = GroupSet::from_attributes(&variable.attributes);
 
         let previous = groups
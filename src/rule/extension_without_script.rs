@@ -6,27 +6,47 @@ define_rule! {
     id: "extension-without-script",
     message: "extension without script",
     run(context) {
-      let mut diagnostics = Vec::new();
-
-      for recipe in context.recipes() {
-        let Some(extension_attribute) = recipe.find_attribute("extension") else {
-          continue;
-        };
-
-        if recipe.has_attribute("script") || recipe.shebang.is_some() {
-          continue;
-        }
-
-        diagnostics.push(Diagnostic::error(
-          format!(
-            "Recipe `{}` uses `[extension]` without `[script]` or a shebang",
-            recipe.name.value
-          ),
-          extension_attribute.range,
-        ));
-      }
-
-      diagnostics
+      Self::attributes(context)
+        .into_iter()
+        .map(|(recipe, extension_attribute)| {
+          Diagnostic::error(
+            format!(
+              "Recipe `{}` uses `[extension]` without `[script]` or a shebang",
+              recipe.name.value
+            ),
+            extension_attribute.range,
+          )
+        })
+        .collect()
+    },
+    quickfixes(context) {
+      Self::attributes(context)
+        .into_iter()
+        .map(|(_, extension_attribute)| Quickfix {
+          range: extension_attribute.range,
+          ..Quickfix::insertion(
+            lsp::Position::new(extension_attribute.range.start.line, 0),
+            "[script]\n",
+            "Add `[script]`",
+          )
+          .safe(false)
+        })
+        .collect()
     }
   }
 }
+
+impl ExtensionWithoutScriptRule {
+  fn attributes<'a>(
+    context: &'a RuleContext<'_>,
+  ) -> Vec<(&'a Recipe, &'a Attribute)> {
+    context
+      .recipes()
+      .iter()
+      .filter(|recipe| {
+        !recipe.has_attribute("script") && recipe.shebang.is_none()
+      })
+      .filter_map(|recipe| Some((recipe, recipe.find_attribute("extension")?)))
+      .collect()
+  }
+}
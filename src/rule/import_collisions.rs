@@ -0,0 +1,198 @@
+use super::*;
+
+struct Definition<'a> {
+  document: &'a Document,
+  groups: GroupSet,
+  name: TextNode,
+}
+
+define_rule! {
+  /// Reports recipes and variables that are defined in more than one file of
+  /// the import graph, pointing at every conflicting definition.
+  ImportCollisionRule {
+    id: "import-collisions",
+    message: "import collision",
+    run(context) {
+      let mut diagnostics = Vec::new();
+
+      if !context.setting_enabled("allow-duplicate-recipes") {
+        diagnostics.extend(Self::collisions(context, "Recipe", |document| {
+          document
+            .recipes()
+            .into_iter()
+            .map(|recipe| (recipe.name, recipe.attributes))
+            .collect()
+        }));
+      }
+
+      if !context.setting_enabled("allow-duplicate-variables") {
+        diagnostics.extend(Self::collisions(context, "Variable", |document| {
+          document
+            .variables()
+            .into_iter()
+            .map(|variable| (variable.name, variable.attributes))
+            .collect()
+        }));
+      }
+
+      diagnostics
+    }
+  }
+}
+
+impl ImportCollisionRule {
+  fn collisions(
+    context: &RuleContext<'_>,
+    kind: &str,
+    definitions: impl Fn(&Document) -> Vec<(TextNode, Vec<Attribute>)>,
+  ) -> Vec<Diagnostic> {
+    let document = context.document();
+
+    let (mut names, mut groups) = (
+      Vec::<String>::new(),
+      HashMap::<String, Vec<Definition>>::new(),
+    );
+
+    for source in
+      once(document).chain(context.imported_documents().iter().copied())
+    {
+      for (name, attributes) in definitions(source) {
+        let entry = groups.entry(name.value.clone()).or_insert_with(|| {
+          names.push(name.value.clone());
+          Vec::new()
+        });
+
+        entry.push(Definition {
+          document: source,
+          groups: GroupSet::from_attributes(&attributes),
+          name,
+        });
+      }
+    }
+
+    let mut diagnostics = Vec::new();
+
+    for name in names {
+      let definitions = &groups[&name];
+
+      let (local, imported) =
+        definitions.iter().partition::<Vec<_>, _>(|definition| {
+          definition.document.uri == document.uri
+        });
+
+      if local.is_empty() {
+        diagnostics
+          .extend(Self::imported_collision(context, kind, &name, &imported));
+
+        continue;
+      }
+
+      for definition in local {
+        let conflicts = imported
+          .iter()
+          .filter(|other| definition.groups.conflicts_with(&other.groups))
+          .collect::<Vec<_>>();
+
+        if conflicts.is_empty() {
+          continue;
+        }
+
+        let mut files = Vec::new();
+
+        for conflict in &conflicts {
+          let file = format!("`{}`", conflict.document.file_name());
+
+          if !files.contains(&file) {
+            files.push(file);
+          }
+        }
+
+        diagnostics.push(conflicts.into_iter().fold(
+          Diagnostic::error(
+            format!("{kind} `{name}` is also defined in {}", files.join(", ")),
+            definition.name.range,
+          ),
+          |diagnostic, conflict| {
+            diagnostic.related(
+              conflict.document.uri.clone(),
+              conflict.name.range,
+              format!("`{name}` is defined here"),
+            )
+          },
+        ));
+      }
+    }
+
+    diagnostics
+  }
+
+  /// Report a collision between two imported files at the import through
+  /// which the later definition is reached.
+  fn imported_collision(
+    context: &RuleContext<'_>,
+    kind: &str,
+    name: &str,
+    definitions: &[&Definition],
+  ) -> Option<Diagnostic> {
+    let (first, second) =
+      definitions.iter().enumerate().find_map(|(index, first)| {
+        definitions[index + 1..]
+          .iter()
+          .find(|second| {
+            first.document.uri != second.document.uri
+              && first.groups.conflicts_with(&second.groups)
+          })
+          .map(|second| (*first, *second))
+      })?;
+
+    let (range, _) = context
+      .resolved_imports(&context.document().uri)
+      .into_iter()
+      .find(|(_, target)| {
+        Self::reaches(context, target, &second.document.uri)
+      })?;
+
+    Some([first, second].into_iter().fold(
+      Diagnostic::error(
+        format!(
+          "{kind} `{name}` is defined in both `{}` and `{}`",
+          first.document.file_name(),
+          second.document.file_name()
+        ),
+        range,
+      ),
+      |diagnostic, definition| {
+        diagnostic.related(
+          definition.document.uri.clone(),
+          definition.name.range,
+          format!("`{name}` is defined here"),
+        )
+      },
+    ))
+  }
+
+  /// Whether `uri` is `start` or reachable from it through imports.
+  fn reaches(
+    context: &RuleContext<'_>,
+    start: &Document,
+    uri: &lsp::Url,
+  ) -> bool {
+    let mut queue = VecDeque::from([start]);
+
+    let mut visited = HashSet::from([start.uri.clone()]);
+
+    while let Some(document) = queue.pop_front() {
+      if document.uri == *uri {
+        return true;
+      }
+
+      for (_, target) in context.resolved_imports(&document.uri) {
+        if visited.insert(target.uri.clone()) {
+          queue.push_back(target);
+        }
+      }
+    }
+
+    false
+  }
+}
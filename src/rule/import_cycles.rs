@@ -0,0 +1,148 @@
+use super::*;
+
+type ImportLink<'a> = (&'a Document, lsp::Range, &'a Document);
+
+define_rule! {
+  /// Reports the import cycles the project loader recorded that pass
+  /// through the document, at the document's import along the cycle, listing
+  /// every import of the cycle as related information.
+  ImportCycleRule {
+    id: "import-cycles",
+    message: "import cycle",
+    run(context) {
+      let Some(project) = context.project() else {
+        return Vec::new();
+      };
+
+      let document = context.document();
+
+      let mut sources = project.dependencies.keys().collect::<Vec<_>>();
+
+      sources.sort();
+
+      let mut diagnostics = Vec::new();
+
+      for source in sources {
+        for dependency in project.dependencies(source) {
+          let ProjectDependencyTarget::Cycle(target) = &dependency.target else {
+            continue;
+          };
+
+          let Some(links) =
+            Self::cycle(context, source, dependency.location, target)
+          else {
+            continue;
+          };
+
+          let Some(start) =
+            links.iter().position(|(source, _, _)| source.uri == document.uri)
+          else {
+            continue;
+          };
+
+          let links = links[start..]
+            .iter()
+            .chain(&links[..start])
+            .copied()
+            .collect::<Vec<_>>();
+
+          let names = once(document.file_name())
+            .chain(links.iter().map(|(_, _, target)| target.file_name()))
+            .map(|name| format!("`{name}`"))
+            .collect::<Vec<_>>();
+
+          diagnostics.push(links.iter().fold(
+            Diagnostic::error(
+              format!("Import cycle: {}", names.join(" -> ")),
+              links[0].1,
+            ),
+            |diagnostic, (source, range, target)| {
+              diagnostic.related(
+                source.uri.clone(),
+                *range,
+                format!(
+                  "`{}` imports `{}`",
+                  source.file_name(),
+                  target.file_name()
+                ),
+              )
+            },
+          ));
+        }
+      }
+
+      diagnostics
+    }
+  }
+}
+
+impl ImportCycleRule {
+  /// The shortest chain of resolved imports leading from `start` to `end`.
+  fn chain<'a>(
+    context: &RuleContext<'a>,
+    start: &'a Document,
+    end: &lsp::Url,
+  ) -> Option<Vec<ImportLink<'a>>> {
+    if start.uri == *end {
+      return Some(Vec::new());
+    }
+
+    let mut previous = HashMap::<lsp::Url, ImportLink<'a>>::new();
+
+    let mut queue = VecDeque::from([start]);
+
+    let mut visited = HashSet::from([start.uri.clone()]);
+
+    while let Some(source) = queue.pop_front() {
+      for (range, target) in context.resolved_imports(&source.uri) {
+        if target.uri == *end {
+          let mut chain = vec![(source, range, target)];
+
+          let mut current = source;
+
+          while current.uri != start.uri {
+            let link = previous.remove(&current.uri)?;
+            current = link.0;
+            chain.push(link);
+          }
+
+          chain.reverse();
+
+          return Some(chain);
+        }
+
+        if visited.insert(target.uri.clone()) {
+          previous.insert(target.uri.clone(), (source, range, target));
+          queue.push_back(target);
+        }
+      }
+    }
+
+    None
+  }
+
+  /// The imports making up the cycle closed by the import at `range` in
+  /// `source` of `target`, which was still being loaded: the chain of
+  /// resolved imports leading from `target` to `source`, followed by that
+  /// import.
+  fn cycle<'a>(
+    context: &RuleContext<'a>,
+    source: &lsp::Url,
+    range: lsp::Range,
+    target: &lsp::Url,
+  ) -> Option<Vec<ImportLink<'a>>> {
+    let document = |uri: &lsp::Url| {
+      once(context.document())
+        .chain(context.imported_documents().iter().copied())
+        .find(|document| document.uri == *uri)
+    };
+
+    let (source, target) = (document(source)?, document(target)?);
+
+    let mut chain = Self::chain(context, target, &source.uri)?;
+
+    chain.push((source, range, target));
+
+    Some(chain)
+  }
+}
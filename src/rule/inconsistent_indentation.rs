@@ -90,6 +90,14 @@ define_rule! {
         .filter(|recipe| recipe.shebang.is_none())
         .filter_map(Self::find_inconsistent_indentation)
         .collect()
+    },
+    quickfixes(context) {
+      context
+        .recipes()
+        .iter()
+        .filter(|recipe| recipe.shebang.is_none())
+        .filter_map(Self::normalize)
+        .collect()
     }
   }
 }
@@ -137,6 +145,54 @@ impl InconsistentIndentationRule {
     )
   }
 
+  /// Re-indent every line the parser would reject to match the first line,
+  /// leaving continuation lines and lines indented with a different kind of
+  /// whitespace alone.
+  ///
+  /// Lines indented further than the first lose that nesting, so this is
+  /// never applied without review.
+  fn normalize(recipe: &Recipe) -> Option<Quickfix> {
+    let diagnostic = Self::find_inconsistent_indentation(recipe)?;
+
+    let mut lines = Self::recipe_body_lines(&recipe.content);
+
+    let first = lines.next()?;
+
+    let mut previous_continues = first.continues;
+
+    let mut edits = Vec::new();
+
+    for line in lines {
+      if line.kind == first.kind
+        && line.indent != first.indent
+        && !previous_continues
+      {
+        let line_number = recipe.range.start.line + line.relative_line;
+
+        edits.push(lsp::TextEdit {
+          range: lsp::Range::at(
+            line_number,
+            0,
+            line_number,
+            u32::try_from(line.indent.chars().count()).unwrap_or(u32::MAX),
+          ),
+          new_text: first.indent.clone(),
+        });
+      }
+
+      previous_continues = line.continues;
+    }
+
+    Some(Quickfix {
+      create: false,
+      edits,
+      range: diagnostic.range,
+      safe: false,
+      title: format!("Normalize indentation of recipe `{}`", recipe.name.value),
+      uri: None,
+    })
+  }
+
   fn recipe_body_lines(content: &str) -> impl Iterator<Item = RecipeLine> + '_ {
     content
     .lines()
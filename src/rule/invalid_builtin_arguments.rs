@@ -0,0 +1,214 @@
+use super::*;
+
+/// A diagnostic about a literal, along with the spellings it could be
+/// replaced with.
+struct Finding {
+  diagnostic: Diagnostic,
+  literal: TextNode,
+  suggestions: Suggestions,
+}
+
+define_rule! {
+  /// Checks string literals passed to builtins that take a mini-language,
+  /// such as `datetime()` formats, `semver_matches()` requirements and
+  /// `style()` names, and literals compared against `os()`, `arch()` and
+  /// `os_family()`.
+  InvalidBuiltinArgumentsRule {
+    id: "invalid-builtin-arguments",
+    message: "invalid builtin argument",
+    run(context) {
+      Self::findings(context)
+        .into_iter()
+        .map(|finding| finding.diagnostic)
+        .collect()
+    },
+    quickfixes(context) {
+      Self::findings(context)
+        .into_iter()
+        .flat_map(|finding| {
+          finding
+            .suggestions
+            .quickfixes(&finding.literal, finding.diagnostic.range)
+        })
+        .collect()
+    }
+  }
+}
+
+impl InvalidBuiltinArgumentsRule {
+  /// Common names for platform values that `just` spells differently.
+  const ALIASES: [(&'static str, &'static str); 10] = [
+    ("amd64", "x86_64"),
+    ("arm64", "aarch64"),
+    ("darwin", "macos"),
+    ("i386", "x86"),
+    ("i686", "x86"),
+    ("mac", "macos"),
+    ("osx", "macos"),
+    ("win", "windows"),
+    ("win32", "windows"),
+    ("x64", "x86_64"),
+  ];
+
+  /// The builtin that `node` calls, if it's a call to a builtin function
+  /// that isn't shadowed by a user-defined function.
+  fn builtin(
+    context: &RuleContext<'_>,
+    node: Node,
+  ) -> Option<&'static Builtin<'static>> {
+    match node.kind() {
+      "function_call" => {
+        let name = context
+          .document()
+          .get_node_text(&node.child_by_field_name("name")?);
+
+        if context.user_function_names().contains(&name) {
+          return None;
+        }
+
+        context.builtin_function(&name)
+      }
+      "expression" | "value" if node.named_child_count() == 1 => {
+        Self::builtin(context, node.named_child(0)?)
+      }
+      _ => None,
+    }
+  }
+
+  fn call_findings(context: &RuleContext<'_>, call: Node) -> Vec<Finding> {
+    let document = context.document();
+
+    let (Some(builtin), Some(arguments)) = (
+      Self::builtin(context, call),
+      call.child_by_field_name("arguments"),
+    ) else {
+      return Vec::new();
+    };
+
+    let Builtin::Function { name, .. } = builtin else {
+      return Vec::new();
+    };
+
+    let mut findings = Vec::new();
+
+    let mut cursor = arguments.walk();
+
+    for (index, argument) in arguments.named_children(&mut cursor).enumerate() {
+      let Some(format) = builtin.argument_format(index) else {
+        continue;
+      };
+
+      let Some(literal) = StringLiteral::new(document, argument) else {
+        continue;
+      };
+
+      let Some((span, message)) = format.check(&literal.text) else {
+        continue;
+      };
+
+      let suggestions = if format == ArgumentFormat::Style {
+        ArgumentFormat::STYLES
+          .iter()
+          .find(|style| {
+            !literal.text.is_empty() && style.starts_with(&literal.text)
+          })
+          .map_or_else(
+            || Suggestions::new(&literal.text, ArgumentFormat::STYLES),
+            |style| Suggestions::from(*style),
+          )
+      } else {
+        Suggestions::default()
+      };
+
+      findings.push(Finding {
+        diagnostic: Diagnostic::error(
+          suggestions.message(format!("{message} in `{name}()`")),
+          literal.range(document, span.start, span.end),
+        ),
+        literal: literal.text_node(document),
+        suggestions,
+      });
+    }
+
+    findings
+  }
+
+  fn comparison_finding(
+    context: &RuleContext<'_>,
+    expression: Node,
+  ) -> Option<Finding> {
+    let document = context.document();
+
+    let mut cursor = expression.walk();
+
+    let children = expression.children(&mut cursor).collect::<Vec<_>>();
+
+    let [lhs, operator, rhs] = children.as_slice() else {
+      return None;
+    };
+
+    if !matches!(operator.kind(), "==" | "!=") {
+      return None;
+    }
+
+    let (builtin, literal) =
+      [(lhs, rhs), (rhs, lhs)]
+        .into_iter()
+        .find_map(|(call, literal)| {
+          Some((
+            Self::builtin(context, *call)?,
+            StringLiteral::new(document, *literal)?,
+          ))
+        })?;
+
+    let (Builtin::Function { name, .. }, Some(values)) =
+      (builtin, builtin.values())
+    else {
+      return None;
+    };
+
+    if values.contains(&literal.text.as_str()) {
+      return None;
+    }
+
+    let suggestions = Self::ALIASES
+      .iter()
+      .find(|(alias, value)| {
+        alias.eq_ignore_ascii_case(&literal.text) && values.contains(value)
+      })
+      .map_or_else(
+        || Suggestions::new(&literal.text, values.iter().copied()),
+        |(_, value)| Suggestions::from(*value),
+      );
+
+    Some(Finding {
+      diagnostic: Diagnostic::warning(
+        suggestions
+          .message(format!("Unknown `{name}()` value `{}`", literal.text)),
+        literal.range(document, 0, literal.text.len()),
+      ),
+      literal: literal.text_node(document),
+      suggestions,
+    })
+  }
+
+  fn findings(context: &RuleContext<'_>) -> Vec<Finding> {
+    let Some(tree) = context.tree() else {
+      return Vec::new();
+    };
+
+    let root = tree.root_node();
+
+    let mut findings = Vec::new();
+
+    for call in root.find_all("function_call") {
+      findings.extend(Self::call_findings(context, call));
+    }
+
+    for expression in root.find_all("expression") {
+      findings.extend(Self::comparison_finding(context, expression));
+    }
+
+    findings
+  }
+}
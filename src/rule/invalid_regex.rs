@@ -0,0 +1,227 @@
+use {
+  super::*,
+  regex_syntax::{
+    ParserBuilder,
+    hir::{Hir, HirKind, Look},
+  },
+};
+
+/// A regex written as a string literal.
+struct Pattern {
+  anchored: bool,
+  literal: StringLiteral,
+}
+
+impl Pattern {
+  fn new(document: &Document, node: Node, anchored: bool) -> Option<Self> {
+    Some(Self {
+      anchored,
+      literal: StringLiteral::new(document, node)?,
+    })
+  }
+}
+
+define_rule! {
+  /// Compiles literal regexes used with `=~` and `!~`, `replace_regex`, and
+  /// `[arg(pattern=...)]`, reporting syntax errors at their offset inside the
+  /// string and warning about patterns that can never match.
+  InvalidRegexRule {
+    id: "invalid-regex",
+    message: "invalid regex",
+    run(context) {
+      let Some(tree) = context.tree() else {
+        return Vec::new();
+      };
+
+      let document = context.document();
+
+      let root = tree.root_node();
+
+      let mut patterns = Vec::new();
+
+      for expression in root.find_all("expression") {
+        let mut cursor = expression.walk();
+
+        let children = expression.children(&mut cursor).collect::<Vec<_>>();
+
+        if let [_, operator, rhs] = children.as_slice()
+          && matches!(operator.kind(), "=~" | "!~")
+        {
+          patterns.extend(Pattern::new(document, *rhs, false));
+        }
+      }
+
+      for call in root.find_all("function_call") {
+        let Some(name) = call.child_by_field_name("name") else {
+          continue;
+        };
+
+        let name = document.get_node_text(&name);
+
+        if name != "replace_regex"
+          || context.user_function_names().contains(&name)
+        {
+          continue;
+        }
+
+        let Some(arguments) = call.child_by_field_name("arguments") else {
+          continue;
+        };
+
+        patterns.extend(
+          arguments
+            .named_child(1)
+            .and_then(|argument| Pattern::new(document, argument, false)),
+        );
+      }
+
+      for parameter in root.find_all("attribute_named_param") {
+        if !Self::arg_pattern(document, parameter) {
+          continue;
+        }
+
+        patterns.extend(
+          parameter
+            .child_by_field_name("value")
+            .and_then(|value| Pattern::new(document, value, true)),
+        );
+      }
+
+      patterns
+        .iter()
+        .filter_map(|pattern| Self::check(document, pattern))
+        .collect()
+    }
+  }
+}
+
+impl InvalidRegexRule {
+  /// Whether `parameter` is the `pattern` keyword of an `[arg]` attribute.
+  fn arg_pattern(document: &Document, parameter: Node) -> bool {
+    if parameter
+      .child_by_field_name("name")
+      .is_none_or(|name| document.get_node_text(&name) != "pattern")
+    {
+      return false;
+    }
+
+    let mut sibling = parameter.prev_sibling();
+
+    while let Some(node) = sibling {
+      if node.kind() == "identifier" {
+        return document.get_node_text(&node) == "arg";
+      }
+
+      sibling = node.prev_sibling();
+    }
+
+    false
+  }
+
+  fn check(document: &Document, pattern: &Pattern) -> Option<Diagnostic> {
+    let hir = match ParserBuilder::new().build().parse(&pattern.literal.text) {
+      Ok(hir) => hir,
+      Err(regex_syntax::Error::Parse(error)) => {
+        return Some(Self::invalid(
+          document,
+          pattern,
+          error.kind(),
+          error.span(),
+        ));
+      }
+      Err(regex_syntax::Error::Translate(error)) => {
+        return Some(Self::invalid(
+          document,
+          pattern,
+          error.kind(),
+          error.span(),
+        ));
+      }
+      Err(error) => {
+        return Some(Diagnostic::error(
+          format!("Invalid regex: {error}"),
+          pattern
+            .literal
+            .range(document, 0, pattern.literal.text.len()),
+        ));
+      }
+    };
+
+    let hir = if pattern.anchored {
+      Hir::concat(vec![Hir::look(Look::Start), hir, Hir::look(Look::End)])
+    } else {
+      hir
+    };
+
+    Self::unmatchable(&hir).then(|| {
+      Diagnostic::warning(
+        if pattern.anchored {
+          format!(
+            "Pattern `{}` can never match a complete argument",
+            pattern.literal.text
+          )
+        } else {
+          format!("Regex `{}` can never match", pattern.literal.text)
+        },
+        pattern
+          .literal
+          .range(document, 0, pattern.literal.text.len()),
+      )
+    })
+  }
+
+  fn consumes(hir: &Hir) -> bool {
+    hir
+      .properties()
+      .minimum_len()
+      .is_some_and(|length| length > 0)
+  }
+
+  fn invalid(
+    document: &Document,
+    pattern: &Pattern,
+    kind: impl Display,
+    span: &regex_syntax::ast::Span,
+  ) -> Diagnostic {
+    Diagnostic::error(
+      format!("Invalid regex: {kind}"),
+      pattern
+        .literal
+        .range(document, span.start.offset, span.end.offset),
+    )
+  }
+
+  /// Whether `hir` can never match any input, either because it contains an
+  /// empty class or because it requires input before the start, or after the
+  /// end, of the text.
+  fn unmatchable(hir: &Hir) -> bool {
+    if hir.properties().minimum_len().is_none() {
+      return true;
+    }
+
+    match hir.kind() {
+      HirKind::Alternation(alternatives) => {
+        alternatives.iter().all(Self::unmatchable)
+      }
+      HirKind::Capture(capture) => Self::unmatchable(&capture.sub),
+      HirKind::Concat(items) => {
+        items
+          .iter()
+          .enumerate()
+          .any(|(index, item)| match item.kind() {
+            HirKind::Look(Look::Start) => {
+              items[..index].iter().any(Self::consumes)
+            }
+            HirKind::Look(Look::End) => {
+              items[index + 1..].iter().any(Self::consumes)
+            }
+            _ => Self::unmatchable(item),
+          })
+      }
+      HirKind::Repetition(repetition) if repetition.min > 0 => {
+        Self::unmatchable(&repetition.sub)
+      }
+      _ => false,
+    }
+  }
+}
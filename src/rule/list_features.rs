@@ -26,6 +26,25 @@ define_rule! {
       );
 
       diagnostics
+    },
+    quickfixes(context) {
+      let document = context.document();
+
+      let last = document
+        .settings()
+        .into_iter()
+        .map(|setting| setting.range)
+        .max_by_key(|range| range.end);
+
+      Self
+        .run(context)
+        .into_iter()
+        .map(|diagnostic| Quickfix {
+          range: diagnostic.range,
+          ..Quickfix::line_after(document, last, "set lists", "Add `set lists`")
+            .safe(false)
+        })
+        .collect()
     }
   }
 }
@@ -9,20 +9,66 @@ define_rule! {
     run(context) {
       let mut diagnostics = Vec::new();
 
-      let recipe_names = context.recipe_names();
-
-      for recipe in context.recipes() {
-        for dependency in &recipe.dependencies {
-          if !recipe_names.contains(&dependency.name) {
-            diagnostics.push(Diagnostic::error(
-              format!("Recipe `{}` not found", dependency.name),
-              dependency.range,
-            ));
-          }
-        }
+      for (recipe, dependency) in Self::missing(context) {
+        diagnostics.push(Diagnostic::error(
+          Self::suggestions(context, recipe, dependency).message(format!(
+            "Recipe `{}` not found",
+            dependency.name.value
+          )),
+          dependency.range,
+        ));
       }
 
       diagnostics
+    },
+    quickfixes(context) {
+      Self::missing(context)
+        .flat_map(|(recipe, dependency)| {
+          Self::suggestions(context, recipe, dependency)
+            .quickfixes(&dependency.name, dependency.range)
+            .into_iter()
+            .chain(
+              (!dependency.name.value.is_empty()
+                && !dependency.name.value.contains("::"))
+                .then(|| {
+                  Quickfix::create_recipe(context.document(), dependency)
+                }),
+            )
+        })
+        .collect()
     }
   }
 }
+
+impl MissingDependencyRule {
+  fn missing<'a>(
+    context: &'a RuleContext<'_>,
+  ) -> impl Iterator<Item = (&'a Recipe, &'a Dependency)> {
+    let recipe_names = context.recipe_names();
+
+    context.recipes().iter().flat_map(move |recipe| {
+      recipe
+        .dependencies
+        .iter()
+        .filter(|dependency| !recipe_names.contains(&dependency.name.value))
+        .map(move |dependency| (recipe, dependency))
+    })
+  }
+
+  /// Suggest other recipes with similar names, leaving out the dependent
+  /// recipe itself since depending on it would be a cycle.
+  fn suggestions(
+    context: &RuleContext<'_>,
+    recipe: &Recipe,
+    dependency: &Dependency,
+  ) -> Suggestions {
+    Suggestions::new(
+      &dependency.name.value,
+      context
+        .recipe_names()
+        .iter()
+        .map(String::as_str)
+        .filter(|name| *name != recipe.name.value),
+    )
+  }
+}
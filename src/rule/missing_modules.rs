@@ -0,0 +1,92 @@
+use super::*;
+
+define_rule! {
+  /// Reports non-optional `mod` statements for which no source file can be
+  /// found, offering to create `<name>.just` next to the current document.
+  MissingModuleRule {
+    id: "missing-modules",
+    message: "missing module",
+    run(context) {
+      MissingModuleRule::missing(context)
+        .into_iter()
+        .map(|module| {
+          Diagnostic::error(
+            format!(
+              "Could not find source file for module `{}`",
+              module.name.value
+            ),
+            module.name.range,
+          )
+        })
+        .collect()
+    },
+    quickfixes(context) {
+      let document = context.document();
+
+      MissingModuleRule::missing(context)
+        .into_iter()
+        .filter_map(|module| {
+          let path = match &module.path {
+            Some(_) => module
+              .resolve(&document.uri)
+              .filter(|path| !path.exists())?,
+            None => document
+              .uri
+              .to_file_path()
+              .ok()?
+              .parent()?
+              .join(format!("{}.just", module.name.value)),
+          };
+
+          let uri = lsp::Url::from_file_path(&path).ok()?;
+
+          let file_name = path.file_name()?.to_string_lossy().into_owned();
+
+          Some(Quickfix::create_file(
+            uri,
+            module.name.range,
+            format!("Create `{file_name}`"),
+          ))
+        })
+        .collect()
+    }
+  }
+}
+
+impl MissingModuleRule {
+  /// Whether the path of `module`, if it has one, is a plain string literal
+  /// rather than a format or shell-expanded string whose value isn't known
+  /// statically.
+  fn literal_path(document: &Document, module: &Module) -> bool {
+    module.path.as_ref().is_none_or(|path| {
+      document
+        .node_at_position(path.range.start)
+        .and_then(|node| {
+          successors(Some(node), Node::parent)
+            .find(|node| node.kind() == "string")
+        })
+        .and_then(|node| StringLiteral::new(document, node))
+        .is_some()
+    })
+  }
+
+  /// Non-optional modules without a source file, unless the document isn't
+  /// a file on disk, in which case there is no directory to look in.
+  fn missing(context: &RuleContext<'_>) -> Vec<Module> {
+    let document = context.document();
+
+    if !document.uri.to_file_path().is_ok_and(|path| path.is_file()) {
+      return Vec::new();
+    }
+
+    document
+      .modules()
+      .into_iter()
+      .filter(|module| {
+        !module.optional
+          && Self::literal_path(document, module)
+          && module.candidates(&document.uri).is_empty()
+      })
+      .collect()
+  }
+}
@@ -8,18 +8,40 @@ define_rule! {
     run(context) {
       let mut diagnostics = Vec::new();
 
-      let recipe_names = context.recipe_names();
-
-      for alias in context.aliases() {
-        if !recipe_names.contains(&alias.value.value) {
-          diagnostics.push(Diagnostic::error(
-            format!("Recipe `{}` not found", alias.value.value),
-            alias.value.range,
-          ));
-        }
+      for alias in Self::missing(context) {
+        diagnostics.push(Diagnostic::error(
+          Self::suggestions(context, &alias.value.value)
+            .message(format!("Recipe `{}` not found", alias.value.value)),
+          alias.value.range,
+        ));
       }
 
       diagnostics
+    },
+    quickfixes(context) {
+      Self::missing(context)
+        .flat_map(|alias| {
+          Self::suggestions(context, &alias.value.value)
+            .quickfixes(&alias.value, alias.value.range)
+        })
+        .collect()
     }
   }
 }
+
+impl MissingRecipeForAliasRule {
+  fn missing<'a>(
+    context: &'a RuleContext<'_>,
+  ) -> impl Iterator<Item = &'a Alias> {
+    let recipe_names = context.recipe_names();
+
+    context
+      .aliases()
+      .iter()
+      .filter(|alias| !recipe_names.contains(&alias.value.value))
+  }
+
+  fn suggestions(context: &RuleContext<'_>, name: &str) -> Suggestions {
+    Suggestions::new(name, context.recipe_names().iter().map(String::as_str))
+  }
+}
@@ -56,6 +56,14 @@ define_rule! {
         .filter(|recipe| recipe.shebang.is_none())
         .filter_map(Self::find_mixed_indentation)
         .collect()
+    },
+    quickfixes(context) {
+      context
+        .recipes()
+        .iter()
+        .filter(|recipe| recipe.shebang.is_none())
+        .filter_map(Self::normalize)
+        .collect()
     }
   }
 }
@@ -104,6 +112,59 @@ impl MixedIndentationRule {
     )
   }
 
+  /// Re-indent every line that doesn't match the kind of the first
+  /// consistently indented line, falling back to four spaces when no line
+  /// is consistently indented.
+  ///
+  /// This flattens any nesting in the re-indented lines, so it's never
+  /// applied without review.
+  fn normalize(recipe: &Recipe) -> Option<Quickfix> {
+    let diagnostic = Self::find_mixed_indentation(recipe)?;
+
+    let lines = Self::recipe_body_lines(&recipe.content).collect::<Vec<_>>();
+
+    let (kind, indent) = lines
+      .iter()
+      .find_map(|line| {
+        let kind = line.kind?;
+
+        let character = match kind {
+          IndentKind::Spaces => " ",
+          IndentKind::Tabs => "\t",
+        };
+
+        Some((Some(kind), character.repeat(line.indent_length)))
+      })
+      .unwrap_or((None, "    ".into()));
+
+    let edits = lines
+      .iter()
+      .filter(|line| kind.is_none() || line.kind != kind)
+      .map(|line| {
+        let line_number = recipe.range.start.line + line.relative_line;
+
+        lsp::TextEdit {
+          range: lsp::Range::at(
+            line_number,
+            0,
+            line_number,
+            u32::try_from(line.indent_length).unwrap_or(u32::MAX),
+          ),
+          new_text: indent.clone(),
+        }
+      })
+      .collect();
+
+    Some(Quickfix {
+      create: false,
+      edits,
+      range: diagnostic.range,
+      safe: false,
+      title: format!("Normalize indentation of recipe `{}`", recipe.name.value),
+      uri: None,
+    })
+  }
+
   fn recipe_body_lines(content: &str) -> impl Iterator<Item = RecipeLine> + '_ {
     content
       .lines()
@@ -0,0 +1,27 @@
+use super::*;
+
+define_rule! {
+  /// Reports modules that share a name with a recipe, since `just foo` could
+  /// then refer to either.
+  ModuleRecipeConflictRule {
+    id: "module-recipe-conflict",
+    message: "name conflict",
+    run(context) {
+      context
+        .document()
+        .modules()
+        .into_iter()
+        .filter(|module| context.recipe(&module.name.value).is_some())
+        .map(|module| {
+          Diagnostic::error(
+            format!(
+              "Module `{}` conflicts with a recipe of the same name",
+              module.name.value
+            ),
+            module.name.range,
+          )
+        })
+        .collect()
+    }
+  }
+}
@@ -0,0 +1,194 @@
+use super::*;
+
+/// An interpolation of a recipe parameter that the shell will word-split
+/// and glob-expand.
+struct UnquotedInterpolation {
+  expression: TextNode,
+  interpolation: TextNode,
+  parameter: String,
+  position: Option<usize>,
+}
+
+define_rule! {
+  /// Opt-in rule that flags recipe parameters interpolated into linewise
+  /// recipe bodies without `quote()` or surrounding shell quotes, since their
+  /// values are passed to the shell verbatim.
+  ShellInjectionRule {
+    id: "shell-injection",
+    message: "possible shell injection",
+    enabled(config) {
+      config
+        .level()
+        .is_some_and(|level| level != RuleLevel::Off)
+    },
+    run(context) {
+      Self::unquoted(context)
+        .into_iter()
+        .map(|unquoted| {
+          let alternative = unquoted.position.map_or_else(
+            || "wrap it in `quote()`".to_string(),
+            |position| format!("wrap it in `quote()` or use `\"${position}\"`"),
+          );
+
+          Diagnostic::warning(
+            format!(
+              "Parameter `{}` is interpolated without quoting, {alternative}",
+              unquoted.parameter
+            ),
+            unquoted.interpolation.range,
+          )
+        })
+        .collect()
+    },
+    quickfixes(context) {
+      Self::unquoted(context)
+        .into_iter()
+        .flat_map(|unquoted| {
+          let range = unquoted.interpolation.range;
+
+          let quote = Quickfix {
+            range,
+            title: format!("Wrap `{}` in `quote()`", unquoted.expression.value),
+            ..Quickfix::replacement(
+              &unquoted.expression,
+              format!("quote({})", unquoted.expression.value),
+            )
+            .safe(false)
+          };
+
+          let positional = unquoted.position.map(|position| Quickfix {
+            range,
+            ..Quickfix::replacement(
+              &unquoted.interpolation,
+              format!("\"${position}\""),
+            )
+            .safe(false)
+          });
+
+          once(quote).chain(positional)
+        })
+        .collect()
+    }
+  }
+}
+
+impl ShellInjectionRule {
+  /// Whether `node` is an argument to `quote()`, somewhere below
+  /// `interpolation`.
+  fn quoted(document: &Document, node: Node, interpolation: Node) -> bool {
+    successors(node.parent(), Node::parent)
+      .take_while(|ancestor| ancestor.id() != interpolation.id())
+      .any(|ancestor| {
+        ancestor.kind() == "function_call"
+          && ancestor
+            .child_by_field_name("name")
+            .is_some_and(|name| document.get_node_text(&name) == "quote")
+      })
+  }
+
+  /// The shell quote that is open after `text`, starting from `open`.
+  fn scan(text: &str, mut open: Option<char>) -> Option<char> {
+    let mut characters = text.chars();
+
+    while let Some(character) = characters.next() {
+      match (open, character) {
+        (Some('\''), '\'') | (Some('"'), '"') => open = None,
+        (None, '\'' | '"') => open = Some(character),
+        (None | Some('"'), '\\') => {
+          characters.next();
+        }
+        _ => {}
+      }
+    }
+
+    open
+  }
+
+  fn unquoted(context: &RuleContext<'_>) -> Vec<UnquotedInterpolation> {
+    let Some(tree) = context.tree() else {
+      return Vec::new();
+    };
+
+    let document = context.document();
+
+    let positional_arguments = context.setting_enabled("positional-arguments");
+
+    let mut unquoted = Vec::new();
+
+    for line in tree.root_node().find_all("recipe_line") {
+      let Some(recipe) = line.get_recipe(document) else {
+        continue;
+      };
+
+      if recipe.shebang.is_some() || recipe.has_attribute("script") {
+        continue;
+      }
+
+      let positional =
+        positional_arguments || recipe.has_attribute("positional-arguments");
+
+      let mut open = None;
+
+      let mut cursor = line.walk();
+
+      for child in line.children(&mut cursor) {
+        if child.kind() != "interpolation" {
+          open = Self::scan(&document.get_node_text(&child), open);
+          continue;
+        }
+
+        if open.is_some() {
+          continue;
+        }
+
+        let Some(expression) = child.named_child(0) else {
+          continue;
+        };
+
+        let Some((index, parameter)) = expression
+          .find_all("identifier")
+          .into_iter()
+          .filter(|identifier| {
+            identifier
+              .parent()
+              .is_none_or(|parent| parent.kind() != "function_call")
+              && !Self::quoted(document, *identifier, child)
+          })
+          .find_map(|identifier| {
+            let name = document.get_node_text(&identifier);
+
+            recipe
+              .parameters
+              .iter()
+              .enumerate()
+              .find(|(_, parameter)| parameter.name == name)
+          })
+        else {
+          continue;
+        };
+
+        let expression_text = document.get_node_text(&expression);
+
+        let position = (positional
+          && expression_text == parameter.name
+          && !matches!(parameter.kind, ParameterKind::Variadic(_)))
+        .then_some(index + 1);
+
+        unquoted.push(UnquotedInterpolation {
+          expression: TextNode {
+            range: expression.get_range(document),
+            value: expression_text,
+          },
+          interpolation: TextNode {
+            range: child.get_range(document),
+            value: document.get_node_text(&child),
+          },
+          parameter: parameter.name.clone(),
+          position,
+        });
+      }
+    }
+
+    unquoted
+  }
+}
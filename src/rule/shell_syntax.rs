@@ -0,0 +1,61 @@
+use super::*;
+
+define_rule! {
+  /// Parses recipe lines, script recipe bodies and backticks whose
+  /// interpreter is a POSIX-style shell, and reports the syntax errors found
+  /// at their location in the justfile.
+  ShellSyntaxRule {
+    id: "shell-syntax",
+    message: "shell syntax error",
+    run(context) {
+      let document = context.document();
+
+      let mut parser = Parser::new();
+
+      let mut diagnostics = Vec::new();
+
+      for script in EmbeddedScript::all(document) {
+        if script.language != EmbeddedLanguage::Shell {
+          continue;
+        }
+
+        let Some(tree) = script.parse(&mut parser) else {
+          continue;
+        };
+
+        let mut pending = vec![tree.root_node()];
+
+        while let Some(node) = pending.pop() {
+          if node.is_missing() {
+            diagnostics.push(Diagnostic::warning(
+              format!("Shell syntax error: missing `{}`", node.kind()),
+              script.range(document, node.start_byte(), node.end_byte()),
+            ));
+          } else if node.is_error() {
+            diagnostics.push(Diagnostic::warning(
+              Self::error_message(&script, node),
+              script.range(document, node.start_byte(), node.end_byte()),
+            ));
+          } else if node.has_error() {
+            let mut cursor = node.walk();
+            pending.extend(node.children(&mut cursor));
+          }
+        }
+      }
+
+      diagnostics
+    }
+  }
+}
+
+impl ShellSyntaxRule {
+  fn error_message(script: &EmbeddedScript, node: Node) -> String {
+    script.text[node.start_byte()..node.end_byte()]
+      .split_whitespace()
+      .next()
+      .map_or_else(
+        || "Shell syntax error".into(),
+        |token| format!("Shell syntax error near `{token}`"),
+      )
+  }
+}
@@ -0,0 +1,24 @@
+use super::*;
+
+define_rule! {
+  /// Reports lists used where a string is required, and strings used where a
+  /// list is required, when `set lists` is enabled.
+  TypeMismatchRule {
+    id: "type-mismatch",
+    message: "type mismatch",
+    run(context) {
+      if !context.setting_enabled("lists") {
+        return Vec::new();
+      }
+
+      let document = context.document();
+
+      let view = ProjectView::from_documents(
+        document,
+        once(document).chain(context.imported_documents().iter().copied()),
+      );
+
+      TypeChecker::new(&view).check(document)
+    }
+  }
+}
@@ -9,14 +9,121 @@ define_rule! {
     run(context) {
       let mut diagnostics = Vec::new();
 
+      let locals = Self::locals(context);
+
       for (name, range) in &context.scope().unresolved_identifiers {
         diagnostics.push(Diagnostic::error(
-          format!("Variable `{name}` not found"),
+          Self::suggestions(context, &locals, name, *range)
+            .message(format!("Variable `{name}` not found")),
           *range,
         ));
       }
 
       diagnostics
+    },
+    quickfixes(context) {
+      let locals = Self::locals(context);
+
+      context
+        .scope()
+        .unresolved_identifiers
+        .iter()
+        .flat_map(|(name, range)| {
+          Self::suggestions(context, &locals, name, *range)
+            .quickfixes(
+              &TextNode {
+                range: *range,
+                value: name.clone(),
+              },
+              *range,
+            )
+            .into_iter()
+            .chain(Self::creations(context, name, *range))
+        })
+        .collect()
+    }
+  }
+}
+
+impl UndefinedIdentifierRule {
+  /// Offer to define the missing variable in the current document, or in any
+  /// of the documents it imports.
+  fn creations(
+    context: &RuleContext<'_>,
+    name: &str,
+    range: lsp::Range,
+  ) -> Vec<Quickfix> {
+    once(Quickfix::create_variable(context.document(), name, range))
+      .chain(context.imported_documents().iter().map(|document| {
+        let quickfix = Quickfix::create_variable(document, name, range);
+
+        let file = document
+          .uri
+          .path_segments()
+          .and_then(Iterator::last)
+          .unwrap_or_else(|| document.uri.as_str());
+
+        Quickfix {
+          title: format!("{} in `{file}`", quickfix.title),
+          ..quickfix
+        }
+      }))
+      .collect()
+  }
+
+  /// Parameter names of each recipe and function in the document, keyed by
+  /// the range they're visible in.
+  fn locals(context: &RuleContext<'_>) -> Vec<(lsp::Range, Vec<String>)> {
+    if context.scope().unresolved_identifiers.is_empty() {
+      return Vec::new();
     }
+
+    let document = context.document();
+
+    document
+      .recipes()
+      .into_iter()
+      .map(|recipe| {
+        (
+          recipe.range,
+          recipe
+            .parameters
+            .into_iter()
+            .map(|parameter| parameter.name)
+            .collect(),
+        )
+      })
+      .chain(document.functions().into_iter().map(|function| {
+        (
+          function.range,
+          function
+            .parameters
+            .into_iter()
+            .map(|parameter| parameter.value)
+            .collect(),
+        )
+      }))
+      .collect()
+  }
+
+  fn suggestions(
+    context: &RuleContext<'_>,
+    locals: &[(lsp::Range, Vec<String>)],
+    name: &str,
+    range: lsp::Range,
+  ) -> Suggestions {
+    Suggestions::new(
+      name,
+      context
+        .variable_and_builtin_names()
+        .iter()
+        .chain(
+          locals
+            .iter()
+            .filter(|(scope, _)| scope.overlaps(range))
+            .flat_map(|(_, names)| names),
+        )
+        .map(String::as_str),
+    )
   }
 }
@@ -8,18 +8,45 @@ define_rule! {
     run(context) {
       let mut diagnostics = Vec::new();
 
-      for attribute in context.attributes() {
+      for attribute in Self::unknown(context) {
         let attribute_name = &attribute.name.value;
 
-        if context.builtin_attributes(attribute_name).is_empty() {
-          diagnostics.push(Diagnostic::error(
-            format!("Unknown attribute `{attribute_name}`"),
-            attribute.name.range,
-          ));
-        }
+        diagnostics.push(Diagnostic::error(
+          Self::suggestions(attribute_name)
+            .message(format!("Unknown attribute `{attribute_name}`")),
+          attribute.name.range,
+        ));
       }
 
       diagnostics
+    },
+    quickfixes(context) {
+      Self::unknown(context)
+        .flat_map(|attribute| {
+          Self::suggestions(&attribute.name.value)
+            .quickfixes(&attribute.name, attribute.name.range)
+        })
+        .collect()
     }
   }
 }
+
+impl UnknownAttributeRule {
+  fn suggestions(name: &str) -> Suggestions {
+    Suggestions::new(
+      name,
+      BUILTINS.iter().filter_map(|builtin| match builtin {
+        Builtin::Attribute { name, .. } => Some(*name),
+        _ => None,
+      }),
+    )
+  }
+
+  fn unknown<'a>(
+    context: &'a RuleContext<'_>,
+  ) -> impl Iterator<Item = &'a Attribute> {
+    context.attributes().iter().filter(|attribute| {
+      context.builtin_attributes(&attribute.name.value).is_empty()
+    })
+  }
+}
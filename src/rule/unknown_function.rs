@@ -9,20 +9,53 @@ define_rule! {
     run(context) {
       let mut diagnostics = Vec::new();
 
-      for function_call in context.function_calls() {
+      for function_call in Self::unknown(context) {
         let function_name = &function_call.name.value;
 
-        if context.builtin_function(function_name.as_str()).is_none()
-          && !context.user_function_names().contains(function_name)
-        {
-          diagnostics.push(Diagnostic::error(
-            format!("Unknown function `{function_name}`"),
-            function_call.name.range,
-          ));
-        }
+        diagnostics.push(Diagnostic::error(
+          Self::suggestions(context, function_name)
+            .message(format!("Unknown function `{function_name}`")),
+          function_call.name.range,
+        ));
       }
 
       diagnostics
+    },
+    quickfixes(context) {
+      Self::unknown(context)
+        .flat_map(|function_call| {
+          Self::suggestions(context, &function_call.name.value)
+            .quickfixes(&function_call.name, function_call.name.range)
+        })
+        .collect()
     }
   }
 }
+
+impl UnknownFunctionRule {
+  fn suggestions(context: &RuleContext<'_>, name: &str) -> Suggestions {
+    Suggestions::new(
+      name,
+      BUILTINS
+        .iter()
+        .flat_map(|builtin| match builtin {
+          Builtin::Function { name, aliases, .. } => {
+            once(*name).chain(aliases.iter().copied()).collect()
+          }
+          _ => Vec::new(),
+        })
+        .chain(context.user_function_names().iter().map(String::as_str)),
+    )
+  }
+
+  fn unknown<'a>(
+    context: &'a RuleContext<'_>,
+  ) -> impl Iterator<Item = &'a FunctionCall> {
+    context.function_calls().iter().filter(|function_call| {
+      let function_name = &function_call.name.value;
+
+      context.builtin_function(function_name.as_str()).is_none()
+        && !context.user_function_names().contains(function_name)
+    })
+  }
+}
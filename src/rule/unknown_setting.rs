@@ -9,16 +9,44 @@ define_rule! {
     run(context) {
       let mut diagnostics = Vec::new();
 
-      for setting in context.settings() {
-        if context.builtin_setting(&setting.name.value).is_none() {
-          diagnostics.push(Diagnostic::error(
-            format!("Unknown setting `{}`", setting.name.value),
-            setting.range,
-          ));
-        }
+      for setting in Self::unknown(context) {
+        diagnostics.push(Diagnostic::error(
+          Self::suggestions(&setting.name.value)
+            .message(format!("Unknown setting `{}`", setting.name.value)),
+          setting.range,
+        ));
       }
 
       diagnostics
+    },
+    quickfixes(context) {
+      Self::unknown(context)
+        .flat_map(|setting| {
+          Self::suggestions(&setting.name.value)
+            .quickfixes(&setting.name, setting.range)
+        })
+        .collect()
     }
   }
 }
+
+impl UnknownSettingRule {
+  fn suggestions(name: &str) -> Suggestions {
+    Suggestions::new(
+      name,
+      BUILTINS.iter().filter_map(|builtin| match builtin {
+        Builtin::Setting { name, .. } => Some(*name),
+        _ => None,
+      }),
+    )
+  }
+
+  fn unknown<'a>(
+    context: &'a RuleContext<'_>,
+  ) -> impl Iterator<Item = &'a Setting> {
+    context
+      .settings()
+      .iter()
+      .filter(|setting| context.builtin_setting(&setting.name.value).is_none())
+  }
+}
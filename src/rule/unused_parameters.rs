@@ -7,46 +7,49 @@ define_rule! {
     id: "unused-parameters",
     message: "unused parameter",
     run(context) {
-      let exported = context.setting_enabled("export");
-
-      let positional_arguments_enabled = context.setting_enabled("positional-arguments");
-
-      context
-        .scope()
-        .recipe_identifier_usage
-        .iter()
-        .filter_map(|(recipe_name, identifiers)| {
-          context.recipe(recipe_name).map(|recipe| (recipe, identifiers))
+      Self::unused(context)
+        .into_iter()
+        .map(|parameter| {
+          Diagnostic::warning(
+            format!("Parameter `{}` appears unused", parameter.name),
+            parameter.range,
+          )
         })
-        .flat_map(|(recipe, identifiers): (&Recipe, _)| {
-          let recipe_enables_positional_arguments =
-            positional_arguments_enabled || recipe.has_attribute("positional-arguments");
-
-          let (positional_usage, uses_all) = if recipe_enables_positional_arguments {
-            (
-              UnusedParameterRule::positional_argument_indices(recipe),
-              recipe.shebang.is_some()
-                || UnusedParameterRule::uses_all_positional_arguments(recipe),
-            )
+        .collect()
+    },
+    quickfixes(context) {
+      Self::unused(context)
+        .into_iter()
+        .flat_map(|parameter| {
+          let name = if matches!(parameter.kind, ParameterKind::Variadic(_)) {
+            lsp::Position {
+              character: parameter.range.start.character + 1,
+              ..parameter.range.start
+            }
           } else {
-            (HashSet::new(), false)
+            parameter.range.start
           };
 
-          recipe.parameters.iter().enumerate().filter_map(move |(index, parameter)| {
-            let used_via_position = uses_all || positional_usage.contains(&(index + 1));
-
-            let is_unused = !identifiers.contains(&parameter.name)
-              && !parameter.export
-              && !exported
-              && !used_via_position;
-
-            is_unused.then(|| {
-              Diagnostic::warning(
-                format!("Parameter `{}` appears unused", parameter.name),
+          [
+            Quickfix {
+              range: parameter.range,
+              ..Quickfix::token_removal(
+                context.document(),
                 parameter.range,
+                format!("Remove parameter `{}`", parameter.name),
               )
-            })
-          })
+              .safe(false)
+            },
+            Quickfix {
+              range: parameter.range,
+              ..Quickfix::insertion(
+                name,
+                "$",
+                format!("Export parameter `{}` with `$`", parameter.name),
+              )
+              .safe(false)
+            },
+          ]
         })
         .collect()
     }
@@ -96,6 +99,54 @@ impl UnusedParameterRule {
       .collect()
   }
 
+  fn unused<'a>(context: &'a RuleContext<'_>) -> Vec<&'a Parameter> {
+    let exported = context.setting_enabled("export");
+
+    let positional_arguments_enabled =
+      context.setting_enabled("positional-arguments");
+
+    context
+      .scope()
+      .recipe_identifier_usage
+      .iter()
+      .filter_map(|(recipe_name, identifiers)| {
+        context
+          .recipe(recipe_name)
+          .map(|recipe| (recipe, identifiers))
+      })
+      .flat_map(|(recipe, identifiers)| {
+        let recipe_enables_positional_arguments = positional_arguments_enabled
+          || recipe.has_attribute("positional-arguments");
+
+        let (positional_usage, uses_all) =
+          if recipe_enables_positional_arguments {
+            (
+              Self::positional_argument_indices(recipe),
+              recipe.shebang.is_some()
+                || Self::uses_all_positional_arguments(recipe),
+            )
+          } else {
+            (HashSet::new(), false)
+          };
+
+        recipe
+          .parameters
+          .iter()
+          .enumerate()
+          .filter(move |(index, parameter)| {
+            let used_via_position =
+              uses_all || positional_usage.contains(&(index + 1));
+
+            !identifiers.contains(&parameter.name)
+              && !parameter.export
+              && !exported
+              && !used_via_position
+          })
+          .map(|(_, parameter)| parameter)
+      })
+      .collect()
+  }
+
   fn uses_all_positional_arguments(recipe: &Recipe) -> bool {
     let bytes = recipe.content.as_bytes();
 
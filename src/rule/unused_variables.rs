@@ -7,35 +7,75 @@ define_rule! {
     id: "unused-variables",
     message: "unused variable",
     run(context) {
-      let mut diagnostics = Vec::new();
+      Self::unused(context)
+        .into_iter()
+        .map(|variable| {
+          Diagnostic::warning(
+            format!("Variable `{}` appears unused", variable.name.value),
+            variable.name.range,
+          )
+        })
+        .collect()
+    },
+    quickfixes(context) {
+      Self::unused(context)
+        .into_iter()
+        .map(|variable| {
+          let start = variable
+            .attributes
+            .iter()
+            .map(|attribute| attribute.range.start)
+            .chain(once(variable.range.start))
+            .min()
+            .unwrap_or(variable.range.start);
 
-      if context.tree().is_none() {
-        return diagnostics;
-      }
+          let end = if variable.range.end.character == 0 {
+            variable.range.end
+          } else {
+            lsp::Position::new(variable.range.end.line + 1, 0)
+          };
 
-      let exported = context.setting_enabled("export");
+          Quickfix {
+            range: variable.name.range,
+            ..Quickfix::removal(
+              lsp::Range::at(start.line, 0, end.line, end.character),
+              format!("Remove unused variable `{}`", variable.name.value),
+            )
+            .safe(false)
+          }
+        })
+        .collect()
+    }
+  }
+}
 
-      for (variable_name, is_used) in &context.scope().variable_usage {
-        if variable_name.starts_with('_') || *is_used {
-          continue;
-        }
+impl UnusedVariableRule {
+  fn unused(context: &RuleContext<'_>) -> Vec<Variable> {
+    let mut unused = Vec::new();
 
-        let Some(variable) = context.document().find_variable(variable_name)
-        else {
-          continue;
-        };
+    if context.tree().is_none() {
+      return unused;
+    }
 
-        if variable.export || exported {
-          continue;
-        }
+    let exported = context.setting_enabled("export");
 
-        diagnostics.push(Diagnostic::warning(
-          format!("Variable `{variable_name}` appears unused"),
-          variable.name.range,
-        ));
+    for (variable_name, is_used) in &context.scope().variable_usage {
+      if variable_name.starts_with('_') || *is_used {
+        continue;
       }
 
-      diagnostics
+      let Some(variable) = context.document().find_variable(variable_name)
+      else {
+        continue;
+      };
+
+      if variable.export || exported {
+        continue;
+      }
+
+      unused.push(variable);
     }
+
+    unused
   }
 }
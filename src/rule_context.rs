@@ -13,6 +13,7 @@ pub struct RuleContext<'a> {
   function_calls: OnceLock<Vec<FunctionCall>>,
   functions: OnceLock<Vec<Function>>,
   imported_documents: Vec<&'a Document>,
+  project: Option<&'a Project>,
   recipe_names: OnceLock<HashSet<String>>,
   recipe_parameters: OnceLock<HashMap<String, Vec<Parameter>>>,
   recipes: OnceLock<Vec<Recipe>>,
@@ -144,10 +145,15 @@ impl<'a> RuleContext<'a> {
       .as_slice()
   }
 
+  pub fn imported_documents(&self) -> &[&'a Document] {
+    &self.imported_documents
+  }
+
   #[must_use]
   pub fn new(
     document: &'a Document,
     imported_documents: impl IntoIterator<Item = &'a Document>,
+    project: Option<&'a Project>,
   ) -> Self {
     Self {
       aliases: OnceLock::new(),
@@ -160,6 +166,7 @@ impl<'a> RuleContext<'a> {
       function_calls: OnceLock::new(),
       functions: OnceLock::new(),
       imported_documents: imported_documents.into_iter().collect(),
+      project,
       recipe_names: OnceLock::new(),
       recipe_parameters: OnceLock::new(),
       recipes: OnceLock::new(),
@@ -172,6 +179,11 @@ impl<'a> RuleContext<'a> {
     }
   }
 
+  /// The project the document was loaded as the root of, if any.
+  pub fn project(&self) -> Option<&'a Project> {
+    self.project
+  }
+
   pub fn recipe(&self, name: &str) -> Option<&Recipe> {
     self
       .recipes()
@@ -206,6 +218,31 @@ impl<'a> RuleContext<'a> {
       .as_slice()
   }
 
+  /// The imports of `uri` that the project loader resolved to a document in
+  /// this context, as the range of each import's path and that document.
+  pub fn resolved_imports(
+    &self,
+    uri: &lsp::Url,
+  ) -> Vec<(lsp::Range, &'a Document)> {
+    self
+      .project
+      .into_iter()
+      .flat_map(|project| project.dependencies(uri))
+      .filter_map(|dependency| {
+        let ProjectDependencyTarget::Resolved(target) = &dependency.target
+        else {
+          return None;
+        };
+
+        let target = once(self.document)
+          .chain(self.imported_documents.iter().copied())
+          .find(|candidate| candidate.uri == *target)?;
+
+        Some((dependency.location, target))
+      })
+      .collect()
+  }
+
   pub fn scope(&self) -> &Scope<'_> {
     self.scope.get_or_init(|| Scope::analyze(self))
   }
@@ -282,6 +319,7 @@ mod tests {
     test(&RuleContext::new(
       documents.get(&uri).unwrap(),
       project.imported_documents(&documents),
+      Some(&project),
     ));
   }
 
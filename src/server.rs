@@ -8,11 +8,18 @@ impl Server {
       completion_provider: Some(lsp::CompletionOptions {
         ..Default::default()
       }),
-      code_action_provider: Some(lsp::CodeActionProviderCapability::Simple(
-        true,
+      code_action_provider: Some(lsp::CodeActionProviderCapability::Options(
+        lsp::CodeActionOptions {
+          code_action_kinds: Some(vec![
+            lsp::CodeActionKind::QUICKFIX,
+            lsp::CodeActionKind::SOURCE,
+            lsp::CodeActionKind::new(Quickfixer::FIX_ALL),
+          ]),
+          ..Default::default()
+        },
       )),
       code_lens_provider: Some(lsp::CodeLensOptions {
-        resolve_provider: Some(false),
+        resolve_provider: Some(true),
       }),
       definition_provider: Some(lsp::OneOf::Left(true)),
       document_symbol_provider: Some(lsp::OneOf::Left(true)),
@@ -39,8 +46,10 @@ impl Server {
         lsp::SemanticTokensServerCapabilities::SemanticTokensOptions(
           lsp::SemanticTokensOptions {
             legend: tokenizer::Tokenizer::legend().clone(),
-            full: Some(lsp::SemanticTokensFullOptions::Bool(true)),
-            range: None,
+            full: Some(lsp::SemanticTokensFullOptions::Delta {
+              delta: Some(true),
+            }),
+            range: Some(true),
             ..Default::default()
           },
         ),
@@ -67,10 +76,27 @@ impl Server {
     Self(Arc::new(Inner::new(client)))
   }
 
+  /// Returns the output retained for a run started by a command.
+  async fn recipe_output(
+    &self,
+    params: RecipeOutputParams,
+  ) -> Result<Option<RecipeOutput>, jsonrpc::Error> {
+    self.0.recipe_output(params).await
+  }
+
+  /// Returns every recipe runnable from the project containing a
+  /// document.
+  async fn recipes(
+    &self,
+    params: lsp::TextDocumentIdentifier,
+  ) -> Result<Vec<RecipeJson>, jsonrpc::Error> {
+    self.0.recipes(params).await
+  }
+
   pub(crate) async fn run() -> Result {
     let (stdin, stdout) = (tokio::io::stdin(), tokio::io::stdout());
 
-    let (service, socket) = LspService::new(Server::new);
+    let (service, socket) = Self::service();
 
     tower_lsp::Server::new(stdin, stdout, socket)
       .serve(service)
@@ -78,6 +104,14 @@ impl Server {
 
     Ok(())
   }
+
+  /// The language service, including the server's custom methods.
+  pub(crate) fn service() -> (LspService<Self>, ClientSocket) {
+    LspService::build(Self::new)
+      .custom_method("just-lsp/recipeOutput", Self::recipe_output)
+      .custom_method("just-lsp/recipes", Self::recipes)
+      .finish()
+  }
 }
 
 impl Debug for Server {
@@ -102,6 +136,13 @@ impl LanguageServer for Server {
     self.0.code_lens(params).await
   }
 
+  async fn code_lens_resolve(
+    &self,
+    params: lsp::CodeLens,
+  ) -> Result<lsp::CodeLens, jsonrpc::Error> {
+    self.0.code_lens_resolve(params).await
+  }
+
   async fn completion(
     &self,
     params: lsp::CompletionParams,
@@ -119,6 +160,13 @@ impl LanguageServer for Server {
     }
   }
 
+  async fn did_change_configuration(
+    &self,
+    params: lsp::DidChangeConfigurationParams,
+  ) {
+    self.0.did_change_configuration(params).await;
+  }
+
   async fn did_close(&self, params: lsp::DidCloseTextDocumentParams) {
     self.0.did_close(params).await;
   }
@@ -228,6 +276,20 @@ impl LanguageServer for Server {
     self.0.semantic_tokens_full(params).await
   }
 
+  async fn semantic_tokens_full_delta(
+    &self,
+    params: lsp::SemanticTokensDeltaParams,
+  ) -> Result<Option<lsp::SemanticTokensFullDeltaResult>, jsonrpc::Error> {
+    self.0.semantic_tokens_full_delta(params).await
+  }
+
+  async fn semantic_tokens_range(
+    &self,
+    params: lsp::SemanticTokensRangeParams,
+  ) -> Result<Option<lsp::SemanticTokensRangeResult>, jsonrpc::Error> {
+    self.0.semantic_tokens_range(params).await
+  }
+
   #[allow(clippy::unused_async)]
   async fn shutdown(&self) -> Result<(), jsonrpc::Error> {
     self.0.shutdown().await
@@ -237,11 +299,50 @@ impl LanguageServer for Server {
 pub(crate) struct Inner {
   client: Client,
   config: RwLock<Config>,
+  creates_files: AtomicBool,
   initialized: AtomicBool,
+  runs: Arc<RwLock<RecipeRuns>>,
+  semantic_tokens: RwLock<HashMap<lsp::Url, lsp::SemanticTokens>>,
+  streams_recipe_output: AtomicBool,
   workspace: RwLock<Workspace>,
 }
 
 impl Inner {
+  /// Retains `text` as output of run `run_id`, and either inserts it at a
+  /// line of the run's virtual document or notifies the client of it.
+  async fn append_output(
+    client: &Client,
+    runs: &RwLock<RecipeRuns>,
+    run_id: u64,
+    document: Option<(&lsp::Url, u32)>,
+    text: &str,
+  ) {
+    let chunk = runs.write().await.append(run_id, text);
+
+    let Some((document_uri, line)) = document else {
+      client
+        .send_notification::<RecipeOutputAppended>(chunk)
+        .await;
+      return;
+    };
+
+    let changes = HashMap::from([(
+      document_uri.clone(),
+      vec![lsp::TextEdit {
+        range: lsp::Range::at(line, 0, line, 0),
+        new_text: text.trim().into(),
+      }],
+    )]);
+
+    client
+      .apply_edit(lsp::WorkspaceEdit {
+        changes: Some(changes),
+        ..Default::default()
+      })
+      .await
+      .ok();
+  }
+
   async fn code_action(
     &self,
     params: lsp::CodeActionParams,
@@ -271,17 +372,51 @@ impl Inner {
         .map(ParameterJson::from)
         .collect::<Vec<_>>();
 
+      let arguments = vec![
+        json(&recipe.name.value)?,
+        json(&params.text_document.uri)?,
+        json(parameters)?,
+      ];
+
       actions.push(lsp::CodeActionOrCommand::CodeAction(lsp::CodeAction {
         title: title.clone(),
         kind: Some(lsp::CodeActionKind::SOURCE),
         command: Some(lsp::Command {
           title,
           command: Command::RunRecipe.to_string(),
-          arguments: Some(vec![
-            json(&recipe.name.value)?,
-            json(&params.text_document.uri)?,
-            json(parameters)?,
-          ]),
+          arguments: Some(arguments.clone()),
+        }),
+        ..Default::default()
+      }));
+
+      let title = format!("Dry run {}", recipe.name.value);
+
+      actions.push(lsp::CodeActionOrCommand::CodeAction(lsp::CodeAction {
+        title: title.clone(),
+        kind: Some(lsp::CodeActionKind::SOURCE),
+        command: Some(lsp::Command {
+          title,
+          command: Command::DryRunRecipe.to_string(),
+          arguments: Some(arguments),
+        }),
+        ..Default::default()
+      }));
+    }
+
+    if document
+      .variables()
+      .iter()
+      .any(|variable| variable.name.range.overlaps(params.range))
+    {
+      let title = String::from("Evaluate variables");
+
+      actions.push(lsp::CodeActionOrCommand::CodeAction(lsp::CodeAction {
+        title: title.clone(),
+        kind: Some(lsp::CodeActionKind::SOURCE),
+        command: Some(lsp::Command {
+          title,
+          command: Command::Evaluate.to_string(),
+          arguments: Some(vec![json(&params.text_document.uri)?]),
         }),
         ..Default::default()
       }));
@@ -296,6 +431,9 @@ impl Inner {
     actions.extend(
       Quickfixer {
         config: Some(&config),
+        creates_files: self
+          .creates_files
+          .load(std::sync::atomic::Ordering::Relaxed),
         document,
         imported_documents: imported_documents.collect(),
         parameters: &params,
@@ -338,18 +476,60 @@ impl Inner {
           command: Some(lsp::Command {
             title: "Run".into(),
             command: Command::RunRecipe.to_string(),
+            arguments: Some(vec![
+              recipe_name.clone(),
+              uri.clone(),
+              parameters.clone(),
+            ]),
+          }),
+          data: None,
+        });
+
+        lenses.push(lsp::CodeLens {
+          range: recipe.name.range,
+          command: Some(lsp::Command {
+            title: "Dry run".into(),
+            command: Command::DryRunRecipe.to_string(),
             arguments: Some(vec![recipe_name, uri, parameters]),
           }),
           data: None,
         });
       }
 
+      if let Some(variable) = document.variables().first() {
+        lenses.push(lsp::CodeLens {
+          range: variable.name.range,
+          command: Some(lsp::Command {
+            title: "Evaluate".into(),
+            command: Command::Evaluate.to_string(),
+            arguments: Some(vec![
+              serde_json::to_value(uri)
+                .map_err(|_| jsonrpc::Error::parse_error())?,
+            ]),
+          }),
+          data: None,
+        });
+      }
+
+      lenses.extend(CodeLenses::new(&workspace, document).lenses());
+
+      lenses.sort_by_key(|lens| lens.range.start);
+
       return Ok(Some(lenses));
     }
 
     Ok(None)
   }
 
+  async fn code_lens_resolve(
+    &self,
+    params: lsp::CodeLens,
+  ) -> Result<lsp::CodeLens, jsonrpc::Error> {
+    let workspace = self.workspace.read().await;
+
+    Ok(CodeLenses::resolve(&workspace, params))
+  }
+
   async fn completion(
     &self,
     params: lsp::CompletionParams,
@@ -449,6 +629,12 @@ impl Inner {
       workspace.documents.change(params)?;
       workspace.load_projects(roots.iter().cloned())?;
 
+      let mut semantic_tokens = self.semantic_tokens.write().await;
+
+      for root in roots.iter().filter(|root| **root != uri) {
+        semantic_tokens.remove(root);
+      }
+
       roots
     };
 
@@ -459,6 +645,38 @@ impl Inner {
     Ok(())
   }
 
+  /// Replaces the configuration with the client's settings, which may be
+  /// nested under a `just-lsp` section, and republishes diagnostics.
+  async fn did_change_configuration(
+    &self,
+    params: lsp::DidChangeConfigurationParams,
+  ) {
+    if params.settings.is_null() {
+      return;
+    }
+
+    let settings = params
+      .settings
+      .get("just-lsp")
+      .cloned()
+      .unwrap_or(params.settings);
+
+    self.load_config(settings).await;
+
+    let roots = self
+      .workspace
+      .read()
+      .await
+      .projects
+      .keys()
+      .cloned()
+      .collect::<Vec<_>>();
+
+    for root in roots {
+      self.publish_diagnostics(&root).await;
+    }
+  }
+
   async fn did_close(&self, params: lsp::DidCloseTextDocumentParams) {
     let uri = params.text_document.uri.clone();
 
@@ -468,6 +686,8 @@ impl Inner {
 
       let closed = workspace.documents.close(&params);
 
+      self.semantic_tokens.write().await.remove(&uri);
+
       workspace.projects.remove(&uri);
       roots.remove(&uri);
 
@@ -498,6 +718,8 @@ impl Inner {
 
       roots.insert(uri.clone());
 
+      self.semantic_tokens.write().await.remove(&uri);
+
       workspace.documents.open(params)?;
       workspace.load_projects(roots.iter().cloned())?;
 
@@ -585,6 +807,8 @@ impl Inner {
       }
     }
 
+    links.extend(DocumentLinks::new(document).links());
+
     Ok(Some(links))
   }
 
@@ -596,100 +820,25 @@ impl Inner {
 
     let workspace = self.workspace.read().await;
 
-    if let Some(document) = workspace.documents.get_open(uri) {
-      let mut symbols = Vec::new();
-
-      for recipe in document.recipes() {
-        #[allow(deprecated)]
-        symbols.push(lsp::DocumentSymbol {
-          name: recipe.name.value,
-          detail: None,
-          kind: lsp::SymbolKind::FUNCTION,
-          tags: None,
-          deprecated: None,
-          range: recipe.range,
-          selection_range: recipe.name.range,
-          children: None,
-        });
-      }
-
-      for alias in document.aliases() {
-        #[allow(deprecated)]
-        symbols.push(lsp::DocumentSymbol {
-          name: alias.name.value,
-          detail: Some(format!("alias for {}", alias.value.value)),
-          kind: lsp::SymbolKind::FUNCTION,
-          tags: None,
-          deprecated: None,
-          range: alias.range,
-          selection_range: alias.name.range,
-          children: None,
-        });
-      }
-
-      for variable in document.variables() {
-        #[allow(deprecated)]
-        symbols.push(lsp::DocumentSymbol {
-          name: variable.name.value,
-          detail: None,
-          kind: lsp::SymbolKind::VARIABLE,
-          tags: None,
-          deprecated: None,
-          range: variable.range,
-          selection_range: variable.name.range,
-          children: None,
-        });
-      }
-
-      for function in document.functions() {
-        let parameters = function
-          .parameters
-          .iter()
-          .map(|parameter| parameter.value.as_str())
-          .collect::<Vec<_>>()
-          .join(", ");
-
-        #[allow(deprecated)]
-        symbols.push(lsp::DocumentSymbol {
-          name: function.name.value,
-          detail: Some(format!("({parameters})")),
-          kind: lsp::SymbolKind::FUNCTION,
-          tags: None,
-          deprecated: None,
-          range: function.range,
-          selection_range: function.name.range,
-          children: None,
-        });
-      }
-
-      for setting in document.settings() {
-        #[allow(deprecated)]
-        symbols.push(lsp::DocumentSymbol {
-          name: setting.name.value,
-          detail: Some(setting.kind.to_string()),
-          kind: lsp::SymbolKind::PROPERTY,
-          tags: None,
-          deprecated: None,
-          range: setting.range,
-          selection_range: setting.range,
-          children: None,
-        });
-      }
-
-      symbols.sort_by_key(|s| s.range.start);
-
-      return Ok(Some(lsp::DocumentSymbolResponse::Nested(symbols)));
-    }
-
-    Ok(None)
+    Ok(workspace.documents.get_open(uri).map(|document| {
+      lsp::DocumentSymbolResponse::Nested(Outline::new(document).symbols())
+    }))
   }
 
   async fn execute_command(
     &self,
     params: lsp::ExecuteCommandParams,
   ) -> Result<Option<serde_json::Value>, jsonrpc::Error> {
-    match Command::try_from(params.command.as_str()) {
-      Ok(Command::RunRecipe) => {
+    let directory = |uri: &lsp::Url| {
+      uri
+        .to_file_path()
+        .ok()
+        .and_then(|path| path.parent().map(std::path::Path::to_path_buf))
+        .unwrap_or_default()
+    };
+
+    let run_id = match Command::try_from(params.command.as_str()) {
+      Ok(command @ (Command::DryRunRecipe | Command::RunRecipe)) => {
         let recipe_name = params
           .arguments
           .first()
@@ -708,13 +857,7 @@ impl Inner {
         if let (Some(recipe_name), Some(uri), Some(parameters)) =
           (recipe_name, uri, parameters)
         {
-          let path = uri
-            .to_file_path()
-            .ok()
-            .and_then(|path| path.parent().map(std::path::Path::to_path_buf))
-            .unwrap_or(PathBuf::new());
-
-          let recipe_arguments = Vec::new();
+          let path = directory(&uri);
 
           if !parameters.is_empty() {
             self.client.show_message(
@@ -726,18 +869,79 @@ impl Inner {
             return Ok(None);
           }
 
-          self.run_recipe(recipe_name, recipe_arguments, path).await;
+          let (output_uri, arguments) = if command == Command::DryRunRecipe {
+            (
+              Self::output_uri(&path, recipe_name, Some("dry-run")),
+              vec!["--dry-run".into(), recipe_name.into()],
+            )
+          } else {
+            (
+              Self::output_uri(&path, recipe_name, None),
+              vec![recipe_name.into()],
+            )
+          };
+
+          Some(self.run_just(output_uri, arguments, path).await)
+        } else {
+          None
+        }
+      }
+      Ok(Command::Evaluate) => {
+        if let Some(uri) = params
+          .arguments
+          .first()
+          .and_then(|uri| uri.as_str())
+          .and_then(|uri| lsp::Url::parse(uri).ok())
+        {
+          let path = directory(&uri);
+
+          Some(
+            self
+              .run_just(
+                Self::output_uri(&path, "", Some("evaluate")),
+                vec!["--evaluate".into()],
+                path,
+              )
+              .await,
+          )
+        } else {
+          None
         }
       }
+      Ok(Command::ShowLocations) => {
+        if let Some(location) = params
+          .arguments
+          .first()
+          .and_then(|locations| {
+            serde_json::from_value::<Vec<lsp::Location>>(locations.clone()).ok()
+          })
+          .and_then(|locations| locations.into_iter().next())
+        {
+          self
+            .client
+            .show_document(lsp::ShowDocumentParams {
+              uri: location.uri,
+              external: Some(false),
+              take_focus: Some(true),
+              selection: Some(location.range),
+            })
+            .await
+            .ok();
+        }
+
+        None
+      }
       Err(error) => {
         self
           .client
           .show_message(lsp::MessageType::ERROR, error)
           .await;
+
+        None
       }
-    }
+    };
 
-    Ok(None)
+    Ok(run_id.map(|run_id| serde_json::json!({ "runId": run_id })))
   }
 
   async fn folding_range(
@@ -748,38 +952,12 @@ impl Inner {
 
     let workspace = self.workspace.read().await;
 
-    if let Some(document) = workspace.documents.get_open(uri) {
-      let recipes = document.recipes();
-
-      let folding_ranges = recipes
-        .into_iter()
-        .map(|recipe| {
-          let start_line = recipe.range.start.line;
-
-          let end_line = recipe.range.end.line;
-
-          if end_line > start_line {
-            lsp::FoldingRange {
-              start_line,
-              end_line: end_line.saturating_sub(1),
-              kind: Some(lsp::FoldingRangeKind::Region),
-              ..Default::default()
-            }
-          } else {
-            lsp::FoldingRange {
-              start_line,
-              end_line: start_line,
-              kind: Some(lsp::FoldingRangeKind::Region),
-              ..Default::default()
-            }
-          }
-        })
-        .collect();
-
-      return Ok(Some(folding_ranges));
-    }
-
-    Ok(None)
+    Ok(
+      workspace
+        .documents
+        .get_open(uri)
+        .map(|document| FoldingRanges::new(document).ranges()),
+    )
   }
 
   async fn formatting(
@@ -871,6 +1049,30 @@ impl Inner {
 
     let position = params.text_document_position_params.position;
 
+    let evaluation = if self.config.read().await.hover.evaluate {
+      let workspace = self.workspace.read().await;
+
+      workspace.project_view(&uri).and_then(|view| {
+        let identifier = view
+          .document()
+          .node_at_position(position)
+          .filter(|node| node.kind() == "identifier")?;
+
+        Resolver::new(view).evaluation(&identifier)
+      })
+    } else {
+      None
+    };
+
+    // `just` runs without any lock held, so a slow or hanging evaluation
+    // doesn't hold up edits.
+    let evaluated = match evaluation {
+      Some((justfile, name)) => Resolver::just_evaluate(&justfile, &name)
+        .await
+        .map(|value| (name, value)),
+      None => None,
+    };
+
     let workspace = self.workspace.read().await;
 
     Ok(workspace.project_view(&uri).and_then(|view| {
@@ -879,7 +1081,13 @@ impl Inner {
         .node_at_position(position)
         .filter(|node| node.kind() == "identifier")?;
 
-      Resolver::new(view).resolve_identifier_hover(&identifier)
+      let resolver = Resolver::new(view);
+
+      match evaluated {
+        Some((name, value)) => resolver.evaluated(name, value),
+        None => resolver,
+      }
+      .resolve_identifier_hover(&identifier)
     }))
   }
 
@@ -889,13 +1097,34 @@ impl Inner {
   ) -> Result<lsp::InitializeResult, jsonrpc::Error> {
     info!("Starting just language server...");
 
+    let streams_recipe_output = params
+      .capabilities
+      .experimental
+      .as_ref()
+      .and_then(|experimental| experimental.get("recipeOutput"))
+      .and_then(Value::as_bool)
+      .unwrap_or_default();
+
+    self
+      .streams_recipe_output
+      .store(streams_recipe_output, std::sync::atomic::Ordering::Relaxed);
+
+    let creates_files = params
+      .capabilities
+      .workspace
+      .as_ref()
+      .and_then(|workspace| workspace.workspace_edit.as_ref())
+      .and_then(|workspace_edit| workspace_edit.resource_operations.as_ref())
+      .is_some_and(|operations| {
+        operations.contains(&lsp::ResourceOperationKind::Create)
+      });
+
+    self
+      .creates_files
+      .store(creates_files, std::sync::atomic::Ordering::Relaxed);
+
     if let Some(options) = params.initialization_options {
-      match serde_json::from_value::<Config>(options) {
-        Ok(config) => *self.config.write().await = config,
-        Err(error) => {
-          warn!(%error, "failed to parse initialization options");
-        }
-      }
+      self.load_config(options).await;
     }
 
     Ok(lsp::InitializeResult {
@@ -921,15 +1150,58 @@ impl Inner {
       .store(true, std::sync::atomic::Ordering::Relaxed);
   }
 
+  /// Replaces the configuration with `value`, warning about every custom
+  /// rule dropped because it's invalid.
+  async fn load_config(&self, value: Value) {
+    match Config::load(value) {
+      Ok((config, errors)) => {
+        for error in errors {
+          self
+            .client
+            .show_message(lsp::MessageType::WARNING, error)
+            .await;
+        }
+
+        *self.config.write().await = config;
+      }
+      Err(error) => {
+        warn!(%error, "failed to parse configuration");
+      }
+    }
+  }
+
   fn new(client: Client) -> Self {
     Self {
       client,
       config: RwLock::new(Config::default()),
+      creates_files: AtomicBool::new(false),
       initialized: AtomicBool::new(false),
+      runs: Arc::new(RwLock::new(RecipeRuns::default())),
+      semantic_tokens: RwLock::new(HashMap::new()),
+      streams_recipe_output: AtomicBool::new(false),
       workspace: RwLock::new(Workspace::default()),
     }
   }
 
+  /// The `just-recipe:` virtual document that output from running `name`
+  /// in `directory` is written to, with `query` distinguishing other kinds
+  /// of invocation.
+  fn output_uri(
+    directory: &std::path::Path,
+    name: &str,
+    query: Option<&str>,
+  ) -> lsp::Url {
+    let mut uri = format!("just-recipe:/{}/{name}", directory.display());
+
+    if let Some(query) = query {
+      uri.push('?');
+      uri.push_str(query);
+    }
+
+    lsp::Url::parse(&uri)
+      .unwrap_or_else(|_| lsp::Url::parse("just-recipe:/output").unwrap())
+  }
+
   async fn prepare_rename(
     &self,
     params: lsp::TextDocumentPositionParams,
@@ -962,15 +1234,17 @@ impl Inner {
 
       match workspace.documents.get_open(uri) {
         Some(document) => {
-          let imported_documents =
-            workspace.projects.get(uri).into_iter().flat_map(|project| {
-              project.imported_documents(&workspace.documents)
-            });
+          let project = workspace.projects.get(uri);
+
+          let imported_documents = project.into_iter().flat_map(|project| {
+            project.imported_documents(&workspace.documents)
+          });
 
           let analyzer = Analyzer {
             config: Some(&config),
             document,
             imported_documents: imported_documents.collect(),
+            project,
           };
 
           (
@@ -992,6 +1266,22 @@ impl Inner {
       .await;
   }
 
+  async fn recipe_output(
+    &self,
+    params: RecipeOutputParams,
+  ) -> Result<Option<RecipeOutput>, jsonrpc::Error> {
+    Ok(self.runs.read().await.get(params.run_id))
+  }
+
+  async fn recipes(
+    &self,
+    params: lsp::TextDocumentIdentifier,
+  ) -> Result<Vec<RecipeJson>, jsonrpc::Error> {
+    let workspace = self.workspace.read().await;
+
+    Ok(RecipeCatalog::new(&workspace).recipes(&params.uri))
+  }
+
   async fn references(
     &self,
     params: lsp::ReferenceParams,
@@ -1049,26 +1339,24 @@ impl Inner {
     }))
   }
 
-  async fn run_recipe(
+  /// Runs `just` with `arguments` in `directory`, returning the id its
+  /// output is retained under.
+  ///
+  /// Clients that declare the `recipeOutput` experimental capability are
+  /// sent `just-lsp/recipeOutputAppended` notifications as output arrives,
+  /// while others have it streamed into the virtual document at
+  /// `document_uri`.
+  async fn run_just(
     &self,
-    recipe_name: &str,
-    recipe_arguments: Vec<String>,
+    document_uri: lsp::Url,
+    arguments: Vec<String>,
     directory: PathBuf,
-  ) {
-    let document_uri = lsp::Url::parse(&format!(
-      "just-recipe:/{}/{}",
-      directory.display(),
-      recipe_name
-    ))
-    .unwrap_or_else(|_| lsp::Url::parse("just-recipe:/output").unwrap());
-
+  ) -> u64 {
     let mut command = tokio::process::Command::new("just");
 
-    command.arg(recipe_name);
+    let label = format!("`just {}`", arguments.join(" "));
 
-    for argument in recipe_arguments {
-      command.arg(argument);
-    }
+    command.args(arguments);
 
     command
       .current_dir(directory.clone())
@@ -1077,37 +1365,48 @@ impl Inner {
 
     let client = self.client.clone();
 
-    client
-      .show_document(lsp::ShowDocumentParams {
-        uri: document_uri.clone(),
-        external: Some(false),
-        take_focus: Some(true),
-        selection: None,
-      })
-      .await
-      .ok();
+    let runs = self.runs.clone();
 
-    let changes = HashMap::from([(
-      document_uri.clone(),
-      vec![lsp::TextEdit {
-        range: lsp::Range::at(0, 0, u32::MAX, 0),
-        new_text: String::new(),
-      }],
-    )]);
+    let run_id = runs.write().await.start();
 
-    client
-      .apply_edit(lsp::WorkspaceEdit {
-        changes: Some(changes),
-        ..Default::default()
-      })
-      .await
-      .ok();
+    let document_uri = (!self
+      .streams_recipe_output
+      .load(std::sync::atomic::Ordering::Relaxed))
+    .then_some(document_uri);
 
-    let recipe_name = recipe_name.to_string();
+    if let Some(document_uri) = &document_uri {
+      client
+        .show_document(lsp::ShowDocumentParams {
+          uri: document_uri.clone(),
+          external: Some(false),
+          take_focus: Some(true),
+          selection: None,
+        })
+        .await
+        .ok();
+
+      let changes = HashMap::from([(
+        document_uri.clone(),
+        vec![lsp::TextEdit {
+          range: lsp::Range::at(0, 0, u32::MAX, 0),
+          new_text: String::new(),
+        }],
+      )]);
 
-    tokio::spawn(async move {
-      match command.spawn() {
-        Ok(mut child) => {
+      client
+        .apply_edit(lsp::WorkspaceEdit {
+          changes: Some(changes),
+          ..Default::default()
+        })
+        .await
+        .ok();
+    }
+
+    tokio::spawn(async move {
+      let document_uri = document_uri.as_ref();
+
+      let exit_code = match command.spawn() {
+        Ok(mut child) => {
           let stdout_lines = LinesStream::new(
             tokio::io::BufReader::new(
               child.stdout.take().expect("Failed to capture stdout"),
@@ -1141,21 +1440,14 @@ impl Inner {
                   || buffer.len() > 1024)
                   && !buffer.is_empty()
                 {
-                  let changes = HashMap::from([(
-                    document_uri.clone(),
-                    vec![lsp::TextEdit {
-                      range: lsp::Range::at(current_line, 0, current_line, 0),
-                      new_text: buffer.trim().into(),
-                    }],
-                  )]);
-
-                  client
-                    .apply_edit(lsp::WorkspaceEdit {
-                      changes: Some(changes),
-                      ..Default::default()
-                    })
-                    .await
-                    .ok();
+                  Self::append_output(
+                    &client,
+                    &runs,
+                    run_id,
+                    document_uri.map(|uri| (uri, current_line)),
+                    &buffer,
+                  )
+                  .await;
 
                   let newlines = u32::try_from(buffer.matches('\n').count())
                     .expect("line count exceeds u32::MAX");
@@ -1174,21 +1466,14 @@ impl Inner {
           }
 
           if !buffer.is_empty() {
-            let changes = HashMap::from([(
-              document_uri.clone(),
-              vec![lsp::TextEdit {
-                range: lsp::Range::at(current_line, 0, current_line, 0),
-                new_text: buffer.trim().into(),
-              }],
-            )]);
-
-            client
-              .apply_edit(lsp::WorkspaceEdit {
-                changes: Some(changes),
-                ..Default::default()
-              })
-              .await
-              .ok();
+            Self::append_output(
+              &client,
+              &runs,
+              run_id,
+              document_uri.map(|uri| (uri, current_line)),
+              &buffer,
+            )
+            .await;
           }
 
           match child.wait().await {
@@ -1197,18 +1482,24 @@ impl Inner {
                 client
                   .show_message(
                     lsp::MessageType::WARNING,
-                    format!("Recipe '{recipe_name}' completed with non-zero exit code: {status}"),
+                    format!(
+                      "{label} completed with non-zero exit code: {status}"
+                    ),
                   )
                   .await;
               }
+
+              status.code()
             }
             Err(error) => {
               client
                 .show_message(
                   lsp::MessageType::ERROR,
-                  format!("Error waiting for recipe '{recipe_name}': {error}"),
+                  format!("Error waiting for {label}: {error}"),
                 )
                 .await;
+
+              None
             }
           }
         }
@@ -1216,47 +1507,166 @@ impl Inner {
           client
             .show_message(
               lsp::MessageType::ERROR,
-              format!("Failed to run recipe '{recipe_name}': {error}"),
+              format!("Failed to run {label}: {error}"),
             )
             .await;
+
+          None
         }
+      };
+
+      let chunk = runs.write().await.finish(run_id, exit_code);
+
+      if document_uri.is_none() {
+        client
+          .send_notification::<RecipeOutputAppended>(chunk)
+          .await;
       }
     });
+
+    run_id
+  }
+
+  /// The semantic tokens of the open document at `uri`, reusing those
+  /// computed for its current version, whose number is the result id.
+  async fn semantic_tokens(
+    &self,
+    uri: &lsp::Url,
+  ) -> Option<lsp::SemanticTokens> {
+    let workspace = self.workspace.read().await;
+
+    let view = workspace.project_view(uri)?;
+
+    let result_id = view.document().version.to_string();
+
+    if let Some(tokens) = self
+      .semantic_tokens
+      .read()
+      .await
+      .get(uri)
+      .filter(|tokens| tokens.result_id.as_ref() == Some(&result_id))
+    {
+      return Some(tokens.clone());
+    }
+
+    match Tokenizer::new(view).tokenize() {
+      Ok(data) => {
+        let tokens = lsp::SemanticTokens {
+          data,
+          result_id: Some(result_id),
+        };
+
+        self
+          .semantic_tokens
+          .write()
+          .await
+          .insert(uri.clone(), tokens.clone());
+
+        Some(tokens)
+      }
+      Err(error) => {
+        self
+          .client
+          .log_message(
+            lsp::MessageType::ERROR,
+            format!("Failed to compute semantic tokens: {error}"),
+          )
+          .await;
+
+        None
+      }
+    }
   }
 
   async fn semantic_tokens_full(
     &self,
     params: lsp::SemanticTokensParams,
   ) -> Result<Option<lsp::SemanticTokensResult>, jsonrpc::Error> {
+    Ok(
+      self
+        .semantic_tokens(&params.text_document.uri)
+        .await
+        .map(lsp::SemanticTokensResult::Tokens),
+    )
+  }
+
+  async fn semantic_tokens_full_delta(
+    &self,
+    params: lsp::SemanticTokensDeltaParams,
+  ) -> Result<Option<lsp::SemanticTokensFullDeltaResult>, jsonrpc::Error> {
     let uri = params.text_document.uri;
 
+    let previous = self
+      .semantic_tokens
+      .read()
+      .await
+      .get(&uri)
+      .filter(|tokens| {
+        tokens.result_id.as_ref() == Some(&params.previous_result_id)
+      })
+      .cloned();
+
+    let Some(current) = self.semantic_tokens(&uri).await else {
+      return Ok(None);
+    };
+
+    Ok(Some(match previous {
+      Some(previous) => lsp::SemanticTokensFullDeltaResult::TokensDelta(
+        lsp::SemanticTokensDelta {
+          edits: Tokenizer::edits(&previous.data, &current.data),
+          result_id: current.result_id,
+        },
+      ),
+      None => lsp::SemanticTokensFullDeltaResult::Tokens(current),
+    }))
+  }
+
+  async fn semantic_tokens_range(
+    &self,
+    params: lsp::SemanticTokensRangeParams,
+  ) -> Result<Option<lsp::SemanticTokensRangeResult>, jsonrpc::Error> {
     let workspace = self.workspace.read().await;
 
-    if let Some(document) = workspace.documents.get_open(&uri) {
-      let tokenizer = Tokenizer::new(document);
-
-      match tokenizer.tokenize() {
-        Ok(data) => {
-          return Ok(Some(lsp::SemanticTokensResult::Tokens(
-            lsp::SemanticTokens {
-              data,
-              result_id: None,
-            },
-          )));
-        }
-        Err(error) => {
-          self
-            .client
-            .log_message(
-              lsp::MessageType::ERROR,
-              format!("Failed to compute semantic tokens: {error}"),
-            )
-            .await;
-        }
-      }
+    let Some(view) = workspace.project_view(&params.text_document.uri) else {
+      return Ok(None);
+    };
+
+    let result_id = view.document().version.to_string();
+
+    if let Some(tokens) = self
+      .semantic_tokens
+      .read()
+      .await
+      .get(&params.text_document.uri)
+      .filter(|tokens| tokens.result_id.as_ref() == Some(&result_id))
+    {
+      return Ok(Some(lsp::SemanticTokensRangeResult::Tokens(
+        lsp::SemanticTokens {
+          data: Tokenizer::filter_range(&tokens.data, params.range),
+          result_id: None,
+        },
+      )));
     }
 
-    Ok(None)
+    match Tokenizer::new(view).tokenize_range(params.range) {
+      Ok(data) => Ok(Some(lsp::SemanticTokensRangeResult::Tokens(
+        lsp::SemanticTokens {
+          data,
+          result_id: None,
+        },
+      ))),
+      Err(error) => {
+        self
+          .client
+          .log_message(
+            lsp::MessageType::ERROR,
+            format!("Failed to compute semantic tokens: {error}"),
+          )
+          .await;
+
+        Ok(None)
+      }
+    }
   }
 
   #[allow(clippy::unused_async)]
@@ -1286,7 +1696,7 @@ mod tests {
 
   impl Test {
     fn new() -> Self {
-      let (service, _) = LspService::new(Server::new);
+      let (service, _) = Server::service();
 
       Self {
         requests: Vec::new(),
@@ -1894,23 +2304,88 @@ mod tests {
   }
 
   #[derive(Debug)]
-  struct SemanticTokensResponse {
-    data: Vec<u32>,
+  struct SemanticTokensDeltaRequest<'a> {
     id: i64,
+    previous_result_id: &'a str,
+    uri: &'a str,
   }
 
-  impl IntoValue for SemanticTokensResponse {
+  impl IntoValue for SemanticTokensDeltaRequest<'_> {
     fn into_value(self) -> Value {
       json!({
         "jsonrpc": "2.0",
         "id": self.id,
-        "result": {
-          "data": self.data,
+        "method": "textDocument/semanticTokens/full/delta",
+        "params": {
+          "textDocument": {
+            "uri": self.uri
+          },
+          "previousResultId": self.previous_result_id
         }
       })
     }
   }
 
+  #[derive(Debug)]
+  struct SemanticTokensRangeRequest<'a> {
+    end_char: u32,
+    end_line: u32,
+    id: i64,
+    start_char: u32,
+    start_line: u32,
+    uri: &'a str,
+  }
+
+  impl IntoValue for SemanticTokensRangeRequest<'_> {
+    fn into_value(self) -> Value {
+      json!({
+        "jsonrpc": "2.0",
+        "id": self.id,
+        "method": "textDocument/semanticTokens/range",
+        "params": {
+          "textDocument": {
+            "uri": self.uri
+          },
+          "range": {
+            "start": {
+              "line": self.start_line,
+              "character": self.start_char
+            },
+            "end": {
+              "line": self.end_line,
+              "character": self.end_char
+            }
+          }
+        }
+      })
+    }
+  }
+
+  #[derive(Debug)]
+  struct SemanticTokensResponse<'a> {
+    data: Vec<u32>,
+    id: i64,
+    result_id: Option<&'a str>,
+  }
+
+  impl IntoValue for SemanticTokensResponse<'_> {
+    fn into_value(self) -> Value {
+      let mut result = json!({
+        "data": self.data,
+      });
+
+      if let Some(result_id) = self.result_id {
+        result["resultId"] = json!(result_id);
+      }
+
+      json!({
+        "jsonrpc": "2.0",
+        "id": self.id,
+        "result": result
+      })
+    }
+  }
+
   #[derive(Debug)]
   struct FoldingRange<'a> {
     end_line: u32,
@@ -2027,6 +2502,7 @@ mod tests {
     arguments: Vec<ParameterJson>,
     command: Command,
     kind: &'static str,
+    recipe: &'static str,
     title: &'static str,
   }
 
@@ -2041,7 +2517,7 @@ mod tests {
 
   impl IntoValue for CodeAction {
     fn into_value(self) -> Value {
-      let recipe_name = json!(self.title);
+      let recipe_name = json!(self.recipe);
 
       let uri = json!("file:///test.just");
 
@@ -2066,12 +2542,12 @@ mod tests {
   }
 
   #[derive(Debug)]
-  struct CodeLensRequest {
+  struct CodeLensRequest<'a> {
     id: i64,
-    uri: &'static str,
+    uri: &'a str,
   }
 
-  impl IntoValue for CodeLensRequest {
+  impl IntoValue for CodeLensRequest<'_> {
     fn into_value(self) -> Value {
       json!({
         "jsonrpc": "2.0",
@@ -2204,7 +2680,34 @@ mod tests {
       .response(json!({
         "jsonrpc": "2.0",
         "id": 2,
-        "result": []
+        "result": [
+          {
+            "title": "Evaluate variables",
+            "kind": "source",
+            "command": {
+              "title": "Evaluate variables",
+              "command": "just-lsp.evaluate",
+              "arguments": ["file:///test.just"]
+            }
+          },
+          {
+            "title": "Remove unused variable `foo`",
+            "kind": "quickfix",
+            "edit": {
+              "changes": {
+                "file:///test.just": [
+                  {
+                    "range": {
+                      "start": { "line": 0, "character": 0 },
+                      "end": { "line": 1, "character": 0 }
+                    },
+                    "newText": ""
+                  }
+                ]
+              }
+            }
+          }
+        ]
       }))
       .run()
       .await
@@ -2276,6 +2779,19 @@ mod tests {
 
   #[tokio::test]
   async fn code_action_with_recipes() -> Result {
+    let bar_parameters = vec![
+      ParameterJson {
+        name: "arg1".into(),
+        default_value: None,
+        variadic: None,
+      },
+      ParameterJson {
+        name: "arg2".into(),
+        default_value: Some("'default'".to_string()),
+        variadic: None,
+      },
+    ];
+
     Test::new()
       .request(InitializeRequest { id: 1 })
       .response(InitializeResponse { id: 1 })
@@ -2303,22 +2819,29 @@ mod tests {
             title: "foo",
             kind: "source",
             command: Command::RunRecipe,
+            recipe: "foo",
+            arguments: vec![],
+          },
+          CodeAction {
+            title: "Dry run foo",
+            kind: "source",
+            command: Command::DryRunRecipe,
+            recipe: "foo",
             arguments: vec![],
           },
           CodeAction {
             title: "bar",
             kind: "source",
             command: Command::RunRecipe,
-            arguments: vec![
-              ParameterJson {
-                name: "arg1".into(),
-                default_value: None,
-              },
-              ParameterJson {
-                name: "arg2".into(),
-                default_value: Some("'default'".to_string()),
-              },
-            ],
+            recipe: "bar",
+            arguments: bar_parameters.clone(),
+          },
+          CodeAction {
+            title: "Dry run bar",
+            kind: "source",
+            command: Command::DryRunRecipe,
+            recipe: "bar",
+            arguments: bar_parameters,
           },
         ],
       })
@@ -2348,6 +2871,384 @@ mod tests {
       .await
   }
 
+  #[tokio::test]
+  async fn code_lens_evaluate() -> Result {
+    Test::new()
+      .request(InitializeRequest { id: 1 })
+      .response(InitializeResponse { id: 1 })
+      .notification(DidOpenNotification {
+        uri: "file:///test.just",
+        text: "x := 'a'\ny := 'b'\n",
+      })
+      .request(CodeLensRequest {
+        id: 2,
+        uri: "file:///test.just",
+      })
+      .response(json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "result": [
+          {
+            "range": lsp::Range::at(0, 0, 0, 1),
+            "command": {
+              "title": "Evaluate",
+              "command": "just-lsp.evaluate",
+              "arguments": ["file:///test.just"]
+            }
+          },
+          {
+            "range": lsp::Range::at(0, 0, 0, 1),
+            "data": {
+              "position": { "line": 0, "character": 0 },
+              "uri": "file:///test.just"
+            }
+          },
+          {
+            "range": lsp::Range::at(1, 0, 1, 1),
+            "data": {
+              "position": { "line": 1, "character": 0 },
+              "uri": "file:///test.just"
+            }
+          }
+        ]
+      }))
+      .run()
+      .await
+  }
+
+  #[derive(Debug)]
+  struct CodeLensResolveRequest {
+    id: i64,
+    lens: Value,
+  }
+
+  impl IntoValue for CodeLensResolveRequest {
+    fn into_value(self) -> Value {
+      json!({
+        "jsonrpc": "2.0",
+        "id": self.id,
+        "method": "codeLens/resolve",
+        "params": self.lens
+      })
+    }
+  }
+
+  #[tokio::test]
+  async fn code_lens_overridden_recipe() -> Result {
+    let tempdir = tempfile::tempdir()?;
+
+    let root =
+      lsp::Url::from_file_path(tempdir.path().join("justfile")).unwrap();
+
+    let imported =
+      lsp::Url::from_file_path(tempdir.path().join("foo.just")).unwrap();
+
+    std::fs::write(imported.to_file_path().unwrap(), "foo:\n  echo foo\n")?;
+
+    Test::new()
+      .request(InitializeRequest { id: 1 })
+      .response(InitializeResponse { id: 1 })
+      .notification(DidOpenNotification {
+        uri: root.as_str(),
+        text: "import 'foo.just'\n\nfoo:\n  echo bar\n",
+      })
+      .notification(DidOpenNotification {
+        uri: imported.as_str(),
+        text: "foo:\n  echo foo\n",
+      })
+      .request(CodeLensRequest {
+        id: 2,
+        uri: imported.as_str(),
+      })
+      .response(json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "result": [
+          {
+            "range": lsp::Range::at(0, 0, 0, 3),
+            "command": {
+              "title": "Run",
+              "command": "just-lsp.run_recipe",
+              "arguments": ["foo", imported, []]
+            }
+          },
+          {
+            "range": lsp::Range::at(0, 0, 0, 3),
+            "command": {
+              "title": "Dry run",
+              "command": "just-lsp.dry_run_recipe",
+              "arguments": ["foo", imported, []]
+            }
+          },
+          {
+            "range": lsp::Range::at(0, 0, 0, 3),
+            "data": {
+              "position": { "line": 0, "character": 0 },
+              "uri": imported
+            }
+          },
+          {
+            "range": lsp::Range::at(0, 0, 0, 3),
+            "command": {
+              "title": "overridden in justfile",
+              "command": "just-lsp.show_locations",
+              "arguments": [[
+                { "uri": root, "range": lsp::Range::at(2, 0, 2, 3) }
+              ]]
+            }
+          }
+        ]
+      }))
+      .run()
+      .await
+  }
+
+  #[tokio::test]
+  async fn code_lens_resolve_counts_references() -> Result {
+    Test::new()
+      .request(InitializeRequest { id: 1 })
+      .response(InitializeResponse { id: 1 })
+      .notification(DidOpenNotification {
+        uri: "file:///test.just",
+        text: indoc! {
+          "
+          x := 'x'
+
+          foo:
+            echo {{x}}
+
+          bar: foo
+
+          baz: foo bar
+            echo {{x}}
+          "
+        },
+      })
+      .request(CodeLensResolveRequest {
+        id: 2,
+        lens: json!({
+          "range": lsp::Range::at(2, 0, 2, 3),
+          "data": {
+            "position": { "line": 2, "character": 0 },
+            "uri": "file:///test.just"
+          }
+        }),
+      })
+      .response(json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "result": {
+          "range": lsp::Range::at(2, 0, 2, 3),
+          "command": {
+            "title": "2 references",
+            "command": "just-lsp.show_locations",
+            "arguments": [
+              [
+                {
+                  "uri": "file:///test.just",
+                  "range": lsp::Range::at(5, 5, 5, 8)
+                },
+                {
+                  "uri": "file:///test.just",
+                  "range": lsp::Range::at(7, 5, 7, 8)
+                }
+              ]
+            ]
+          }
+        }
+      }))
+      .request(CodeLensResolveRequest {
+        id: 3,
+        lens: json!({
+          "range": lsp::Range::at(0, 0, 0, 1),
+          "data": {
+            "position": { "line": 0, "character": 0 },
+            "uri": "file:///test.just"
+          }
+        }),
+      })
+      .response(json!({
+        "jsonrpc": "2.0",
+        "id": 3,
+        "result": {
+          "range": lsp::Range::at(0, 0, 0, 1),
+          "command": {
+            "title": "2 references",
+            "command": "just-lsp.show_locations",
+            "arguments": [
+              [
+                {
+                  "uri": "file:///test.just",
+                  "range": lsp::Range::at(3, 9, 3, 10)
+                },
+                {
+                  "uri": "file:///test.just",
+                  "range": lsp::Range::at(8, 9, 8, 10)
+                }
+              ]
+            ]
+          }
+        }
+      }))
+      .run()
+      .await
+  }
+
+  #[tokio::test]
+  async fn code_lens_show_locations_command() -> Result {
+    Test::new()
+      .request(InitializeRequest { id: 1 })
+      .response(InitializeResponse { id: 1 })
+      .notification(DidOpenNotification {
+        uri: "file:///test.just",
+        text: "foo:\n\nbar: foo\n",
+      })
+      .request(json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "workspace/executeCommand",
+        "params": {
+          "command": "just-lsp.show_locations",
+          "arguments": [[
+            {
+              "uri": "file:///test.just",
+              "range": lsp::Range::at(2, 0, 2, 3)
+            }
+          ]]
+        }
+      }))
+      .response(json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "result": null
+      }))
+      .request(json!({
+        "jsonrpc": "2.0",
+        "id": 3,
+        "method": "workspace/executeCommand",
+        "params": {
+          "command": "just-lsp.show_locations",
+          "arguments": [[]]
+        }
+      }))
+      .response(json!({
+        "jsonrpc": "2.0",
+        "id": 3,
+        "result": null
+      }))
+      .run()
+      .await
+  }
+
+  #[tokio::test]
+  async fn code_lens_used_by() -> Result {
+    Test::new()
+      .request(InitializeRequest { id: 1 })
+      .response(InitializeResponse { id: 1 })
+      .notification(DidOpenNotification {
+        uri: "file:///test.just",
+        text: "foo:\n\nbar: foo\n\nbaz: foo\n",
+      })
+      .request(CodeLensRequest {
+        id: 2,
+        uri: "file:///test.just",
+      })
+      .response(json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "result": [
+          {
+            "range": lsp::Range::at(0, 0, 0, 3),
+            "command": {
+              "title": "Run",
+              "command": "just-lsp.run_recipe",
+              "arguments": ["foo", "file:///test.just", []]
+            }
+          },
+          {
+            "range": lsp::Range::at(0, 0, 0, 3),
+            "command": {
+              "title": "Dry run",
+              "command": "just-lsp.dry_run_recipe",
+              "arguments": ["foo", "file:///test.just", []]
+            }
+          },
+          {
+            "range": lsp::Range::at(0, 0, 0, 3),
+            "data": {
+              "position": { "line": 0, "character": 0 },
+              "uri": "file:///test.just"
+            }
+          },
+          {
+            "range": lsp::Range::at(0, 0, 0, 3),
+            "command": {
+              "title": "used by: bar, baz",
+              "command": "just-lsp.show_locations",
+              "arguments": [[
+                {
+                  "uri": "file:///test.just",
+                  "range": lsp::Range::at(2, 0, 2, 3)
+                },
+                {
+                  "uri": "file:///test.just",
+                  "range": lsp::Range::at(4, 0, 4, 3)
+                }
+              ]]
+            }
+          },
+          {
+            "range": lsp::Range::at(2, 0, 2, 3),
+            "command": {
+              "title": "Run",
+              "command": "just-lsp.run_recipe",
+              "arguments": ["bar", "file:///test.just", []]
+            }
+          },
+          {
+            "range": lsp::Range::at(2, 0, 2, 3),
+            "command": {
+              "title": "Dry run",
+              "command": "just-lsp.dry_run_recipe",
+              "arguments": ["bar", "file:///test.just", []]
+            }
+          },
+          {
+            "range": lsp::Range::at(2, 0, 2, 3),
+            "data": {
+              "position": { "line": 2, "character": 0 },
+              "uri": "file:///test.just"
+            }
+          },
+          {
+            "range": lsp::Range::at(4, 0, 4, 3),
+            "command": {
+              "title": "Run",
+              "command": "just-lsp.run_recipe",
+              "arguments": ["baz", "file:///test.just", []]
+            }
+          },
+          {
+            "range": lsp::Range::at(4, 0, 4, 3),
+            "command": {
+              "title": "Dry run",
+              "command": "just-lsp.dry_run_recipe",
+              "arguments": ["baz", "file:///test.just", []]
+            }
+          },
+          {
+            "range": lsp::Range::at(4, 0, 4, 3),
+            "data": {
+              "position": { "line": 4, "character": 0 },
+              "uri": "file:///test.just"
+            }
+          }
+        ]
+      }))
+      .run()
+      .await
+  }
+
   #[tokio::test]
   async fn code_lens_with_recipes() -> Result {
     Test::new()
@@ -2384,6 +3285,27 @@ mod tests {
               "arguments": ["foo", "file:///test.just", []]
             }
           },
+          {
+            "range": {
+              "start": { "line": 0, "character": 0 },
+              "end": { "line": 0, "character": 3 }
+            },
+            "command": {
+              "title": "Dry run",
+              "command": "just-lsp.dry_run_recipe",
+              "arguments": ["foo", "file:///test.just", []]
+            }
+          },
+          {
+            "range": {
+              "start": { "line": 0, "character": 0 },
+              "end": { "line": 0, "character": 3 }
+            },
+            "data": {
+              "position": { "line": 0, "character": 0 },
+              "uri": "file:///test.just"
+            }
+          },
           {
             "range": {
               "start": { "line": 3, "character": 0 },
@@ -2401,6 +3323,34 @@ mod tests {
                 ]
               ]
             }
+          },
+          {
+            "range": {
+              "start": { "line": 3, "character": 0 },
+              "end": { "line": 3, "character": 3 }
+            },
+            "command": {
+              "title": "Dry run",
+              "command": "just-lsp.dry_run_recipe",
+              "arguments": [
+                "bar",
+                "file:///test.just",
+                [
+                  { "name": "arg1", "default_value": null },
+                  { "name": "arg2", "default_value": "'default'" }
+                ]
+              ]
+            }
+          },
+          {
+            "range": {
+              "start": { "line": 3, "character": 0 },
+              "end": { "line": 3, "character": 3 }
+            },
+            "data": {
+              "position": { "line": 3, "character": 0 },
+              "uri": "file:///test.just"
+            }
           }
         ]
       }))
@@ -2429,6 +3379,89 @@ mod tests {
     }
   }
 
+  #[tokio::test]
+  async fn did_change_configuration_validates_and_republishes() -> Result {
+    let (service, mut socket) = Server::service();
+
+    let mut service = Spawn::new(service);
+
+    service
+      .call(serde_json::from_value(
+        InitializeRequest { id: 1 }.into_value(),
+      )?)
+      .await?;
+
+    let initialized = service.call(serde_json::from_value(json!({
+      "jsonrpc": "2.0",
+      "method": "initialized",
+      "params": {}
+    }))?);
+
+    let (response, _) = tokio::join!(initialized, socket.next());
+
+    response?;
+
+    let open = service.call(serde_json::from_value(
+      DidOpenNotification {
+        uri: "file:///test.just",
+        text: "foo:\n  echo foo\n",
+      }
+      .into_value(),
+    )?);
+
+    let (response, _) = tokio::join!(open, socket.next());
+
+    response?;
+
+    let change = service.call(serde_json::from_value(json!({
+      "jsonrpc": "2.0",
+      "method": "workspace/didChangeConfiguration",
+      "params": {
+        "settings": {
+          "just-lsp": {
+            "custom-rules": [
+              {
+                "id": "bad",
+                "message": "Bad",
+                "query": "(nonexistent_node)"
+              },
+              {
+                "id": "recipe-name",
+                "message": "Recipe `@name`",
+                "query": "(recipe_header name: (identifier) @name)"
+              }
+            ]
+          }
+        }
+      }
+    }))?);
+
+    let (response, requests) =
+      tokio::join!(change, (&mut socket).take(2).collect::<Vec<_>>());
+
+    response?;
+
+    let [message, diagnostics] = requests.try_into().unwrap();
+
+    let message = serde_json::to_value(message)?;
+
+    assert_eq!(message["method"], "window/showMessage");
+    assert_eq!(
+      message["params"]["message"],
+      "Invalid query for custom rule `bad`: Query error at 1:2. Invalid node type \"nonexistent_node\""
+    );
+
+    let diagnostics = serde_json::to_value(diagnostics)?;
+
+    assert_eq!(diagnostics["method"], "textDocument/publishDiagnostics");
+    assert_eq!(
+      diagnostics["params"]["diagnostics"][0]["message"],
+      "Recipe `foo`"
+    );
+
+    Ok(())
+  }
+
   #[tokio::test]
   async fn dependency_open_republishes_root_diagnostics() -> Result {
     let tempdir = tempfile::tempdir()?;
@@ -2441,7 +3474,7 @@ mod tests {
 
     std::fs::write(imported.to_file_path().unwrap(), "")?;
 
-    let (service, mut socket) = LspService::new(Server::new);
+    let (service, mut socket) = Server::service();
 
     let mut service = Spawn::new(service);
 
@@ -2791,6 +3824,46 @@ mod tests {
       .await
   }
 
+  #[tokio::test]
+  async fn document_link_script_file() -> Result {
+    let tempdir = tempfile::tempdir()?;
+
+    let root = tempdir.path().join("justfile");
+    let target = tempdir.path().join("setup.py");
+
+    std::fs::write(&target, "print(1)\n")?;
+
+    let root_uri = lsp::Url::from_file_path(root).unwrap();
+
+    Test::new()
+      .request(InitializeRequest { id: 1 })
+      .response(InitializeResponse { id: 1 })
+      .notification(DidOpenNotification {
+        uri: root_uri.as_str(),
+        text: "[script('python3', 'setup.py')]\nsetup:\n",
+      })
+      .request(DocumentLinkRequest {
+        id: 2,
+        uri: root_uri.as_str(),
+      })
+      .response(json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "result": [
+          {
+            "range": {
+              "start": { "line": 0, "character": 19 },
+              "end": { "line": 0, "character": 29 }
+            },
+            "target": lsp::Url::from_file_path(&target).unwrap(),
+            "tooltip": target.display().to_string()
+          }
+        ]
+      }))
+      .run()
+      .await
+  }
+
   #[tokio::test]
   async fn document_symbol_empty_document() -> Result {
     Test::new()
@@ -2977,6 +4050,38 @@ mod tests {
       .await
   }
 
+  #[tokio::test]
+  async fn execute_command_returns_run_id() -> Result {
+    Test::new()
+      .request(json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+          "capabilities": {
+            "experimental": { "recipeOutput": true }
+          }
+        }
+      }))
+      .response(InitializeResponse { id: 1 })
+      .request(json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "workspace/executeCommand",
+        "params": {
+          "command": "just-lsp.evaluate",
+          "arguments": ["file:///test.just"]
+        }
+      }))
+      .response(json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "result": { "runId": 0 }
+      }))
+      .run()
+      .await
+  }
+
   #[tokio::test]
   async fn folding_range() -> Result {
     Test::new()
@@ -3501,6 +4606,8 @@ mod tests {
             echo $a
             echo $b
           ```
+
+          Effective value: `true`
           "
         },
         kind: "markdown",
@@ -3927,6 +5034,94 @@ mod tests {
       .await
   }
 
+  #[tokio::test]
+  async fn recipe_output_unknown_run() -> Result {
+    Test::new()
+      .request(InitializeRequest { id: 1 })
+      .response(InitializeResponse { id: 1 })
+      .request(json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "just-lsp/recipeOutput",
+        "params": { "runId": 7 }
+      }))
+      .response(json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "result": null
+      }))
+      .run()
+      .await
+  }
+
+  #[tokio::test]
+  async fn recipes() -> Result {
+    Test::new()
+      .request(InitializeRequest { id: 1 })
+      .response(InitializeResponse { id: 1 })
+      .notification(DidOpenNotification {
+        uri: "file:///test.just",
+        text: indoc! {
+          "
+          # Run the tests
+          [group('ci')]
+          test *filters: build
+            cargo test {{filters}}
+
+          build:
+          "
+        },
+      })
+      .request(json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "just-lsp/recipes",
+        "params": { "uri": "file:///test.just" }
+      }))
+      .response(json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "result": [
+          {
+            "confirm": null,
+            "dependencies": ["build"],
+            "doc": "Run the tests",
+            "groups": ["ci"],
+            "location": {
+              "uri": "file:///test.just",
+              "range": lsp::Range::at(1, 0, 5, 0)
+            },
+            "module_path": [],
+            "name": "test",
+            "parameters": [
+              {
+                "default_value": null,
+                "name": "filters",
+                "variadic": "zero-or-more"
+              }
+            ],
+            "private": false
+          },
+          {
+            "confirm": null,
+            "dependencies": [],
+            "doc": null,
+            "groups": [],
+            "location": {
+              "uri": "file:///test.just",
+              "range": lsp::Range::at(5, 0, 6, 0)
+            },
+            "module_path": [],
+            "name": "build",
+            "parameters": [],
+            "private": false
+          }
+        ]
+      }))
+      .run()
+      .await
+  }
+
   #[tokio::test]
   async fn recipe_references() -> Result {
     Test::new()
@@ -4061,9 +5256,194 @@ mod tests {
       .response(SemanticTokensResponse {
         id: 2,
         data: vec![
-          0, 0, 3, 6, 1, //
+          0, 0, 3, 10, 1, //
+          0, 3, 1, 3, 0, //
+          1, 2, 4, 6, 0, //
+          0, 5, 5, 2, 0,
+        ],
+        result_id: Some("1"),
+      })
+      .run()
+      .await
+  }
+
+  #[tokio::test]
+  async fn semantic_tokens_full_delta() -> Result {
+    Test::new()
+      .request(InitializeRequest { id: 1 })
+      .response(InitializeResponse { id: 1 })
+      .notification(DidOpenNotification {
+        uri: "file:///test.just",
+        text: "foo:\n",
+      })
+      .request(SemanticTokensRequest {
+        id: 2,
+        uri: "file:///test.just",
+      })
+      .response(SemanticTokensResponse {
+        id: 2,
+        data: vec![
+          0, 0, 3, 10, 1, //
+          0, 3, 1, 3, 0,
+        ],
+        result_id: Some("1"),
+      })
+      .notification(DidChangeNotification {
+        uri: "file:///test.just",
+        version: 2,
+        changes: vec![lsp::TextDocumentContentChangeEvent {
+          range: Some(lsp::Range::at(1, 0, 1, 0)),
+          range_length: None,
+          text: "bar:\n".into(),
+        }],
+      })
+      .request(SemanticTokensDeltaRequest {
+        id: 3,
+        previous_result_id: "1",
+        uri: "file:///test.just",
+      })
+      .response(json!({
+        "jsonrpc": "2.0",
+        "id": 3,
+        "result": {
+          "resultId": "2",
+          "edits": [
+            {
+              "start": 10,
+              "deleteCount": 0,
+              "data": [1, 0, 3, 10, 1, 0, 3, 1, 3, 0]
+            }
+          ]
+        }
+      }))
+      .request(SemanticTokensDeltaRequest {
+        id: 4,
+        previous_result_id: "2",
+        uri: "file:///test.just",
+      })
+      .response(json!({
+        "jsonrpc": "2.0",
+        "id": 4,
+        "result": {
+          "resultId": "2",
+          "edits": []
+        }
+      }))
+      .run()
+      .await
+  }
+
+  #[tokio::test]
+  async fn semantic_tokens_full_delta_unknown_result_id() -> Result {
+    Test::new()
+      .request(InitializeRequest { id: 1 })
+      .response(InitializeResponse { id: 1 })
+      .notification(DidOpenNotification {
+        uri: "file:///test.just",
+        text: "foo:\n",
+      })
+      .request(SemanticTokensDeltaRequest {
+        id: 2,
+        previous_result_id: "0",
+        uri: "file:///test.just",
+      })
+      .response(SemanticTokensResponse {
+        id: 2,
+        data: vec![
+          0, 0, 3, 10, 1, //
+          0, 3, 1, 3, 0,
+        ],
+        result_id: Some("1"),
+      })
+      .run()
+      .await
+  }
+
+  #[tokio::test]
+  async fn semantic_tokens_range() -> Result {
+    Test::new()
+      .request(InitializeRequest { id: 1 })
+      .response(InitializeResponse { id: 1 })
+      .notification(DidOpenNotification {
+        uri: "file:///test.just",
+        text: indoc! {
+          "
+          foo:
+
+          bar:
+
+          baz:
+          "
+        },
+      })
+      .request(SemanticTokensRangeRequest {
+        id: 2,
+        uri: "file:///test.just",
+        start_line: 2,
+        start_char: 0,
+        end_line: 3,
+        end_char: 0,
+      })
+      .response(SemanticTokensResponse {
+        id: 2,
+        data: vec![
+          2, 0, 3, 10, 1, //
+          0, 3, 1, 3, 0,
+        ],
+        result_id: None,
+      })
+      .run()
+      .await
+  }
+
+  #[tokio::test]
+  async fn semantic_tokens_range_uses_cached_tokens() -> Result {
+    Test::new()
+      .request(InitializeRequest { id: 1 })
+      .response(InitializeResponse { id: 1 })
+      .notification(DidOpenNotification {
+        uri: "file:///test.just",
+        text: indoc! {
+          "
+          foo:
+
+          bar:
+
+          baz:
+          "
+        },
+      })
+      .request(SemanticTokensRequest {
+        id: 2,
+        uri: "file:///test.just",
+      })
+      .response(SemanticTokensResponse {
+        id: 2,
+        data: vec![
+          0, 0, 3, 10, 1, //
+          0, 3, 1, 3, 0, //
+          2, 0, 3, 10, 1, //
+          0, 3, 1, 3, 0, //
+          2, 0, 3, 10, 1, //
+          0, 3, 1, 3, 0,
+        ],
+        result_id: Some("1"),
+      })
+      .request(SemanticTokensRangeRequest {
+        id: 3,
+        uri: "file:///test.just",
+        start_line: 2,
+        start_char: 0,
+        end_line: 3,
+        end_char: 0,
+      })
+      .response(SemanticTokensResponse {
+        id: 3,
+        data: vec![
+          2, 0, 3, 10, 1, //
           0, 3, 1, 3, 0,
         ],
+        result_id: None,
       })
       .run()
       .await
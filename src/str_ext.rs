@@ -1,6 +1,11 @@
 use super::*;
 
 pub trait StrExt {
+  /// Returns the edit distance between this string and `other`, counted in
+  /// characters, where swapping two adjacent characters counts as a single
+  /// edit.
+  fn edit_distance(&self, other: &str) -> usize;
+
   /// Returns the decoded value of a plain string literal.
   fn literal(&self) -> Option<String>;
 
@@ -10,6 +15,41 @@ pub trait StrExt {
 }
 
 impl StrExt for str {
+  fn edit_distance(&self, other: &str) -> usize {
+    let (a, b) = (
+      self.chars().collect::<Vec<char>>(),
+      other.chars().collect::<Vec<char>>(),
+    );
+
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in distances.iter_mut().enumerate() {
+      row[0] = i;
+    }
+
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+      *distance = j;
+    }
+
+    for i in 1..=a.len() {
+      for j in 1..=b.len() {
+        let cost = usize::from(a[i - 1] != b[j - 1]);
+
+        let mut distance = (distances[i - 1][j] + 1)
+          .min(distances[i][j - 1] + 1)
+          .min(distances[i - 1][j - 1] + cost);
+
+        if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+          distance = distance.min(distances[i - 2][j - 2] + 1);
+        }
+
+        distances[i][j] = distance;
+      }
+    }
+
+    distances[a.len()][b.len()]
+  }
+
   fn literal(&self) -> Option<String> {
     let (quote, value) = if let Some(value) = self
       .strip_prefix('"')
@@ -131,6 +171,25 @@ mod tests {
     assert_eq!("\r\nabc".point_delta(), Point::new(1, "abc".len()));
   }
 
+  #[test]
+  fn edit_distance() {
+    #[track_caller]
+    fn case(a: &str, b: &str, expected: usize) {
+      assert_eq!(a.edit_distance(b), expected);
+      assert_eq!(b.edit_distance(a), expected);
+    }
+
+    case("", "", 0);
+    case("foo", "foo", 0);
+    case("", "foo", 3);
+    case("foo", "fo", 1);
+    case("foo", "bar", 3);
+    case("kitten", "sitting", 3);
+    case("uppercase", "upercase", 1);
+    case("héllo", "hello", 1);
+    case("buidl", "build", 1);
+  }
+
   #[test]
   fn empty_string_produces_origin() {
     assert_eq!("".point_delta(), Point::new(0, 0));
@@ -0,0 +1,119 @@
+use super::*;
+
+/// A plain string literal, with the source byte offset of each byte of its
+/// cooked contents, so that positions inside the value can be mapped back to
+/// the document.
+#[derive(Debug)]
+pub struct StringLiteral {
+  offsets: Vec<usize>,
+  pub text: String,
+}
+
+impl StringLiteral {
+  fn from_string(document: &Document, node: Node) -> Option<Self> {
+    let raw = document.get_node_text(&node);
+
+    let text = raw.literal()?;
+
+    let start = node.start_byte() + 1;
+
+    let inner = &raw[1..raw.len() - 1];
+
+    let mut offsets = Vec::new();
+
+    if raw.starts_with('\'') {
+      offsets.extend((0..inner.len()).map(|index| start + index));
+    } else {
+      let mut characters = inner.char_indices().peekable();
+
+      while let Some((index, character)) = characters.next() {
+        if character != '\\' {
+          offsets
+            .extend((0..character.len_utf8()).map(|byte| start + index + byte));
+          continue;
+        }
+
+        let (_, escaped) = characters.next()?;
+
+        let cooked = match escaped {
+          '\n' => continue,
+          '\r' => {
+            characters.next();
+            continue;
+          }
+          'u' => {
+            let mut codepoint = String::new();
+
+            for (_, character) in characters.by_ref() {
+              match character {
+                '{' => {}
+                '}' => break,
+                _ => codepoint.push(character),
+              }
+            }
+
+            char::from_u32(u32::from_str_radix(&codepoint, 16).ok()?)?
+          }
+          'n' => '\n',
+          'r' => '\r',
+          't' => '\t',
+          other => other,
+        };
+
+        offsets.extend((0..cooked.len_utf8()).map(|_| start + index));
+      }
+    }
+
+    offsets.push(node.end_byte() - 1);
+
+    Some(Self { offsets, text })
+  }
+
+  /// The string literal that `node` consists of, looking through
+  /// single-child expression and value wrappers.
+  #[must_use]
+  pub fn new(document: &Document, node: Node) -> Option<Self> {
+    match node.kind() {
+      "string" if node.find("format_string").is_none() => {
+        Self::from_string(document, node)
+      }
+      "expression" | "regex_literal" | "value"
+        if node.named_child_count() == 1 =>
+      {
+        Self::new(document, node.named_child(0)?)
+      }
+      _ => None,
+    }
+  }
+
+  /// The document byte offset of byte `index` of the cooked text, clamped
+  /// to the closing quote.
+  #[must_use]
+  pub fn offset(&self, index: usize) -> usize {
+    self.offsets[index.min(self.offsets.len() - 1)]
+  }
+
+  /// The document range covering bytes `start..end` of the cooked text.
+  #[must_use]
+  pub fn range(
+    &self,
+    document: &Document,
+    start: usize,
+    end: usize,
+  ) -> lsp::Range {
+    lsp::Range {
+      start: document.content.byte_to_lsp_position(self.offset(start)),
+      end: document.content.byte_to_lsp_position(self.offset(end)),
+    }
+  }
+
+  /// The cooked text together with the range of the whole value, suitable
+  /// for replacement quickfixes.
+  #[must_use]
+  pub fn text_node(&self, document: &Document) -> TextNode {
+    TextNode {
+      range: self.range(document, 0, self.text.len()),
+      value: self.text.clone(),
+    }
+  }
+}
@@ -2,6 +2,14 @@ use super::*;
 
 #[derive(Debug, Parser)]
 pub(crate) struct Analyze {
+  #[arg(
+    long,
+    value_name = "PATH",
+    help = "Path to a JSON configuration file, in the format of the server's \
+      initialization options",
+    value_hint = clap::ValueHint::FilePath
+  )]
+  config: Option<PathBuf>,
   #[arg(
     value_name = "PATH",
     help = "Path to the justfile to analyze",
@@ -11,7 +19,31 @@ pub(crate) struct Analyze {
 }
 
 impl Analyze {
+  /// Reads the configuration at `path`, warning about every custom rule
+  /// dropped because it's invalid.
+  fn load_config(path: &Path) -> Result<Config> {
+    use yansi::Paint;
+
+    let content = fs::read_to_string(path).map_err(|error| {
+      anyhow!("could not read `{}`: {error}", path.display())
+    })?;
+
+    let (config, errors) = serde_json::from_str(&content)
+      .and_then(Config::load)
+      .map_err(|error| {
+        anyhow!("could not parse `{}`: {error}", path.display())
+      })?;
+
+    for error in errors {
+      eprintln!("{} {error}", "warning:".yellow().bold());
+    }
+
+    Ok(config)
+  }
+
   pub(crate) fn run(self) -> Result<()> {
+    let config = self.config.as_deref().map(Self::load_config).transpose()?;
+
     let path = match self.path {
       Some(path) => path,
       None => Subcommand::find_justfile()?,
@@ -55,16 +87,17 @@ impl Analyze {
 
     let content = document.content.to_string();
 
-    let imported_documents = workspace
-      .projects
-      .get(&uri)
+    let project = workspace.projects.get(&uri);
+
+    let imported_documents = project
       .into_iter()
       .flat_map(|project| project.imported_documents(&workspace.documents));
 
     let analyzer = Analyzer {
-      config: None,
+      config: config.as_ref(),
       document,
       imported_documents: imported_documents.collect(),
+      project,
     };
 
     let diagnostics = analyzer.analyze();
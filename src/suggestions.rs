@@ -0,0 +1,194 @@
+use super::*;
+
+/// Names similar to an unknown name, used to turn "not found" diagnostics
+/// into "did you mean" diagnostics with matching quickfixes.
+#[derive(Debug, Default, PartialEq)]
+pub struct Suggestions(Vec<String>);
+
+impl Suggestions {
+  const LIMIT: usize = 3;
+
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  /// Append a "did you mean" hint to `message`, if there are any suggestions.
+  #[must_use]
+  pub fn message(&self, message: impl Into<String>) -> String {
+    let message = message.into();
+
+    let Some((last, rest)) = self.0.split_last() else {
+      return message;
+    };
+
+    let candidates = if rest.is_empty() {
+      format!("`{last}`")
+    } else {
+      format!(
+        "{} or `{last}`",
+        rest
+          .iter()
+          .map(|candidate| format!("`{candidate}`"))
+          .collect::<Vec<_>>()
+          .join(", ")
+      )
+    };
+
+    format!("{message}, did you mean {candidates}?")
+  }
+
+  /// Collect the closest candidates within an edit distance proportional to
+  /// the length of `name`.
+  ///
+  /// Case-insensitive matches are always suggested, since they're the most
+  /// common kind of typo for setting and attribute names.
+  #[must_use]
+  pub fn new<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+  ) -> Self {
+    let threshold = name.chars().count().max(3) / 3;
+
+    let mut matches = candidates
+      .into_iter()
+      .filter(|candidate| *candidate != name)
+      .filter_map(|candidate| {
+        let distance = if candidate.eq_ignore_ascii_case(name) {
+          0
+        } else {
+          name.edit_distance(candidate)
+        };
+
+        (distance <= threshold).then_some((distance, candidate))
+      })
+      .collect::<Vec<_>>();
+
+    matches.sort_unstable();
+    matches.dedup();
+
+    let closest = matches.first().map(|(distance, _)| *distance);
+
+    Self(
+      matches
+        .into_iter()
+        .take_while(|(distance, _)| Some(*distance) == closest)
+        .take(Self::LIMIT)
+        .map(|(_, candidate)| candidate.to_string())
+        .collect(),
+    )
+  }
+
+  /// Build a replacement quickfix for each suggestion, anchored to the
+  /// diagnostic `range`.
+  ///
+  /// These are never safe to apply automatically, since the intended name
+  /// can't be known for certain.
+  #[must_use]
+  pub fn quickfixes(
+    &self,
+    name: &TextNode,
+    range: lsp::Range,
+  ) -> Vec<Quickfix> {
+    self
+      .0
+      .iter()
+      .map(|candidate| Quickfix {
+        range,
+        ..Quickfix::replacement(name, candidate).safe(false)
+      })
+      .collect()
+  }
+}
+
+impl From<&str> for Suggestions {
+  fn from(candidate: &str) -> Self {
+    Self(vec![candidate.to_string()])
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, pretty_assertions::assert_eq};
+
+  #[test]
+  fn case_insensitive_match() {
+    assert_eq!(
+      Suggestions::new("Linux", ["linux", "macos"]),
+      Suggestions(vec!["linux".into()])
+    );
+  }
+
+  #[test]
+  fn closest_only() {
+    assert_eq!(
+      Suggestions::new("buidl", ["build", "bui", "build-docs", "buil"]),
+      Suggestions(vec!["buil".into(), "build".into()])
+    );
+  }
+
+  #[test]
+  fn distant_names_are_ignored() {
+    assert!(Suggestions::new("foo", ["bar", "baz"]).is_empty());
+  }
+
+  #[test]
+  fn identical_name_is_ignored() {
+    assert!(Suggestions::new("foo", ["foo"]).is_empty());
+  }
+
+  #[test]
+  fn limits_candidates() {
+    assert_eq!(
+      Suggestions::new("aaa", ["aab", "aac", "aad", "aae"]),
+      Suggestions(vec!["aab".into(), "aac".into(), "aad".into()])
+    );
+  }
+
+  #[test]
+  fn message() {
+    #[track_caller]
+    fn case(suggestions: &[&str], expected: &str) {
+      assert_eq!(
+        Suggestions(suggestions.iter().map(ToString::to_string).collect())
+          .message("Recipe `foo` not found"),
+        expected
+      );
+    }
+
+    case(&[], "Recipe `foo` not found");
+    case(&["fo"], "Recipe `foo` not found, did you mean `fo`?");
+    case(
+      &["fo", "fooo"],
+      "Recipe `foo` not found, did you mean `fo` or `fooo`?",
+    );
+    case(
+      &["fo", "fooo", "boo"],
+      "Recipe `foo` not found, did you mean `fo`, `fooo` or `boo`?",
+    );
+  }
+
+  #[test]
+  fn quickfixes() {
+    let name = TextNode {
+      range: lsp::Range::at(0, 5, 0, 10),
+      value: "buidl".into(),
+    };
+
+    assert_eq!(
+      Suggestions::new("buidl", ["build"])
+        .quickfixes(&name, lsp::Range::at(0, 0, 0, 10)),
+      vec![Quickfix {
+        create: false,
+        edits: vec![lsp::TextEdit {
+          range: lsp::Range::at(0, 5, 0, 10),
+          new_text: "build".into(),
+        }],
+        range: lsp::Range::at(0, 0, 0, 10),
+        safe: false,
+        title: "Replace `buidl` with `build`".into(),
+        uri: None,
+      }]
+    );
+  }
+}
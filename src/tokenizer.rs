@@ -1,8 +1,12 @@
 use super::*;
 
+const BASH_HIGHLIGHTS_QUERY: &str =
+  include_str!("../vendor/tree-sitter-bash/queries/highlights.scm");
 const HIGHLIGHTS_QUERY: &str = include_str!("../queries/highlights.scm");
 const INJECTIONS_QUERY: &str = include_str!("../queries/injections.scm");
 const LOCALS_QUERY: &str = include_str!("../queries/locals.scm");
+const PYTHON_HIGHLIGHTS_QUERY: &str =
+  include_str!("../vendor/tree-sitter-python/queries/highlights.scm");
 
 const TOKEN_TYPES: &[&str] = &[
   "comment",
@@ -15,23 +19,47 @@ const TOKEN_TYPES: &[&str] = &[
   "namespace",
   "decorator",
   "boolean",
+  "recipe",
+  "alias",
+  "setting",
+  "module",
+  "builtinConstant",
 ];
 
-const TOKEN_MODIFIERS: &[&str] = &["declaration", "deprecated"];
+const TOKEN_MODIFIERS: &[&str] = &[
+  "declaration",
+  "deprecated",
+  "private",
+  "exported",
+  "readonly",
+  "defaultLibrary",
+  "unresolved",
+];
 
 const HIGHLIGHTS: &[(&str, Option<&str>, &[&str])] = &[
   ("attribute", Some("decorator"), &[]),
   ("boolean", Some("boolean"), &[]),
   ("comment", Some("comment"), &[]),
+  ("constant", Some("variable"), &["readonly"]),
+  (
+    "constant.builtin",
+    Some("builtinConstant"),
+    &["defaultLibrary"],
+  ),
+  ("constructor", Some("function"), &[]),
   ("error", Some("keyword"), &["deprecated"]),
-  ("function", Some("function"), &["declaration"]),
+  ("escape", Some("string"), &[]),
+  ("function", Some("function"), &[]),
+  ("function.builtin", Some("function"), &["defaultLibrary"]),
   ("function.call", Some("function"), &[]),
+  ("function.method", Some("function"), &[]),
   ("keyword", Some("keyword"), &[]),
   ("keyword.conditional", Some("keyword"), &[]),
   ("keyword.directive", Some("keyword"), &[]),
   ("keyword.import", Some("keyword"), &[]),
   ("module", Some("namespace"), &[]),
   ("operator", Some("operator"), &[]),
+  ("property", Some("variable"), &[]),
   ("punctuation.bracket", Some("operator"), &[]),
   ("punctuation.delimiter", Some("operator"), &[]),
   ("punctuation.special", Some("operator"), &[]),
@@ -54,9 +82,20 @@ pub(crate) static SEMANTIC_TOKENS_LEGEND: LazyLock<lsp::SemanticTokensLegend> =
       .collect(),
   });
 
+static BASH_HIGHLIGHT_CONFIGURATION: LazyLock<HighlightConfiguration> =
+  LazyLock::new(|| {
+    Tokenizer::highlight_configuration(
+      EmbeddedLanguage::Shell.language(),
+      "bash",
+      BASH_HIGHLIGHTS_QUERY,
+      "",
+      "",
+    )
+  });
+
 static HIGHLIGHT_CONFIGURATION: LazyLock<HighlightConfiguration> =
   LazyLock::new(|| {
-    let mut configuration = HighlightConfiguration::new(
+    Tokenizer::highlight_configuration(
       // SAFETY: tree_sitter_just returns a valid static tree-sitter language.
       unsafe { tree_sitter_just() },
       "just",
@@ -64,16 +103,6 @@ static HIGHLIGHT_CONFIGURATION: LazyLock<HighlightConfiguration> =
       INJECTIONS_QUERY,
       LOCALS_QUERY,
     )
-    .expect("Failed to create highlight configuration");
-
-    let names = HIGHLIGHTS
-      .iter()
-      .map(|(name, _, _)| *name)
-      .collect::<Vec<_>>();
-
-    configuration.configure(&names);
-
-    configuration
   });
 
 static HIGHLIGHT_MAPPINGS: LazyLock<Vec<Option<TokenMap>>> =
@@ -86,6 +115,17 @@ static HIGHLIGHT_MAPPINGS: LazyLock<Vec<Option<TokenMap>>> =
       .collect()
   });
 
+static PYTHON_HIGHLIGHT_CONFIGURATION: LazyLock<HighlightConfiguration> =
+  LazyLock::new(|| {
+    Tokenizer::highlight_configuration(
+      EmbeddedLanguage::Python.language(),
+      "python",
+      PYTHON_HIGHLIGHTS_QUERY,
+      "",
+      "",
+    )
+  });
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 struct Token {
   length: u32,
@@ -112,9 +152,168 @@ impl TokenMap {
 
 pub(crate) struct Tokenizer<'doc> {
   document: &'doc Document,
+  export: bool,
+  resolver: Resolver<'doc>,
+  shell: Option<EmbeddedLanguage>,
 }
 
 impl<'doc> Tokenizer<'doc> {
+  /// The token type and modifiers of `identifier`, according to what it
+  /// resolves to, or `None` to leave it to the highlight pass.
+  fn classify(
+    &self,
+    identifier: &Node,
+  ) -> Option<(&'static str, Vec<&'static str>)> {
+    let parent = identifier.parent()?;
+
+    let name = self.document.get_node_text(identifier);
+
+    let private = |name: &str, attributes: &[Attribute]| {
+      (name.starts_with('_')
+        || attributes
+          .iter()
+          .any(|attribute| attribute.name.value == "private"))
+      .then_some("private")
+    };
+
+    let declaration = matches!(
+      parent.kind(),
+      "assignment"
+        | "function_definition"
+        | "function_parameters"
+        | "parameter"
+        | "recipe_header"
+    )
+    .then_some("declaration");
+
+    if parent.kind() == "alias"
+      && parent
+        .child_by_field_name("left")
+        .is_some_and(|left| left.id() == identifier.id())
+    {
+      let alias = self
+        .document
+        .aliases()
+        .into_iter()
+        .find(|alias| alias.name.value == name)?;
+
+      return Some((
+        "alias",
+        once("declaration")
+          .chain(private(&name, &alias.attributes))
+          .collect(),
+      ));
+    }
+
+    match parent.kind() {
+      "module" => return Some(("module", vec!["declaration"])),
+      "module_path" => {
+        return Some(if identifier.next_named_sibling().is_some() {
+          ("module", Vec::new())
+        } else {
+          ("recipe", Vec::new())
+        });
+      }
+      _ => {}
+    }
+
+    let Some(symbol) = self.resolver.resolve_symbol(identifier) else {
+      return match parent.kind() {
+        "alias" | "dependency" | "dependency_expression" => {
+          Some(("recipe", vec!["unresolved"]))
+        }
+        "function_call" => Some(("function", vec!["unresolved"])),
+        "setting" => Some(("setting", vec!["unresolved"])),
+        "value" => Some(("variable", vec!["unresolved"])),
+        _ => None,
+      };
+    };
+
+    let (token_type, modifiers) = match symbol {
+      Symbol::Builtin(Builtin::Attribute { .. }) => return None,
+      Symbol::Builtin(Builtin::Constant { .. }) => {
+        ("builtinConstant", vec!["readonly", "defaultLibrary"])
+      }
+      Symbol::Builtin(Builtin::Function { deprecated, .. }) => (
+        "function",
+        once("defaultLibrary")
+          .chain(deprecated.map(|_| "deprecated"))
+          .collect(),
+      ),
+      Symbol::Builtin(Builtin::Setting { deprecated, .. }) => (
+        "setting",
+        once("defaultLibrary")
+          .chain(deprecated.map(|_| "deprecated"))
+          .collect(),
+      ),
+      Symbol::Function(_) => ("function", Vec::new()),
+      Symbol::FunctionParameter(_) => ("parameter", Vec::new()),
+      Symbol::Parameter(parameter) => (
+        "parameter",
+        (parameter.export || self.export)
+          .then_some("exported")
+          .into_iter()
+          .collect(),
+      ),
+      Symbol::Recipe(recipe) => (
+        "recipe",
+        private(&recipe.value.name.value, &recipe.value.attributes)
+          .into_iter()
+          .collect(),
+      ),
+      Symbol::Variable(variable) => (
+        "variable",
+        once("readonly")
+          .chain((variable.value.export || self.export).then_some("exported"))
+          .chain(private(
+            &variable.value.name.value,
+            &variable.value.attributes,
+          ))
+          .collect(),
+      ),
+    };
+
+    Some((
+      token_type,
+      declaration.into_iter().chain(modifiers).collect(),
+    ))
+  }
+
+  /// The edits that turn the encoded tokens `previous` into `current`, as a
+  /// single replacement of everything between their common prefix and
+  /// suffix.
+  pub(crate) fn edits(
+    previous: &[lsp::SemanticToken],
+    current: &[lsp::SemanticToken],
+  ) -> Vec<lsp::SemanticTokensEdit> {
+    let prefix = previous
+      .iter()
+      .zip(current)
+      .take_while(|(previous, current)| previous == current)
+      .count();
+
+    if prefix == previous.len() && prefix == current.len() {
+      return Vec::new();
+    }
+
+    let suffix = previous[prefix..]
+      .iter()
+      .rev()
+      .zip(current[prefix..].iter().rev())
+      .take_while(|(previous, current)| previous == current)
+      .count();
+
+    let integers = |tokens: usize| {
+      u32::try_from(tokens * 5).expect("Token count must fit within a u32")
+    };
+
+    vec![lsp::SemanticTokensEdit {
+      start: integers(prefix),
+      delete_count: integers(previous.len() - prefix - suffix),
+      data: Some(current[prefix..current.len() - suffix].to_vec()),
+    }]
+  }
+
   fn encode_tokens(mut tokens: Vec<Token>) -> Vec<lsp::SemanticToken> {
     tokens.sort_by(|left, right| {
       left
@@ -147,6 +346,118 @@ impl<'doc> Tokenizer<'doc> {
       .collect()
   }
 
+  /// The tokens of `tokens`, encoded for a whole document, that overlap
+  /// `range`, re-encoded on their own.
+  pub(crate) fn filter_range(
+    tokens: &[lsp::SemanticToken],
+    range: lsp::Range,
+  ) -> Vec<lsp::SemanticToken> {
+    let tokens = tokens
+      .iter()
+      .scan((0u32, 0u32), |(line, start), token| {
+        if token.delta_line > 0 {
+          (*line, *start) = (*line + token.delta_line, token.delta_start);
+        } else {
+          *start += token.delta_start;
+        }
+
+        Some(Token {
+          length: token.length,
+          line: *line,
+          modifiers_bitset: token.token_modifiers_bitset,
+          start_character: *start,
+          type_index: token.token_type,
+        })
+      })
+      .filter(|token| Self::overlaps(token, range))
+      .collect();
+
+    Self::encode_tokens(tokens)
+  }
+
+  fn highlight_configuration(
+    language: Language,
+    name: &str,
+    highlights: &str,
+    injections: &str,
+    locals: &str,
+  ) -> HighlightConfiguration {
+    let mut configuration = HighlightConfiguration::new(
+      language, name, highlights, injections, locals,
+    )
+    .expect("Failed to create highlight configuration");
+
+    let names = HIGHLIGHTS
+      .iter()
+      .map(|(name, _, _)| *name)
+      .collect::<Vec<_>>();
+
+    configuration.configure(&names);
+
+    configuration
+  }
+
+  /// The bytes to highlight for tokens overlapping `bytes`: the whole lines
+  /// of the top-level items overlapping them. Items are independent of one
+  /// another, so highlighting them on their own gives the same result as
+  /// highlighting the whole document, unless it has syntax errors.
+  fn highlight_span(&self, bytes: &Range<usize>) -> Range<usize> {
+    let rope = &self.document.content;
+
+    let Some(tree) = self
+      .document
+      .tree
+      .as_ref()
+      .filter(|tree| !tree.root_node().has_error())
+    else {
+      return 0..rope.len_bytes();
+    };
+
+    let root = tree.root_node();
+
+    let mut cursor = root.walk();
+
+    let items = root
+      .children(&mut cursor)
+      .filter(|item| {
+        item.end_byte() > bytes.start && item.start_byte() < bytes.end
+      })
+      .collect::<Vec<_>>();
+
+    let (Some(first), Some(last)) = (items.first(), items.last()) else {
+      return bytes.start..bytes.start;
+    };
+
+    let end_line = rope.byte_to_line(last.end_byte().saturating_sub(1)) + 1;
+
+    rope.line_to_byte(rope.byte_to_line(first.start_byte()))
+      ..rope.line_to_byte(end_line.min(rope.len_lines()))
+  }
+
+  /// The highlight configuration for an injected language. The injection
+  /// query defaults recipe bodies to `bash` and separately injects each
+  /// quoted element of `set shell`, so the default is taken to mean the
+  /// configured shell and the quoted elements are ignored.
+  fn injection(
+    name: &str,
+    shell: Option<EmbeddedLanguage>,
+  ) -> Option<&'static HighlightConfiguration> {
+    if name.starts_with(['"', '\'']) {
+      return None;
+    }
+
+    let language = if name == "bash" {
+      shell?
+    } else {
+      EmbeddedLanguage::from_command([name])?
+    };
+
+    match language {
+      EmbeddedLanguage::Python => Some(&PYTHON_HIGHLIGHT_CONFIGURATION),
+      EmbeddedLanguage::Shell => Some(&BASH_HIGHLIGHT_CONFIGURATION),
+    }
+  }
+
   #[must_use]
   pub(crate) fn legend() -> &'static lsp::SemanticTokensLegend {
     &SEMANTIC_TOKENS_LEGEND
@@ -170,8 +481,51 @@ impl<'doc> Tokenizer<'doc> {
   }
 
   #[must_use]
-  pub(crate) fn new(document: &'doc Document) -> Self {
-    Self { document }
+  pub(crate) fn new(view: impl Into<ProjectView<'doc>>) -> Self {
+    let view = view.into();
+
+    let document = view.document();
+
+    Self {
+      document,
+      export: view.find_setting("export").is_some_and(|setting| {
+        matches!(setting.value.kind, SettingKind::Boolean(true))
+      }),
+      resolver: Resolver::new(view),
+      shell: EmbeddedScript::shell(document),
+    }
+  }
+
+  fn overlaps(token: &Token, range: lsp::Range) -> bool {
+    (token.line, token.start_character + token.length)
+      > (range.start.line, range.start.character)
+      && (token.line, token.start_character)
+        < (range.end.line, range.end.character)
+  }
+
+  /// Tokens for the shebangs overlapping `bytes`, which are directives to
+  /// `just` even though the injected language sees them as comments.
+  fn shebang_tokens(&self, bytes: &Range<usize>) -> Vec<Token> {
+    let Some(tree) = self.document.tree.as_ref() else {
+      return Vec::new();
+    };
+
+    tree
+      .root_node()
+      .find_all("shebang")
+      .into_iter()
+      .filter(|shebang| {
+        shebang.end_byte() > bytes.start && shebang.start_byte() < bytes.end
+      })
+      .flat_map(|shebang| {
+        Self::span_to_tokens(
+          &self.document.content,
+          shebang.start_byte(),
+          shebang.end_byte(),
+          TokenMap::new("keyword", &[]),
+        )
+      })
+      .collect()
   }
 
   fn span_to_tokens(
@@ -237,6 +591,35 @@ impl<'doc> Tokenizer<'doc> {
       .collect()
   }
 
+  /// Tokens for the identifiers overlapping `bytes`, classified by the
+  /// symbols they resolve to.
+  fn symbol_tokens(&self, bytes: &Range<usize>) -> Vec<Token> {
+    let Some(tree) = self.document.tree.as_ref() else {
+      return Vec::new();
+    };
+
+    tree
+      .root_node()
+      .find_all("identifier")
+      .into_iter()
+      .filter(|identifier| {
+        identifier.end_byte() > bytes.start
+          && identifier.start_byte() < bytes.end
+      })
+      .filter_map(|identifier| {
+        let (token_type, modifiers) = self.classify(&identifier)?;
+
+        Some(Self::span_to_tokens(
+          &self.document.content,
+          identifier.start_byte(),
+          identifier.end_byte(),
+          TokenMap::new(token_type, &modifiers),
+        ))
+      })
+      .flatten()
+      .collect()
+  }
+
   fn token_type_index(token_type: &str) -> u32 {
     TOKEN_TYPES
       .iter()
@@ -248,6 +631,33 @@ impl<'doc> Tokenizer<'doc> {
   }
 
   pub(crate) fn tokenize(&self) -> Result<Vec<lsp::SemanticToken>> {
+    Ok(Self::encode_tokens(
+      self.tokens(0..self.document.content.len_bytes())?,
+    ))
+  }
+
+  /// Tokens overlapping `range`, encoded relative to the start of the
+  /// document. Only the top-level items overlapping the range are
+  /// highlighted.
+  pub(crate) fn tokenize_range(
+    &self,
+    range: lsp::Range,
+  ) -> Result<Vec<lsp::SemanticToken>> {
+    let rope = &self.document.content;
+
+    let line =
+      |line: u32| rope.line_to_byte((line as usize).min(rope.len_lines()));
+
+    let tokens = self
+      .tokens(line(range.start.line)..line(range.end.line.saturating_add(1)))?
+      .into_iter()
+      .filter(|token| Self::overlaps(token, range))
+      .collect();
+
+    Ok(Self::encode_tokens(tokens))
+  }
+
+  fn tokens(&self, bytes: Range<usize>) -> Result<Vec<Token>> {
     let mut highlighter = Highlighter::new();
 
     highlighter
@@ -256,51 +666,79 @@ impl<'doc> Tokenizer<'doc> {
       .set_language(&unsafe { tree_sitter_just() })
       .map_err(|error| anyhow!("Failed to configure highlighter: {error}"))?;
 
-    let source = self.document.content.to_string();
+    let span = self.highlight_span(&bytes);
+
+    let source = self.document.content.byte_slice(span.clone()).to_string();
+
+    let shell = self.shell;
 
     let highlight_iter = highlighter
-      .highlight(&HIGHLIGHT_CONFIGURATION, source.as_bytes(), None, |_| None)
+      .highlight(&HIGHLIGHT_CONFIGURATION, source.as_bytes(), None, |name| {
+        Self::injection(name, shell)
+      })
       .map_err(|error| anyhow!("Failed to highlight document: {error}"))?;
 
     let mut highlight_stack = Vec::new();
 
-    let tokens = highlight_iter
-      .map(|event| {
-        event.map_err(|error| anyhow!("Failed to highlight document: {error}"))
-      })
-      .filter_map(|event| match event {
-        Err(error) => Some(Err(error)),
-        Ok(HighlightEvent::HighlightStart(Highlight(index))) => {
+    let mut tokens = Vec::new();
+
+    for event in highlight_iter {
+      match event
+        .map_err(|error| anyhow!("Failed to highlight document: {error}"))?
+      {
+        HighlightEvent::HighlightStart(Highlight(index)) => {
           highlight_stack.push(index);
-          None
         }
-        Ok(HighlightEvent::HighlightEnd) => {
+        HighlightEvent::HighlightEnd => {
           highlight_stack.pop();
-          None
         }
-        Ok(HighlightEvent::Source { start, end }) => highlight_stack
-          .iter()
-          .rev()
-          .find_map(|index| {
+        HighlightEvent::Source { start, end } => {
+          let (start, end) = (span.start + start, span.start + end);
+
+          if start >= bytes.end {
+            break;
+          }
+
+          if end <= bytes.start {
+            continue;
+          }
+
+          let mapping = highlight_stack.iter().rev().find_map(|index| {
             HIGHLIGHT_MAPPINGS
               .get(*index)
               .and_then(|mapping| mapping.as_ref().copied())
-          })
-          .map(|mapping| {
-            Ok(Self::span_to_tokens(
+          });
+
+          if let Some(mapping) = mapping {
+            tokens.extend(Self::span_to_tokens(
               &self.document.content,
               start,
               end,
               mapping,
-            ))
-          }),
-      })
-      .collect::<Result<Vec<_>>>()?
+            ));
+          }
+        }
+      }
+    }
+
+    let overrides = self
+      .shebang_tokens(&bytes)
       .into_iter()
-      .flatten()
-      .collect();
+      .chain(self.symbol_tokens(&bytes))
+      .collect::<Vec<_>>();
 
-    Ok(Self::encode_tokens(tokens))
+    let positions = overrides
+      .iter()
+      .map(|token| (token.line, token.start_character))
+      .collect::<BTreeSet<_>>();
+
+    tokens.retain(|token| {
+      !positions.contains(&(token.line, token.start_character))
+    });
+
+    tokens.extend(overrides);
+
+    Ok(tokens)
   }
 
   fn trailing_line_break_len(rope: &Rope, line_idx: usize) -> usize {
@@ -339,6 +777,26 @@ mod tests {
     token_type: &'static str,
   }
 
+  fn symbols(source: &str) -> Vec<(&'static str, Vec<&'static str>)> {
+    tokenize(source)
+      .into_iter()
+      .filter(|token| {
+        !matches!(token.token_type, "keyword" | "operator" | "string")
+      })
+      .map(|token| {
+        (
+          token.token_type,
+          TOKEN_MODIFIERS
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| token.modifiers & (1 << index) != 0)
+            .map(|(_, modifier)| *modifier)
+            .collect(),
+        )
+      })
+      .collect()
+  }
+
   fn tokenize(source: &str) -> Vec<Expected> {
     let tokens = Tokenizer::new(&Document::from(source)).tokenize().unwrap();
 
@@ -382,7 +840,7 @@ mod tests {
           start: 0,
           length: 3,
           modifiers: Tokenizer::modifier_bitset(&["declaration"]),
-          token_type: "function",
+          token_type: "recipe",
         },
         Expected {
           line: 0,
@@ -402,8 +860,8 @@ mod tests {
           line: 2,
           start: 6,
           length: 3,
-          modifiers: 0,
-          token_type: "variable",
+          modifiers: Tokenizer::modifier_bitset(&["declaration"]),
+          token_type: "alias",
         },
         Expected {
           line: 2,
@@ -412,6 +870,13 @@ mod tests {
           modifiers: 0,
           token_type: "operator",
         },
+        Expected {
+          line: 2,
+          start: 13,
+          length: 3,
+          modifiers: 0,
+          token_type: "recipe",
+        },
       ]
     );
   }
@@ -425,7 +890,7 @@ mod tests {
           line: 0,
           start: 0,
           length: 3,
-          modifiers: 0,
+          modifiers: Tokenizer::modifier_bitset(&["declaration", "readonly"]),
           token_type: "variable",
         },
         Expected {
@@ -481,8 +946,8 @@ mod tests {
           line: 1,
           start: 0,
           length: 3,
-          modifiers: Tokenizer::modifier_bitset(&["declaration"]),
-          token_type: "function",
+          modifiers: Tokenizer::modifier_bitset(&["declaration", "private"]),
+          token_type: "recipe",
         },
         Expected {
           line: 1,
@@ -511,8 +976,8 @@ mod tests {
           line: 0,
           start: 4,
           length: 6,
-          modifiers: 0,
-          token_type: "keyword",
+          modifiers: Tokenizer::modifier_bitset(&["defaultLibrary"]),
+          token_type: "setting",
         },
         Expected {
           line: 0,
@@ -532,6 +997,18 @@ mod tests {
     );
   }
 
+  #[test]
+  fn builtin_constant_and_unresolved_variable() {
+    assert_eq!(
+      symbols("foo := HEX + bar\n"),
+      [
+        ("variable", vec!["declaration", "readonly"]),
+        ("builtinConstant", vec!["readonly", "defaultLibrary"]),
+        ("variable", vec!["unresolved"]),
+      ]
+    );
+  }
+
   #[test]
   fn comment() {
     assert_eq!(
@@ -555,7 +1032,7 @@ mod tests {
           line: 0,
           start: 0,
           length: 3,
-          modifiers: 0,
+          modifiers: Tokenizer::modifier_bitset(&["declaration", "readonly"]),
           token_type: "variable",
         },
         Expected {
@@ -661,7 +1138,7 @@ mod tests {
           start: 0,
           length: 3,
           modifiers: Tokenizer::modifier_bitset(&["declaration"]),
-          token_type: "function",
+          token_type: "recipe",
         },
         Expected {
           line: 0,
@@ -675,7 +1152,7 @@ mod tests {
           start: 0,
           length: 3,
           modifiers: Tokenizer::modifier_bitset(&["declaration"]),
-          token_type: "function",
+          token_type: "recipe",
         },
         Expected {
           line: 1,
@@ -689,12 +1166,54 @@ mod tests {
           start: 5,
           length: 3,
           modifiers: 0,
-          token_type: "function",
+          token_type: "recipe",
         },
       ]
     );
   }
 
+  #[test]
+  fn deprecated_builtin_function() {
+    assert_eq!(
+      symbols("foo := env_var('HOME') + shout()\n"),
+      [
+        ("variable", vec!["declaration", "readonly"]),
+        ("function", vec!["deprecated", "defaultLibrary"]),
+        ("function", vec!["unresolved"]),
+      ]
+    );
+  }
+
+  #[test]
+  fn edits() {
+    let tokenize = |source: &str| {
+      Tokenizer::new(&Document::from(source)).tokenize().unwrap()
+    };
+
+    let previous = tokenize("foo:\n\nbar:\n");
+
+    assert_eq!(Tokenizer::edits(&previous, &previous), []);
+
+    let current = tokenize("foo:\n\nbaz := '1'\n\nbar:\n");
+
+    let edits = Tokenizer::edits(&previous, &current);
+
+    assert_eq!(edits.len(), 1);
+
+    assert_eq!(edits[0].start, 10);
+    assert_eq!(edits[0].delete_count, 0);
+
+    let mut patched = previous.clone();
+
+    patched.splice(
+      (edits[0].start / 5) as usize
+        ..((edits[0].start + edits[0].delete_count) / 5) as usize,
+      edits[0].data.clone().unwrap(),
+    );
+
+    assert_eq!(patched, current);
+  }
+
   #[test]
   fn empty_document() {
     assert_eq!(tokenize(""), []);
@@ -821,7 +1340,7 @@ mod tests {
           line: 0,
           start: 0,
           length: 3,
-          modifiers: 0,
+          modifiers: Tokenizer::modifier_bitset(&["declaration", "readonly"]),
           token_type: "variable",
         },
         Expected {
@@ -872,7 +1391,11 @@ mod tests {
           line: 0,
           start: 7,
           length: 3,
-          modifiers: 0,
+          modifiers: Tokenizer::modifier_bitset(&[
+            "declaration",
+            "exported",
+            "readonly"
+          ]),
           token_type: "variable",
         },
         Expected {
@@ -893,6 +1416,26 @@ mod tests {
     );
   }
 
+  #[test]
+  fn exported_parameter() {
+    assert_eq!(
+      symbols(indoc! {
+        "
+        foo $bar baz:
+          echo {{ bar }} {{ baz }}
+        "
+      }),
+      [
+        ("recipe", vec!["declaration"]),
+        ("parameter", vec!["declaration", "exported"]),
+        ("parameter", vec!["declaration"]),
+        ("function", vec![]),
+        ("parameter", vec!["exported"]),
+        ("parameter", vec![]),
+      ]
+    );
+  }
+
   #[test]
   fn function_call() {
     assert_eq!(
@@ -902,7 +1445,7 @@ mod tests {
           line: 0,
           start: 0,
           length: 3,
-          modifiers: 0,
+          modifiers: Tokenizer::modifier_bitset(&["declaration", "readonly"]),
           token_type: "variable",
         },
         Expected {
@@ -916,7 +1459,7 @@ mod tests {
           line: 0,
           start: 7,
           length: 3,
-          modifiers: 0,
+          modifiers: Tokenizer::modifier_bitset(&["defaultLibrary"]),
           token_type: "function",
         },
         Expected {
@@ -967,6 +1510,82 @@ mod tests {
     );
   }
 
+  #[test]
+  fn injected_python_script() {
+    assert_eq!(
+      symbols(indoc! {
+        "
+        foo:
+          #!/usr/bin/env python3
+          print(None)
+        "
+      }),
+      [
+        ("recipe", vec!["declaration"]),
+        ("function", vec!["defaultLibrary"]),
+        ("builtinConstant", vec!["defaultLibrary"]),
+      ]
+    );
+  }
+
+  #[test]
+  fn injected_shell() {
+    assert_eq!(
+      symbols(indoc! {
+        "
+        foo dir:
+          ls -la {{ dir }} $HOME # list
+        "
+      }),
+      [
+        ("recipe", vec!["declaration"]),
+        ("parameter", vec!["declaration"]),
+        ("function", vec![]),
+        ("variable", vec!["readonly"]),
+        ("parameter", vec![]),
+        ("variable", vec![]),
+        ("comment", vec![]),
+      ]
+    );
+  }
+
+  #[test]
+  fn injected_shell_setting_language() {
+    assert_eq!(
+      symbols(indoc! {
+        "
+        set shell := ['python3', '-c']
+
+        foo:
+          print(1)
+        "
+      }),
+      [
+        ("setting", vec!["defaultLibrary"]),
+        ("recipe", vec!["declaration"]),
+        ("function", vec!["defaultLibrary"]),
+      ]
+    );
+  }
+
+  #[test]
+  fn injected_shell_unsupported_interpreter() {
+    assert_eq!(
+      symbols(indoc! {
+        "
+        set shell := ['pwsh', '-c']
+
+        foo:
+          Get-ChildItem
+        "
+      }),
+      [
+        ("setting", vec!["defaultLibrary"]),
+        ("recipe", vec!["declaration"]),
+      ]
+    );
+  }
+
   #[test]
   fn modifier_bitsets() {
     #[track_caller]
@@ -996,13 +1615,33 @@ mod tests {
           line: 0,
           start: 4,
           length: 3,
-          modifiers: 0,
-          token_type: "namespace",
+          modifiers: Tokenizer::modifier_bitset(&["declaration"]),
+          token_type: "module",
         },
       ]
     );
   }
 
+  #[test]
+  fn module_path_dependency() {
+    assert_eq!(
+      symbols(indoc! {
+        "
+        mod foo
+
+        bar: foo::baz
+        "
+      }),
+      [
+        ("namespace", vec![]),
+        ("module", vec!["declaration"]),
+        ("recipe", vec!["declaration"]),
+        ("module", vec![]),
+        ("recipe", vec![]),
+      ]
+    );
+  }
+
   #[test]
   fn multibyte_comment() {
     #[track_caller]
@@ -1059,20 +1698,20 @@ mod tests {
           start: 0,
           length: 3,
           modifiers: Tokenizer::modifier_bitset(&["declaration"]),
-          token_type: "function",
+          token_type: "recipe",
         },
         Expected {
           line: 0,
           start: 4,
           length: 3,
-          modifiers: 0,
+          modifiers: Tokenizer::modifier_bitset(&["declaration"]),
           token_type: "parameter",
         },
         Expected {
           line: 0,
           start: 8,
           length: 3,
-          modifiers: 0,
+          modifiers: Tokenizer::modifier_bitset(&["declaration"]),
           token_type: "parameter",
         },
         Expected {
@@ -1086,6 +1725,27 @@ mod tests {
     );
   }
 
+  #[test]
+  fn private_alias_and_unresolved_dependency() {
+    assert_eq!(
+      symbols(indoc! {
+        "
+        [private]
+        alias b := _build
+
+        _build: test
+        "
+      }),
+      [
+        ("decorator", vec![]),
+        ("alias", vec!["declaration", "private"]),
+        ("recipe", vec!["private"]),
+        ("recipe", vec!["declaration", "private"]),
+        ("recipe", vec!["unresolved"]),
+      ]
+    );
+  }
+
   #[test]
   fn recipe() {
     assert_eq!(
@@ -1096,7 +1756,7 @@ mod tests {
           start: 0,
           length: 3,
           modifiers: Tokenizer::modifier_bitset(&["declaration"]),
-          token_type: "function",
+          token_type: "recipe",
         },
         Expected {
           line: 0,
@@ -1124,7 +1784,7 @@ mod tests {
           start: 0,
           length: 3,
           modifiers: Tokenizer::modifier_bitset(&["declaration"]),
-          token_type: "function",
+          token_type: "recipe",
         },
         Expected {
           line: 0,
@@ -1163,6 +1823,87 @@ mod tests {
     case("boolean", 9);
   }
 
+  #[test]
+  fn tokenize_range() {
+    let document = Document::from(indoc! {
+      "
+      foo:
+
+      bar := 'a'
+
+      baz:
+      "
+    });
+
+    let tokens = Tokenizer::new(&document)
+      .tokenize_range(lsp::Range::at(2, 4, 2, 9))
+      .unwrap();
+
+    assert_eq!(
+      tokens,
+      [
+        lsp::SemanticToken {
+          delta_line: 2,
+          delta_start: 4,
+          length: 2,
+          token_modifiers_bitset: 0,
+          token_type: Tokenizer::token_type_index("operator"),
+        },
+        lsp::SemanticToken {
+          delta_line: 0,
+          delta_start: 3,
+          length: 3,
+          token_modifiers_bitset: 0,
+          token_type: Tokenizer::token_type_index("string"),
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn tokenize_range_matches_full_tokens() {
+    let document = Document::from(indoc! {
+      "
+      #!/usr/bin/env just --justfile
+
+      set export
+
+      # build things
+      [private]
+      build target='x86' *args: (lint target)
+        cc {{ target }} {{ args }} -o \"$HOME/out\"
+
+      lint target:
+        #!/usr/bin/env bash
+        echo \"linting {{ target }}\"
+
+      version := `git describe` + '-' + os()
+
+      mod? tools
+      "
+    });
+
+    assert!(!document.tree.as_ref().unwrap().root_node().has_error());
+
+    let tokenizer = Tokenizer::new(&document);
+
+    let full = tokenizer.tokenize().unwrap();
+
+    let lines = u32::try_from(document.content.len_lines()).unwrap();
+
+    for start in 0..lines {
+      for end in start..=(start + 3).min(lines) {
+        let range = lsp::Range::at(start, 0, end, 0);
+
+        assert_eq!(
+          tokenizer.tokenize_range(range).unwrap(),
+          Tokenizer::filter_range(&full, range),
+          "{range:?}",
+        );
+      }
+    }
+  }
+
   #[test]
   fn trailing_line_break() {
     #[track_caller]
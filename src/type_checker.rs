@@ -0,0 +1,479 @@
+use super::*;
+
+/// Infers whether just expressions produce strings or lists under
+/// `set lists`, and reports lists flowing into places that need a string, or
+/// the other way around.
+///
+/// Inference is deliberately shallow: anything whose type can't be
+/// determined locally, such as calls to user-defined functions, variadic
+/// parameters, or builtins that return either a string or `[]`, is unknown
+/// and never reported.
+#[derive(Debug)]
+pub struct TypeChecker<'a> {
+  view: &'a ProjectView<'a>,
+}
+
+impl<'a> TypeChecker<'a> {
+  /// The canonical name of the builtin function `name`, unless it is shadowed
+  /// by a user-defined function.
+  fn builtin(&self, name: &str) -> Option<&'static str> {
+    if self.view.find_function(name).is_some() {
+      return None;
+    }
+
+    BUILTINS.iter().find_map(|builtin| match builtin {
+      Builtin::Function {
+        name: builtin_name,
+        aliases,
+        ..
+      } if *builtin_name == name || aliases.contains(&name) => {
+        Some(*builtin_name)
+      }
+      _ => None,
+    })
+  }
+
+  /// Report every type mismatch in `document`.
+  #[must_use]
+  pub fn check(&self, document: &Document) -> Vec<Diagnostic> {
+    let Some(tree) = &document.tree else {
+      return Vec::new();
+    };
+
+    let root = tree.root_node();
+
+    let mut diagnostics = Vec::new();
+
+    for call in root.find_all("function_call") {
+      self.check_call(document, call, &mut diagnostics);
+    }
+
+    for expression in root.find_all("expression") {
+      self.check_operators(document, expression, &mut diagnostics);
+    }
+
+    for interpolation in root.find_all("interpolation") {
+      self.check_interpolation(document, interpolation, &mut diagnostics);
+    }
+
+    diagnostics.sort_by_key(|diagnostic| diagnostic.range.start);
+
+    diagnostics
+  }
+
+  fn check_call(
+    &self,
+    document: &Document,
+    call: Node,
+    diagnostics: &mut Vec<Diagnostic>,
+  ) {
+    let Some(name) = call
+      .child_by_field_name("name")
+      .and_then(|name| self.builtin(&document.get_node_text(&name)))
+    else {
+      return;
+    };
+
+    let Some(arguments) = call.child_by_field_name("arguments") else {
+      return;
+    };
+
+    let mut cursor = arguments.walk();
+
+    for (index, argument) in arguments.named_children(&mut cursor).enumerate() {
+      let Some(expected) = Self::parameter(name, index) else {
+        continue;
+      };
+
+      self.expect(
+        document,
+        argument,
+        expected,
+        &format!("Argument {} of `{name}()`", index + 1),
+        diagnostics,
+      );
+    }
+  }
+
+  fn check_interpolation(
+    &self,
+    document: &Document,
+    interpolation: Node,
+    diagnostics: &mut Vec<Diagnostic>,
+  ) {
+    let Some(expression) = interpolation.named_child(0) else {
+      return;
+    };
+
+    let subject = if interpolation.get_parent("format_string").is_some() {
+      "Format string interpolation"
+    } else if let Some(recipe) = interpolation
+      .get_parent("recipe_body")
+      .and_then(|_| interpolation.get_recipe(document))
+    {
+      if recipe.shebang.is_some() {
+        "Interpolation in a shebang recipe"
+      } else if recipe.find_attribute("script").is_some() {
+        "Interpolation in a script recipe"
+      } else {
+        return;
+      }
+    } else {
+      return;
+    };
+
+    self.expect(
+      document,
+      expression,
+      ValueType::String,
+      subject,
+      diagnostics,
+    );
+  }
+
+  fn check_operators(
+    &self,
+    document: &Document,
+    expression: Node,
+    diagnostics: &mut Vec<Diagnostic>,
+  ) {
+    let children = Self::children(expression);
+
+    let rest = match children.as_slice() {
+      [slash, rest @ ..] if slash.kind() == "/" => {
+        if let Some(found) = self.operation(document, rest, &mut Vec::new())
+          && found != ValueType::String
+          && let (Some(first), Some(last)) = (rest.first(), rest.last())
+        {
+          diagnostics.push(Self::mismatch(
+            "Operator `/`",
+            ValueType::String,
+            found,
+            lsp::Range {
+              start: first.get_range(document).start,
+              end: last.get_range(document).end,
+            },
+          ));
+        }
+
+        rest
+      }
+      children => children,
+    };
+
+    let [lhs, operator, rhs] = rest else {
+      return;
+    };
+
+    if !matches!(operator.kind(), "+" | "/" | "=~" | "!~") {
+      return;
+    }
+
+    let subject = format!("Operator `{}`", operator.kind());
+
+    for operand in [*lhs, *rhs] {
+      self.expect(document, operand, ValueType::String, &subject, diagnostics);
+    }
+  }
+
+  fn children(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+
+    node
+      .children(&mut cursor)
+      .filter(|child| child.kind() != "comment")
+      .collect()
+  }
+
+  fn expect(
+    &self,
+    document: &Document,
+    node: Node,
+    expected: ValueType,
+    subject: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+  ) {
+    if let Some(found) = self.expression(document, node, &mut Vec::new())
+      && found != expected
+    {
+      diagnostics.push(Self::mismatch(
+        subject,
+        expected,
+        found,
+        node.get_range(document),
+      ));
+    }
+  }
+
+  fn expression(
+    &self,
+    document: &Document,
+    node: Node,
+    stack: &mut Vec<String>,
+  ) -> Option<ValueType> {
+    match node.kind() {
+      "expression" => match Self::children(node).as_slice() {
+        [slash, ..] if slash.kind() == "/" => Some(ValueType::String),
+        children => self.operation(document, children, stack),
+      },
+      "external_command" | "regex_literal" | "string" => {
+        Some(ValueType::String)
+      }
+      "function_call" => {
+        let name = self.builtin(
+          &document.get_node_text(&node.child_by_field_name("name")?),
+        )?;
+
+        let arguments = match node.child_by_field_name("arguments") {
+          Some(arguments) => {
+            let mut cursor = arguments.walk();
+
+            arguments
+              .named_children(&mut cursor)
+              .map(|argument| self.expression(document, argument, stack))
+              .collect()
+          }
+          None => Vec::new(),
+        };
+
+        Self::returns(name, &arguments)
+      }
+      "identifier" => {
+        let name = document.get_node_text(&node);
+
+        if let Some(parameter) = node.get_recipe(document).and_then(|recipe| {
+          recipe
+            .parameters
+            .into_iter()
+            .find(|parameter| parameter.name == name)
+        }) {
+          return (parameter.kind == ParameterKind::Normal)
+            .then_some(ValueType::String);
+        }
+
+        if node.get_function(document).is_some_and(|function| {
+          function
+            .parameters
+            .iter()
+            .any(|parameter| parameter.value == name)
+        }) {
+          return None;
+        }
+
+        self.variable(&name, stack)
+      }
+      "if_expression" => {
+        let mut cursor = node.walk();
+
+        let mut bodies = once(node.child_by_field_name("body")?)
+          .chain(
+            node
+              .children_by_field_name("alternative", &mut cursor)
+              .filter_map(|alternative| {
+                alternative.child_by_field_name("body")
+              }),
+          )
+          .map(|body| self.expression(document, body, stack))
+          .collect::<Vec<_>>()
+          .into_iter();
+
+        let first = bodies.next()??;
+
+        bodies.all(|body| body == Some(first)).then_some(first)
+      }
+      "list_literal" => Some(ValueType::List),
+      "value" => {
+        let mut cursor = node.walk();
+
+        let inner = node
+          .named_children(&mut cursor)
+          .find(|child| child.kind() != "comment")?;
+
+        self.expression(document, inner, stack)
+      }
+      _ => None,
+    }
+  }
+
+  /// Infer the type of the expression `node` in `document`, or `None` if it
+  /// can't be determined.
+  #[must_use]
+  pub fn infer(&self, document: &Document, node: Node) -> Option<ValueType> {
+    self.expression(document, node, &mut Vec::new())
+  }
+
+  /// Infer the type of the variable `name`, as resolved through the project
+  /// view.
+  #[must_use]
+  pub fn infer_variable(&self, name: &str) -> Option<ValueType> {
+    self.variable(name, &mut Vec::new())
+  }
+
+  fn mismatch(
+    subject: &str,
+    expected: ValueType,
+    found: ValueType,
+    range: lsp::Range,
+  ) -> Diagnostic {
+    Diagnostic::error(
+      format!("{subject} expects a {expected}, found a {found}"),
+      range,
+    )
+  }
+
+  #[must_use]
+  pub fn new(view: &'a ProjectView<'a>) -> Self {
+    Self { view }
+  }
+
+  fn operation(
+    &self,
+    document: &Document,
+    children: &[Node],
+    stack: &mut Vec<String>,
+  ) -> Option<ValueType> {
+    match children {
+      [inner] => self.expression(document, *inner, stack),
+      [_, operator, _] => match operator.kind() {
+        "+" | "/" => Some(ValueType::String),
+        "++" => Some(ValueType::List),
+        _ => None,
+      },
+      _ => None,
+    }
+  }
+
+  /// The type the builtin `name` requires for its argument at `index`, if
+  /// it requires one.
+  ///
+  /// Builtins that apply to each list element, or that accept any value,
+  /// have no requirement.
+  fn parameter(name: &str, index: usize) -> Option<ValueType> {
+    match (name, index) {
+      ("join_list", 0) => Some(ValueType::List),
+      ("absolute_path" | "bool" | "env" | "len" | "quote" | "show", 0)
+      | ("append" | "prepend", 1)
+      | ("shell", 1..) => None,
+      _ => Some(ValueType::String),
+    }
+  }
+
+  /// The type returned by the builtin `name` for the given argument types.
+  fn returns(name: &str, arguments: &[Option<ValueType>]) -> Option<ValueType> {
+    match name {
+      "absolute_path" | "quote" => arguments.first().copied().flatten(),
+      "append" | "prepend" => arguments.get(1).copied().flatten(),
+      "bool" | "is_dependency" | "num_jobs" | "path_exists"
+      | "semver_matches" | "which" => None,
+      "split" => Some(ValueType::List),
+      _ => Some(ValueType::String),
+    }
+  }
+
+  fn variable(&self, name: &str, stack: &mut Vec<String>) -> Option<ValueType> {
+    if stack.iter().any(|visiting| visiting == name) {
+      return None;
+    }
+
+    let located = self.view.find_variable(name)?;
+
+    let document = self.view.find_document(&located.uri)?;
+
+    let root = document.tree.as_ref()?.root_node();
+
+    let assignment =
+      root.find_all("assignment").into_iter().find(|assignment| {
+        assignment.child_by_field_name("left").is_some_and(|left| {
+          left.get_range(document) == located.value.name.range
+        })
+      })?;
+
+    stack.push(name.to_string());
+
+    let value_type = self.expression(
+      document,
+      assignment.child_by_field_name("right")?,
+      stack,
+    );
+
+    stack.pop();
+
+    value_type
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, indoc::indoc, pretty_assertions::assert_eq};
+
+  #[track_caller]
+  fn case(content: &str, expected: Option<ValueType>) {
+    let document = Document::from(content);
+
+    let view = ProjectView::from(&document);
+
+    assert_eq!(TypeChecker::new(&view).infer_variable("foo"), expected);
+  }
+
+  #[test]
+  fn builtin_return_types() {
+    case("foo := split('a b')", Some(ValueType::List));
+    case("foo := join_list(['a', 'b'])", Some(ValueType::String));
+    case("foo := uppercase('a')", Some(ValueType::String));
+    case("foo := which('just')", None);
+  }
+
+  #[test]
+  fn concatenation() {
+    case("foo := 'a' + 'b'", Some(ValueType::String));
+    case("foo := ['a'] ++ ['b']", Some(ValueType::List));
+    case("foo := 'a' / 'b'", Some(ValueType::String));
+  }
+
+  #[test]
+  fn conditionals() {
+    case(
+      "foo := if 'a' == 'b' { ['a'] } else { ['b'] }",
+      Some(ValueType::List),
+    );
+    case("foo := if 'a' == 'b' { ['a'] } else { 'b' }", None);
+  }
+
+  #[test]
+  fn cyclic_variables_are_unknown() {
+    case("foo := bar\nbar := foo", None);
+  }
+
+  #[test]
+  fn literals() {
+    case("foo := 'a'", Some(ValueType::String));
+    case("foo := `echo a`", Some(ValueType::String));
+    case("foo := ['a', 'b']", Some(ValueType::List));
+  }
+
+  #[test]
+  fn mapped_builtins_follow_their_argument() {
+    case("foo := quote(['a'])", Some(ValueType::List));
+    case("foo := quote('a')", Some(ValueType::String));
+    case("foo := append('.txt', ['a'])", Some(ValueType::List));
+  }
+
+  #[test]
+  fn user_functions_are_unknown() {
+    case(
+      indoc! {
+        "
+        uppercase(x) := x
+
+        foo := uppercase('a')
+        "
+      },
+      None,
+    );
+  }
+
+  #[test]
+  fn variable_references() {
+    case("foo := bar\nbar := ['a']", Some(ValueType::List));
+  }
+}
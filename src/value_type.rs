@@ -0,0 +1,18 @@
+use super::*;
+
+/// The type of a value under `set lists`, as inferred by the
+/// [`TypeChecker`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ValueType {
+  List,
+  String,
+}
+
+impl Display for ValueType {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      ValueType::List => write!(f, "list"),
+      ValueType::String => write!(f, "string"),
+    }
+  }
+}
@@ -226,6 +226,52 @@ fn analyze_accepts_imported_recipe() -> Result {
     .run()
 }
 
+#[test]
+fn analyze_applies_config_and_reports_invalid_custom_rules() -> Result {
+  Test::new()?
+    .file(
+      "config.json",
+      indoc! {
+        r#"
+        {
+          "custom-rules": [
+            {
+              "id": "bad",
+              "message": "Bad",
+              "query": "(nonexistent_node)"
+            },
+            {
+              "id": "recipe-name",
+              "message": "Recipe `@name` is reported",
+              "name": "recipe name",
+              "query": "(recipe (recipe_header name: (identifier) @name) @diagnostic)"
+            }
+          ]
+        }
+        "#
+      },
+    )
+    .file("justfile", "foo:\n  echo foo\n")
+    .argument("--config")
+    .argument("config.json")
+    .argument("justfile")
+    .expected_stderr(
+      "warning: Invalid query for custom rule `bad`: Query error at 1:2. Invalid node type \"nonexistent_node\"\n",
+    )
+    .expected_stdout(indoc! {
+      "
+      warning[recipe-name]: recipe name
+         ╭─[ justfile:1:1 ]
+         │
+       1 │ foo:
+         │ ──┬─
+         │   ╰─── Recipe `foo` is reported
+      ───╯
+      "
+    })
+    .run()
+}
+
 #[test]
 fn analyze_errors_when_explicit_path_cannot_be_read() -> Result {
   Test::new()?